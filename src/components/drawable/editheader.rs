@@ -1,37 +1,60 @@
 use std::collections::HashMap;
 
 use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
+use crate::components::text_input::TextInput;
+use crate::core::commands::Completion;
 use crate::core::theme;
 
-pub fn draw(frame: &mut Frame, area: Rect, selection: u8, input_buffer: &mut HashMap<u8, String>) {
-    let mut header_name = "".to_string();
-    let mut header_value = "".to_string();
-    let areas: [Rect; 3] = Layout::horizontal([
-        Constraint::Ratio(3, 7),
-        Constraint::Ratio(3, 7),
-        Constraint::Ratio(1, 7),
+// the fields by sub_focus_element, the button comes after them
+const FIELDS: [&str; 3] = ["Name", "Value", "Description"];
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    selection: u8,
+    input_buffer: &mut HashMap<u8, TextInput>,
+    completion: Option<&Completion>,
+    is_new: bool,
+) {
+    let areas: [Rect; 4] = Layout::horizontal([
+        Constraint::Ratio(3, 10),
+        Constraint::Ratio(4, 10),
+        Constraint::Ratio(2, 10),
+        Constraint::Ratio(1, 10),
     ])
     .areas(area);
 
-    header_name.push_str(input_buffer.get(&0).unwrap_or(&"".to_string()));
-    header_value.push_str(input_buffer.get(&1).unwrap_or(&"".to_string()));
+    for (field, name) in FIELDS.iter().enumerate() {
+        let is_selected = selection as usize == field;
+        // the completions of the field, cycled through by completing again
+        let title = match completion {
+            Some(completion) if is_selected => {
+                format!("{} ({})", name, completion.candidates.join(" "))
+            }
+            _ => name.to_string(),
+        };
+        let block = theme::set_input_block(current_theme, is_selected).title(title);
+        let inner = block.inner(areas[field]);
+        frame.render_widget(block, areas[field]);
+        input_buffer.entry(field as u8).or_default().draw(
+            frame,
+            inner,
+            Style::default(),
+            is_selected,
+        );
+    }
 
+    let is_selected = selection as usize == FIELDS.len();
     frame.render_widget(
-        Paragraph::new(header_name).block(theme::set_input_block(selection == 0)),
-        *areas.get(0).unwrap(),
-    );
-    frame.render_widget(
-        Paragraph::new(header_value).block(theme::set_input_block(selection == 1)),
-        *areas.get(1).unwrap(),
-    );
-    frame.render_widget(
-        Paragraph::new("ADD")
+        Paragraph::new(if is_new { "ADD" } else { "SAVE" })
             .centered()
-            .block(theme::set_button_block(selection == 2))
-            .style(theme::set_button_style(selection == 2)),
-        *areas.get(2).unwrap(),
+            .block(theme::set_button_block(current_theme, is_selected))
+            .style(theme::set_button_style(current_theme, is_selected)),
+        areas[FIELDS.len()],
     );
 }
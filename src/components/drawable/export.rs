@@ -0,0 +1,73 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph, Tabs};
+use ratatui::Frame;
+use strum::IntoEnumIterator;
+
+use crate::components::structs::RequestStructure;
+use crate::core::enums::{Action, Popup, SnippetFormat, ThemeState, WidgetType};
+use crate::core::keymap::Keymap;
+use crate::core::{exporters, theme};
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    selected_format: usize,
+    request: &RequestStructure,
+) {
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title("Copy as")
+        .title_bottom(
+            Line::from(format!(
+                " {} ",
+                keymap.popup_hint(
+                    Popup::Export,
+                    &[
+                        (&[Action::PreviousFormat, Action::NextFormat], "format"),
+                        (&[Action::Copy], "copy"),
+                        (&[Action::Cancel], "close"),
+                    ]
+                )
+            ))
+            .right_aligned(),
+        );
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+
+    let [tabs_area, snippet_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+
+    let tabs = Tabs::new(SnippetFormat::iter().map(|format| format.to_string()))
+        .select(selected_format)
+        .divider("")
+        .style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Normal,
+                WidgetType::Tab,
+            )
+            .unwrap(),
+        )
+        .highlight_style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Focus,
+                WidgetType::Tab,
+            )
+            .unwrap(),
+        );
+    frame.render_widget(tabs, tabs_area);
+
+    let format = SnippetFormat::iter()
+        .nth(selected_format)
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(exporters::render(format, request)),
+        snippet_area,
+    );
+}
@@ -1,27 +1,107 @@
-use crate::core::enums::FocusedWindow;
+use crate::core::enums::{Action, FocusedWindow, InputMode};
+use crate::core::keymap::{format_keys, Keymap};
+use crate::core::theme;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Padding, Paragraph};
 use ratatui::Frame;
 
-pub fn draw(frame: &mut Frame, area: Rect, focus_window: &FocusedWindow) {
-    let keybindings_text = Line::from(vec![
-        Span::styled("Showing keybings for ", Style::default().fg(Color::Green)),
-        Span::styled(
-            focus_window.to_string(),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ),
-    ]);
-    let footer_text = match focus_window {
-        FocusedWindow::Collections => keybindings_text,
-        FocusedWindow::Request => keybindings_text,
-        FocusedWindow::Response => keybindings_text,
-        _ => Line::from(""),
+// the actions worth a reminder, in the order they are shown; which keys they are on
+// comes from the keymap
+fn relevant_actions(mode: &InputMode, window: &FocusedWindow) -> Vec<Action> {
+    let mut actions = match (mode, window) {
+        (InputMode::Normal, FocusedWindow::Collections) => vec![
+            Action::Open,
+            Action::OpenTab,
+            Action::Create,
+            Action::Delete,
+            Action::Rename,
+            Action::Duplicate,
+            Action::Move,
+            Action::Undo,
+            Action::Export,
+        ],
+        (InputMode::Normal, FocusedWindow::Request) => {
+            vec![
+                Action::Send,
+                Action::Edit,
+                Action::Create,
+                Action::Delete,
+                Action::ToggleHeader,
+                Action::PickMethod,
+                Action::EditUrl,
+                Action::MoveLeft,
+                Action::MoveRight,
+                Action::NextTab,
+                Action::CloseTab,
+                Action::Export,
+            ]
+        }
+        (InputMode::Normal, FocusedWindow::Editor) => vec![
+            Action::InsertMode,
+            Action::UndoEdit,
+            Action::RedoEdit,
+            Action::Save,
+            Action::CloseEditor,
+        ],
+        (InputMode::Normal, _) => vec![Action::MoveLeft, Action::MoveRight],
+        (InputMode::Insert, FocusedWindow::Editor) => vec![Action::NormalMode, Action::Save],
+        (InputMode::Insert, _) => vec![
+            Action::NextField,
+            Action::Complete,
+            Action::Submit,
+            Action::Cancel,
+        ],
+        (InputMode::Control, _) => vec![Action::Submit, Action::Complete, Action::Cancel],
     };
+    // the way to every other binding comes first, so it is never left out
+    if *mode == InputMode::Normal {
+        actions.insert(0, Action::Help);
+        actions.extend([Action::CommandMode, Action::SearchMode]);
+    }
+    actions
+}
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    mode: &InputMode,
+    focus_window: &FocusedWindow,
+) {
+    let key_style = Style::default()
+        .fg(Color::from(current_theme.focus.highlight))
+        .add_modifier(Modifier::BOLD);
+    let label_style = Style::default().fg(Color::from(current_theme.normal.foreground));
+
+    let mut spans = vec![Span::styled(
+        format!(" {} ", mode.to_string().to_uppercase()),
+        Style::default()
+            .fg(Color::from(current_theme.selection.foreground))
+            .bg(Color::from(current_theme.selection.background)),
+    )];
+    let mut width = spans[0].width();
+    let bindings = keymap.active_bindings(mode, focus_window);
+    for action in relevant_actions(mode, focus_window) {
+        let Some((keys, _)) = bindings.iter().find(|(_, bound)| *bound == action) else {
+            continue;
+        };
+        let hint = [
+            Span::styled(format!("  {}", format_keys(keys)), key_style),
+            Span::styled(format!(" {}", action), label_style),
+        ];
+        // hints which do not fit are left out rather than cut in half
+        width += hint.iter().map(Span::width).sum::<usize>();
+        if width > area.width.saturating_sub(1) as usize {
+            break;
+        }
+        spans.extend(hint);
+    }
 
     frame.render_widget(
-        Paragraph::new(footer_text).block(Block::new().padding(Padding::left(1))),
+        Paragraph::new(Line::from(spans)).block(Block::new().padding(Padding::left(1))),
         area,
     );
 }
@@ -1,8 +1,43 @@
 use ratatui::layout::Rect;
-use ratatui::widgets::{Block, Padding, Paragraph};
+use ratatui::widgets::{Block, Padding, Tabs};
 use ratatui::Frame;
 
-pub fn draw(frame: &mut Frame, area: Rect) {
-    let header_text = Paragraph::new("header text").block(Block::new().padding(Padding::left(1)));
-    frame.render_widget(header_text, area);
+use crate::core::enums::{ThemeState, WidgetType};
+use crate::core::theme;
+
+// the open requests, numbered and marked while they have unsaved changes
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    titles: &[(String, bool)],
+    active: usize,
+) {
+    let titles = titles
+        .iter()
+        .enumerate()
+        .map(|(index, (title, modified))| match modified {
+            true => format!("{} {} ●", index + 1, title),
+            false => format!("{} {}", index + 1, title),
+        });
+    let tabs = Tabs::new(titles)
+        .select(active)
+        .block(Block::new().padding(Padding::left(1)))
+        .style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Normal,
+                WidgetType::Tab,
+            )
+            .unwrap(),
+        )
+        .highlight_style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Focus,
+                WidgetType::Tab,
+            )
+            .unwrap(),
+        );
+    frame.render_widget(tabs, area);
 }
@@ -0,0 +1,108 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, Row, Table, TableState};
+use ratatui::Frame;
+use strum::{EnumMessage, IntoEnumIterator};
+
+use crate::core::enums::{Action, Popup, ThemeState, WidgetType};
+use crate::core::keymap::{format_keys, Keymap};
+use crate::core::theme;
+
+// keys, scope, action and description of every binding, actions without one included
+fn rows(keymap: &Keymap, filter: &str) -> Vec<[String; 4]> {
+    let filter = filter.to_lowercase();
+    Action::iter()
+        .flat_map(|action| {
+            let description = action.get_message().unwrap_or_default().to_string();
+            let bindings = keymap.bindings_of(action);
+            if bindings.is_empty() {
+                return vec![[
+                    "unbound".to_string(),
+                    String::new(),
+                    action.to_string(),
+                    description,
+                ]];
+            }
+            bindings
+                .into_iter()
+                .map(|(scope, keys)| {
+                    [
+                        format_keys(&keys),
+                        scope,
+                        action.to_string(),
+                        description.clone(),
+                    ]
+                })
+                .collect()
+        })
+        .filter(|row| {
+            row.iter()
+                .any(|column| column.to_lowercase().contains(&filter))
+        })
+        .collect()
+}
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    filter: &str,
+    table_state: &mut TableState,
+) {
+    let title = match filter {
+        "" => "Help".to_string(),
+        filter => format!("Help /{}", filter),
+    };
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .title_bottom(
+            Line::from(format!(
+                " type to search  {} ",
+                keymap.popup_hint(
+                    Popup::Help,
+                    &[
+                        (&[Action::MoveUp, Action::MoveDown], "scroll"),
+                        (&[Action::Cancel], "clear or close"),
+                    ]
+                )
+            ))
+            .right_aligned(),
+        );
+
+    let table = Table::new(
+        rows(keymap, filter).into_iter().map(Row::new),
+        [
+            Constraint::Length(12),
+            Constraint::Length(20),
+            Constraint::Length(18),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(
+        Row::new(["Keys", "Mode", "Action", "Description"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(block)
+    .style(
+        theme::match_color_theme_for_widgets(
+            current_theme.clone(),
+            ThemeState::Normal,
+            WidgetType::List,
+        )
+        .unwrap(),
+    )
+    .row_highlight_style(
+        theme::match_color_theme_for_widgets(
+            current_theme.clone(),
+            ThemeState::Focus,
+            WidgetType::List,
+        )
+        .unwrap(),
+    );
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_stateful_widget(table, area, table_state);
+}
@@ -0,0 +1,149 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, List, ListState, Padding, Paragraph, Wrap};
+use ratatui::Frame;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::core::enums::{Action, Popup, ThemeState, WidgetType};
+use crate::core::history::HistoryEntry;
+use crate::core::keymap::Keymap;
+use crate::core::{exporters, helpers, theme};
+
+// the request as it was sent and what came back, one after the other
+fn details<'a>(current_theme: &theme::Config, entry: &'a HistoryEntry) -> Vec<Line<'a>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let request = &entry.request;
+    let mut lines = vec![Line::styled(
+        format!(
+            "{} {}",
+            request.method.to_uppercase(),
+            exporters::full_url(request)
+        ),
+        bold,
+    )];
+    lines.extend(
+        request
+            .enabled_headers()
+            .map(|header| Line::from(format!("{}: {}", header.name, header.value))),
+    );
+    if !request.body.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(request.body.lines().map(Line::from));
+    }
+
+    lines.push(Line::from(""));
+    let environment = match &entry.environment {
+        Some(environment) => format!(", environment {}", environment),
+        None => String::new(),
+    };
+    match (&entry.response, &entry.error) {
+        (Some(response), _) => {
+            lines.push(Line::styled(
+                format!(
+                    "{} {} in {} ms{}",
+                    response.status, response.reason, entry.duration_ms, environment
+                ),
+                bold.fg(current_theme.status_color(response.status)),
+            ));
+            lines.extend(
+                response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| Line::from(format!("{}: {}", name, value))),
+            );
+            lines.push(Line::from(""));
+            lines.extend(
+                response
+                    .display_body()
+                    .lines()
+                    .map(|line| Line::from(line.to_string())),
+            );
+        }
+        (None, error) => lines.push(Line::styled(
+            format!(
+                "No response after {} ms{}: {}",
+                entry.duration_ms,
+                environment,
+                error.as_deref().unwrap_or_default()
+            ),
+            bold,
+        )),
+    }
+    lines
+}
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    entries: &[&HistoryEntry],
+    filter: &str,
+    list_state: &mut ListState,
+) {
+    let title = match filter {
+        "" => "History".to_string(),
+        filter => format!("History /{}", filter),
+    };
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .title_bottom(
+            Line::from(format!(
+                " {} ",
+                keymap.popup_hint(
+                    Popup::History,
+                    &[
+                        (&[Action::Submit], "replay"),
+                        (&[Action::SaveAsRequest], "save as request"),
+                        (&[Action::ExportHistory], "export as HAR"),
+                        (&[Action::Cancel], "close"),
+                    ]
+                )
+            ))
+            .right_aligned(),
+        );
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    let [list_area, details_area] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(inner);
+
+    let items = entries.iter().map(|entry| {
+        let sent_at = UNIX_EPOCH + Duration::from_millis(entry.sent_at);
+        // only the date and time of the ISO timestamp
+        let sent_at = helpers::format_timestamp(sent_at)
+            .get(..16)
+            .unwrap_or_default()
+            .replace('T', " ");
+        format!("{}  {}", sent_at, entry.summary())
+    });
+    let list = List::new(items)
+        .style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Normal,
+                WidgetType::List,
+            )
+            .unwrap(),
+        )
+        .highlight_style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Focus,
+                WidgetType::List,
+            )
+            .unwrap(),
+        );
+    frame.render_stateful_widget(list, list_area, list_state);
+
+    if let Some(entry) = list_state.selected().and_then(|index| entries.get(index)) {
+        frame.render_widget(
+            Paragraph::new(details(current_theme, entry))
+                .wrap(Wrap { trim: false })
+                .block(Block::new().padding(Padding::left(1))),
+            details_area,
+        );
+    }
+}
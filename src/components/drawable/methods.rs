@@ -0,0 +1,68 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, List, ListItem, ListState};
+use ratatui::Frame;
+
+use crate::core::enums::{Action, Popup, ThemeState, WidgetType};
+use crate::core::keymap::Keymap;
+use crate::core::theme;
+
+/// The methods offered for a request, anything else is typed in after picking `custom`.
+pub const METHODS: [&str; 8] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE",
+];
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    list_state: &mut ListState,
+) {
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title("Method")
+        .title_bottom(
+            Line::from(format!(
+                " {} ",
+                keymap.popup_hint(
+                    Popup::Method,
+                    &[(&[Action::Submit], "pick"), (&[Action::Cancel], "close")]
+                )
+            ))
+            .right_aligned(),
+        );
+
+    let items = METHODS
+        .iter()
+        .map(|method| {
+            ListItem::new(*method).style(
+                Style::default()
+                    .fg(current_theme.method_color(method))
+                    .add_modifier(Modifier::BOLD),
+            )
+        })
+        .chain(std::iter::once(ListItem::new("custom…")));
+    let list = List::new(items)
+        .block(block)
+        .style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Normal,
+                WidgetType::List,
+            )
+            .unwrap(),
+        )
+        .highlight_style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Focus,
+                WidgetType::List,
+            )
+            .unwrap(),
+        );
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_stateful_widget(list, area, list_state);
+}
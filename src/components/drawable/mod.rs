@@ -1,3 +1,10 @@
 pub mod editheader;
+pub mod export;
 pub mod footer;
 pub mod header;
+pub mod help;
+pub mod history;
+pub mod methods;
+pub mod trash;
+pub mod url;
+pub mod workspaces;
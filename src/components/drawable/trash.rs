@@ -0,0 +1,69 @@
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, List, ListState};
+use ratatui::Frame;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::core::enums::{Action, Popup, ThemeState, WidgetType};
+use crate::core::keymap::Keymap;
+use crate::core::trash::TrashEntry;
+use crate::core::{helpers, theme};
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    entries: &[TrashEntry],
+    list_state: &mut ListState,
+) {
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title("Trash")
+        .title_bottom(
+            Line::from(format!(
+                " {} ",
+                keymap.popup_hint(
+                    Popup::Trash,
+                    &[
+                        (&[Action::Restore], "restore"),
+                        (&[Action::Purge], "purge"),
+                        (&[Action::PurgeAll], "purge all"),
+                        (&[Action::Cancel], "close"),
+                    ]
+                )
+            ))
+            .right_aligned(),
+        );
+
+    let items = entries.iter().map(|entry| {
+        let deleted_at = UNIX_EPOCH + Duration::from_secs(entry.deleted_at);
+        // only the date and time of the ISO timestamp
+        let deleted_at = helpers::format_timestamp(deleted_at)
+            .get(..16)
+            .unwrap_or_default()
+            .replace('T', " ");
+        format!("{}  {}", deleted_at, entry.path)
+    });
+    let list = List::new(items)
+        .block(block)
+        .style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Normal,
+                WidgetType::List,
+            )
+            .unwrap(),
+        )
+        .highlight_style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Focus,
+                WidgetType::List,
+            )
+            .unwrap(),
+        );
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_stateful_widget(list, area, list_state);
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Paragraph};
+use ratatui::Frame;
+
+use crate::components::text_input::TextInput;
+use crate::core::{helpers, syntax, theme};
+
+/// The url of the request with its variables colored, or the field editing it. While the
+/// url is edited the title tells what keeps it from being sent.
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    url: &str,
+    input: Option<&mut TextInput>,
+    variables: &HashMap<String, String>,
+) {
+    let known = Style::default()
+        .fg(Color::from(current_theme.focus.highlight))
+        .add_modifier(Modifier::BOLD);
+    let unknown = Style::default()
+        .fg(Color::from(current_theme.error.foreground))
+        .add_modifier(Modifier::UNDERLINED);
+
+    let Some(input) = input else {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .style(Color::White);
+        frame.render_widget(
+            Paragraph::new(syntax::highlight_variables(url, variables, known, unknown))
+                .block(block),
+            area,
+        );
+        return;
+    };
+
+    let block = theme::set_input_block(current_theme, true);
+    let block = match helpers::validate_url(input.text(), variables) {
+        Ok(_) => block.title("URL"),
+        Err(err) => block
+            .title(format!("URL: {}", err))
+            .title_style(Style::default().fg(Color::from(current_theme.error.foreground))),
+    };
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let highlights: Vec<_> = syntax::find_variables(input.text())
+        .into_iter()
+        .map(|(range, name)| match variables.contains_key(&name) {
+            true => (range, known),
+            false => (range, unknown),
+        })
+        .collect();
+    input.draw_highlighted(
+        frame,
+        inner,
+        Style::default().fg(Color::from(current_theme.focus.foreground)),
+        true,
+        &highlights,
+    );
+}
@@ -0,0 +1,64 @@
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, List, ListState};
+use ratatui::Frame;
+use std::path::{Path, PathBuf};
+
+use crate::core::enums::{Action, Popup, ThemeState, WidgetType};
+use crate::core::keymap::Keymap;
+use crate::core::theme;
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    current_theme: &theme::Config,
+    keymap: &Keymap,
+    current: &Path,
+    workspaces: &[PathBuf],
+    list_state: &mut ListState,
+) {
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(format!("Workspaces  {}", current.display()))
+        .title_bottom(
+            Line::from(format!(
+                " {}  :workspace <path> ",
+                keymap.popup_hint(
+                    Popup::Workspace,
+                    &[(&[Action::Submit], "open"), (&[Action::Cancel], "close")]
+                )
+            ))
+            .right_aligned(),
+        );
+
+    let items = workspaces.iter().map(|root| {
+        // the opened workspace is marked like the opened folders of the tree
+        let icon = if root == current {
+            "\u{f07c}"
+        } else {
+            "\u{f07b}"
+        };
+        format!("{} {}", icon, root.display())
+    });
+    let list = List::new(items)
+        .block(block)
+        .style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Normal,
+                WidgetType::List,
+            )
+            .unwrap(),
+        )
+        .highlight_style(
+            theme::match_color_theme_for_widgets(
+                current_theme.clone(),
+                ThemeState::Focus,
+                WidgetType::List,
+            )
+            .unwrap(),
+        );
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_stateful_widget(list, area, list_state);
+}
@@ -1,26 +1,42 @@
-use crate::core::request_parser;
+use crate::components::structs::{Header, RequestStructure};
+use crate::components::text_input::TextInput;
 use anyhow::Result;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
+// puts the header of the edit fields into the request, over the header at `index` or as a
+// new one, and tells where it ended up
 pub fn request_widget_edit_headers_enter_event(
-    input_buffer: &HashMap<u8, String>,
-    file_path: &PathBuf,
-) -> Result<()> {
-    let mut data = request_parser::read_json_file(&file_path)?;
+    input_buffer: &HashMap<u8, TextInput>,
+    data: &mut RequestStructure,
+    index: Option<usize>,
+) -> Result<usize> {
+    let field = |key: u8| {
+        input_buffer
+            .get(&key)
+            .map(|input| input.text().trim().to_string())
+            .unwrap_or_default()
+    };
+    let name = field(0);
+    if !Header::is_valid_name(&name) {
+        anyhow::bail!("{:?} is not a valid header name", name);
+    }
 
-    // Update an existing field inside the nested object
-    data.headers.insert(
-        input_buffer.get(&0).unwrap().to_string(),
-        //json!(input_buffer.get(&1).unwrap()),
-        input_buffer.get(&1).unwrap().to_string(),
-    );
+    let index = match index.filter(|index| *index < data.headers.len()) {
+        // editing keeps whether the header is enabled
+        Some(index) => {
+            let header = &mut data.headers[index];
+            header.name = name;
+            header.value = field(1);
+            header.description = field(2);
+            index
+        }
+        None => {
+            let mut header = Header::new(name, field(1));
+            header.description = field(2);
+            data.headers.push(header);
+            data.headers.len() - 1
+        }
+    };
 
-    crate::core::helpers::logger("called");
-
-    // TODO:
-    // update the json and  overwrite the file
-    let _ = request_parser::update_json_file(&file_path, data)?;
-
-    Ok(())
+    Ok(index)
 }
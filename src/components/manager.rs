@@ -8,6 +8,7 @@ use ratatui::Frame;
 use super::structs::App;
 use super::widget_contents;
 use crate::core::enums::{RequestWidgetTabs, ResponseWidgetTabs};
+use crate::core::http::Response;
 use strum::IntoEnumIterator;
 
 pub fn match_request_widget_with_opened_tab<'a>(
@@ -18,7 +19,7 @@ pub fn match_request_widget_with_opened_tab<'a>(
 ) -> Result<(), Box<dyn Error>> {
     let selected_tab = RequestWidgetTabs::iter().nth(state.selected_tab).unwrap();
     match selected_tab {
-        RequestWidgetTabs::Body => widget_contents::body::render(),
+        RequestWidgetTabs::Body => widget_contents::body::render(state, frame, area),
         RequestWidgetTabs::Query => widget_contents::query::render(),
         RequestWidgetTabs::Header => {
             widget_contents::header::render(state, frame, scroll_items, area)
@@ -32,9 +33,10 @@ pub fn match_request_widget_with_opened_tab<'a>(
 
 pub fn match_response_widget_with_opened_tab<'a>(
     selected_tab: ResponseWidgetTabs,
+    response: Option<&Response>,
 ) -> Result<Paragraph<'a>, Box<dyn Error>> {
     match selected_tab {
-        ResponseWidgetTabs::ResponseBody => widget_contents::response_body::render(),
-        ResponseWidgetTabs::ResponseHeader => widget_contents::response_header::render(),
+        ResponseWidgetTabs::ResponseBody => widget_contents::response_body::render(response),
+        ResponseWidgetTabs::ResponseHeader => widget_contents::response_header::render(response),
     }
 }
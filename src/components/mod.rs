@@ -2,5 +2,7 @@ pub mod drawable;
 pub mod events;
 pub mod manager;
 pub mod structs;
+pub mod text_editor;
+pub mod text_input;
 pub mod widget_contents;
 pub mod widgets;
@@ -1,22 +1,51 @@
 use ratatui::layout::Rect;
-use ratatui::widgets::{ListState, Scrollbar, ScrollbarState};
-use serde::{Deserialize, Serialize};
+use ratatui::widgets::{ListState, Scrollbar, ScrollbarState, TableState};
+use serde::{Deserialize, Serialize, Serializer};
 
-use crate::core::enums::{FocusedWindow, InputMode, InputStrategy, LogTypes, WindowOperation};
+use crate::components::text_editor::TextEditor;
+use crate::components::text_input::TextInput;
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::commands::Completion;
+use crate::core::config::UserConfig;
+use crate::core::enums::{
+    BodyType, FocusedWindow, InputMode, InputStrategy, LogTypes, WindowOperation,
+};
+use crate::core::helpers;
+use crate::core::history::HistoryEntry;
+use crate::core::http::{self, Response};
+use crate::core::keymap::{KeyChord, Keymap};
+use crate::core::request_parser::LoadError;
 use crate::core::theme;
-use std::collections::HashMap;
+use crate::core::trash::TrashEntry;
+use crate::core::workspace::Workspace;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+use std::time::SystemTime;
 
 //App holds the state of the application
 #[derive(Default)]
 pub struct App {
+    // the file of the request shown, which may not be the one highlighted in the collections
+    pub request_path: Option<PathBuf>,
     pub request_data: RequestStructure,
+    // edits of the method, url, headers or options which are not saved yet, the body
+    // keeps its own in the editor
+    pub is_request_modified: bool,
+    // why the selected request could not be loaded, shown in place of the request
+    pub request_error: Option<LoadError>,
     pub rectangles: HashMap<String, Rect>,
-    pub input_buffer: HashMap<u8, String>,
+    // the fields of the header being edited, by sub_focus_element
+    pub input_buffer: HashMap<u8, TextInput>,
     pub theme: theme::Config,
+    pub keymap: Keymap,
+    // keys of a sequence like `g g` pressed so far
+    pub pending_keys: Vec<KeyChord>,
+    // the theme picked with `:theme`, and when its files changed to reload it on changes
+    pub theme_name: Option<String>,
+    pub theme_modified: Vec<Option<SystemTime>>,
     // Current value of the input box
-    pub input: String,
-    // Position of cursor in the editor area.
-    pub character_index: usize,
+    pub input: TextInput,
     // Current input mode
     pub input_mode: InputMode,
     // Input strategy
@@ -30,12 +59,21 @@ pub struct App {
     pub selected_collection: String,
     pub selected_request: String,
     pub show_collection_children: bool,
+    // requests of the opened collection which fail to load
+    pub broken_requests: HashSet<String>,
+    // folders of the opened collection listed in `collections`, and the expanded ones
+    pub collection_folders: HashSet<String>,
+    pub expanded_folders: HashSet<String>,
     // request
     pub selected_tab: usize,
-    pub vertical_scroll: usize,
     pub vertical_scroll_state: ScrollbarState,
+    pub header_table_state: TableState,
+    // the header the edit fields were filled from, none when adding a header
+    pub editing_header: Option<usize>,
     // response
     pub selected_response_tab: usize,
+    // what the server answered when the request was last sent
+    pub response: Option<Response>,
     // operation
     pub current_operation: WindowOperation,
     // common attr to decide which element to focus on
@@ -44,28 +82,190 @@ pub struct App {
     pub is_show_popup: bool,
     pub popup_msg: String,
     pub popup_type: LogTypes,
+    // copy as popup
+    pub is_show_export_popup: bool,
+    pub selected_export_format: usize,
+    pub export_request: RequestStructure,
+    // trash
+    pub undo_stack: Vec<String>,
+    pub delete_contents: Vec<String>,
+    pub is_show_trash_popup: bool,
+    pub trash_entries: Vec<TrashEntry>,
+    pub trash_list_state: ListState,
+    // the opened workspace, its collections are the ones listed
+    pub workspace: Workspace,
+    // the named environment picked with `:env`, only the shared variables are used without one
+    pub active_environment: Option<String>,
+    // workspace switcher
+    pub is_show_workspace_popup: bool,
+    pub recent_workspaces: Vec<PathBuf>,
+    pub workspace_list_state: ListState,
+    pub config: UserConfig,
+    // commands run from the `:` prompt, the oldest first
+    pub command_history: Vec<String>,
+    // the command of the history shown in the prompt, and what was typed before browsing
+    pub history_index: Option<usize>,
+    pub history_draft: String,
+    pub completion: Option<Completion>,
+    // the request body while it is being edited
+    pub body_editor: Option<TextEditor>,
+    pub is_show_help_popup: bool,
+    // typed while the help is open, narrows it down to the matching bindings
+    pub help_filter: String,
+    pub help_table_state: TableState,
+    pub is_show_method_popup: bool,
+    pub method_list_state: ListState,
+    // the url while it is edited in place
+    pub url_input: Option<TextInput>,
+    // the open requests, the shown one lives in the fields above while its tab is active
+    pub tabs: Vec<RequestTab>,
+    pub active_tab: usize,
+    // the sent requests, the most recent first
+    pub is_show_history_popup: bool,
+    pub request_history: Vec<HistoryEntry>,
+    // typed while the history is open, narrows it down to the matching requests
+    pub request_history_filter: String,
+    pub request_history_state: ListState,
+    pub http_client: http::Client,
+    // the request being sent, answered on another thread so the ui keeps going
+    pub pending_request: Option<PendingRequest>,
+}
+
+/// A request sent from a worker thread, whose entry arrives once it is answered.
+pub struct PendingRequest {
+    // the request it was sent from, the answer is shown in its tab
+    pub path: Option<PathBuf>,
+    // replays from the history only refresh the history
+    pub is_replay: bool,
+    pub receiver: Receiver<HistoryEntry>,
+}
+
+/// A request opened in a tab of the header bar, with everything it shows kept while
+/// another tab is active.
+#[derive(Default)]
+pub struct RequestTab {
+    pub path: Option<PathBuf>,
+    pub request_data: RequestStructure,
+    pub is_request_modified: bool,
+    pub request_error: Option<LoadError>,
+    // edits of the body which are not saved yet
+    pub body_editor: Option<TextEditor>,
+    pub response: Option<Response>,
+    pub selected_tab: usize,
+    pub header_table_state: TableState,
+}
+
+impl RequestTab {
+    pub fn is_modified(&self) -> bool {
+        self.is_request_modified
+            || self
+                .body_editor
+                .as_ref()
+                .is_some_and(|editor| editor.is_modified())
+    }
 }
 
 //pub struct RequestWidget {
 //    pub tabs: RequestWidgetTabs,
 //}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RequestStructure {
+    #[serde(default)]
+    pub version: u32,
     pub method: String,
     pub url: String,
-    pub headers: HashMap<String, String>,
+    pub headers: Vec<Header>,
+    #[serde(serialize_with = "sorted_map")]
     pub query_parameters: HashMap<String, String>,
-    pub body_type: String,
+    pub body_type: BodyType,
     pub body: String,
     pub options: RequestOptions,
+    // free form data kept alongside the request, eg. where it was generated from
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "sorted_map"
+    )]
+    pub metadata: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+impl Default for RequestStructure {
+    fn default() -> Self {
+        RequestStructure {
+            version: REQUEST_FILE_VERSION,
+            method: String::new(),
+            url: String::new(),
+            headers: vec![],
+            query_parameters: HashMap::new(),
+            body_type: BodyType::None,
+            body: String::new(),
+            options: RequestOptions::default(),
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+impl RequestStructure {
+    /// The headers which are sent, in their order.
+    pub fn enabled_headers(&self) -> impl Iterator<Item = &Header> {
+        self.headers.iter().filter(|header| header.enabled)
+    }
+
+    /// The value of the first enabled header called `name`, in any case.
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.enabled_headers()
+            .find(|header| header.name.eq_ignore_ascii_case(name))
+            .map(|header| header.value.as_str())
+    }
+}
+
+/// A header of a request. Names can repeat, like several `Accept` headers, and disabled
+/// headers stay in the request without being sent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Header {
+    pub name: String,
+    pub value: String,
+    #[serde(default = "is_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+fn is_enabled() -> bool {
+    true
+}
+
+impl Header {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Header {
+            name: name.into(),
+            value: value.into(),
+            enabled: true,
+            description: String::new(),
+        }
+    }
+
+    /// Whether `name` can be the name of a header.
+    pub fn is_valid_name(name: &str) -> bool {
+        helpers::is_token(name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct RequestOptions {
-    validate_ssl: bool,
-    follow_redirect: bool,
-    attach_cookies: bool,
-    proxy: String,
-    timeout: u8,
+    pub validate_ssl: bool,
+    pub follow_redirect: bool,
+    pub attach_cookies: bool,
+    pub proxy: String,
+    // 0 waits forever
+    pub timeout_ms: u64,
+}
+
+// request files are reviewed in diffs, so maps are written in a stable order
+fn sorted_map<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
 }
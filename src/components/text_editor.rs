@@ -0,0 +1,635 @@
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::core::{syntax, theme};
+
+// what tab inserts and an opened bracket adds to the next line
+const INDENT: &str = "  ";
+// how many undo steps are kept
+const UNDO_LIMIT: usize = 100;
+
+const BRACKETS: [(char, char); 3] = [('{', '}'), ('[', ']'), ('(', ')')];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    lines: Vec<String>,
+    row: usize,
+    column: usize,
+}
+
+/// A multi-line text editor with line numbers, soft wrap, undo and redo, used for request
+/// bodies and other long text. Whether keys insert text or move around is decided by the
+/// `InputMode` of the app, like the modes of vim.
+///
+/// Columns count characters rather than bytes.
+#[derive(Debug, Clone)]
+pub struct TextEditor {
+    lines: Vec<String>,
+    row: usize,
+    column: usize,
+    // the text as it was last saved, to tell whether there is anything to save
+    saved: Vec<String>,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    // the first row of the wrapped text shown
+    scroll: usize,
+}
+
+fn char_count(line: &str) -> usize {
+    line.chars().count()
+}
+
+fn byte_index(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+fn leading_whitespace(line: &str) -> String {
+    line.chars()
+        .take_while(|character| character.is_whitespace())
+        .collect()
+}
+
+impl TextEditor {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        TextEditor {
+            saved: lines.clone(),
+            lines,
+            row: 0,
+            column: 0,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            scroll: 0,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.lines != self.saved
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved = self.lines.clone();
+    }
+
+    fn line(&self) -> &str {
+        &self.lines[self.row]
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            lines: self.lines.clone(),
+            row: self.row,
+            column: self.column,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.lines = snapshot.lines;
+        self.row = snapshot.row;
+        self.column = snapshot.column;
+    }
+
+    /// Remembers the text before a change, so undo goes back to it. Everything typed in one
+    /// go in insert mode is a single step.
+    pub fn checkpoint(&mut self) {
+        let snapshot = self.snapshot();
+        if self.undo_stack.last() != Some(&snapshot) {
+            self.undo_stack.push(snapshot);
+            if self.undo_stack.len() > UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self) {
+        // a checkpoint taken without changing anything since is no step to undo
+        while let Some(snapshot) = self.undo_stack.pop() {
+            if snapshot.lines != self.lines {
+                self.redo_stack.push(self.snapshot());
+                self.restore(snapshot);
+                return;
+            }
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.redo_stack.pop() {
+            self.undo_stack.push(self.snapshot());
+            self.restore(snapshot);
+        }
+    }
+
+    fn clamp_column(&mut self) {
+        self.column = self.column.min(char_count(self.line()));
+    }
+
+    pub fn move_up(&mut self) {
+        self.row = self.row.saturating_sub(1);
+        self.clamp_column();
+    }
+
+    pub fn move_down(&mut self) {
+        self.row = (self.row + 1).min(self.lines.len() - 1);
+        self.clamp_column();
+    }
+
+    pub fn move_left(&mut self) {
+        self.column = self.column.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.column += 1;
+        self.clamp_column();
+    }
+
+    pub fn move_to_line_start(&mut self) {
+        self.column = 0;
+    }
+
+    pub fn move_to_line_end(&mut self) {
+        self.column = char_count(self.line());
+    }
+
+    // the first character of the line which is not indentation
+    pub fn move_to_indentation(&mut self) {
+        self.column = char_count(&leading_whitespace(self.line()));
+    }
+
+    pub fn move_to_first_line(&mut self) {
+        self.row = 0;
+        self.column = 0;
+    }
+
+    pub fn move_to_last_line(&mut self) {
+        self.row = self.lines.len() - 1;
+        self.column = 0;
+    }
+
+    // words are runs of letters, digits and underscores, moving past the end of a line goes
+    // on with the next one
+    pub fn move_word_right(&mut self) {
+        let characters: Vec<char> = self.line().chars().collect();
+        if self.column >= characters.len() {
+            if self.row + 1 < self.lines.len() {
+                self.row += 1;
+                self.move_to_indentation();
+            }
+            return;
+        }
+        let is_word = |character: char| character.is_alphanumeric() || character == '_';
+        let mut column = self.column;
+        let in_word = is_word(characters[column]);
+        while column < characters.len() && is_word(characters[column]) == in_word {
+            column += 1;
+        }
+        while column < characters.len() && characters[column].is_whitespace() {
+            column += 1;
+        }
+        self.column = column;
+    }
+
+    pub fn move_word_left(&mut self) {
+        if self.column == 0 {
+            if self.row > 0 {
+                self.row -= 1;
+                self.move_to_line_end();
+            }
+            return;
+        }
+        let characters: Vec<char> = self.line().chars().collect();
+        let is_word = |character: char| character.is_alphanumeric() || character == '_';
+        let mut column = self.column.min(characters.len());
+        while column > 0 && characters[column - 1].is_whitespace() {
+            column -= 1;
+        }
+        if column > 0 {
+            let in_word = is_word(characters[column - 1]);
+            while column > 0
+                && !characters[column - 1].is_whitespace()
+                && is_word(characters[column - 1]) == in_word
+            {
+                column -= 1;
+            }
+        }
+        self.column = column;
+    }
+
+    pub fn insert_char(&mut self, character: char) {
+        let index = byte_index(self.line(), self.column);
+        self.lines[self.row].insert(index, character);
+        self.column += 1;
+    }
+
+    /// Inserts pasted text, which may span several lines.
+    pub fn insert_text(&mut self, text: &str) {
+        self.checkpoint();
+        for (index, line) in text.lines().enumerate() {
+            if index > 0 {
+                self.split_line(String::new());
+            }
+            let byte = byte_index(self.line(), self.column);
+            self.lines[self.row].insert_str(byte, line);
+            self.column += char_count(line);
+        }
+    }
+
+    pub fn indent(&mut self) {
+        for character in INDENT.chars() {
+            self.insert_char(character);
+        }
+    }
+
+    // the rest of the line goes onto a new line starting with `indentation`
+    fn split_line(&mut self, indentation: String) {
+        let index = byte_index(self.line(), self.column);
+        let rest = self.lines[self.row].split_off(index);
+        self.row += 1;
+        self.column = char_count(&indentation);
+        self.lines.insert(self.row, indentation + &rest);
+    }
+
+    /// Breaks the line at the cursor, keeping its indentation. Between a pair of brackets
+    /// the closing one moves down a line and the cursor goes onto an indented line between.
+    pub fn new_line(&mut self) {
+        let indentation = leading_whitespace(self.line());
+        let characters: Vec<char> = self.line().chars().collect();
+        let before = self
+            .column
+            .checked_sub(1)
+            .and_then(|index| characters.get(index));
+        let after = characters.get(self.column);
+        let opens = before.is_some_and(|before| BRACKETS.iter().any(|(open, _)| open == before));
+
+        if !opens {
+            self.split_line(indentation);
+            return;
+        }
+        let closes = BRACKETS
+            .iter()
+            .any(|(open, close)| Some(open) == before && Some(close) == after);
+        self.split_line(format!("{}{}", indentation, INDENT));
+        if closes {
+            let column = self.column;
+            self.split_line(indentation);
+            self.row -= 1;
+            self.column = column;
+        }
+    }
+
+    pub fn open_line_below(&mut self) {
+        self.move_to_line_end();
+        self.new_line();
+    }
+
+    pub fn open_line_above(&mut self) {
+        let indentation = leading_whitespace(self.line());
+        self.column = char_count(&indentation);
+        self.lines.insert(self.row, indentation);
+    }
+
+    /// Deletes the character before the cursor, joining the line with the one above at its
+    /// start.
+    pub fn delete_before(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+            self.delete_after();
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.move_to_line_end();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// Deletes the character under the cursor, joining the next line at the end of a line.
+    pub fn delete_after(&mut self) {
+        let line = self.line();
+        if self.column < char_count(line) {
+            let start = byte_index(line, self.column);
+            let end = byte_index(line, self.column + 1);
+            self.lines[self.row].replace_range(start..end, "");
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    pub fn delete_word_before(&mut self) {
+        let end = self.column;
+        let row = self.row;
+        self.move_word_left();
+        if self.row != row {
+            // at the start of a line the line break is the word
+            self.row = row;
+            self.column = end;
+            self.delete_before();
+            return;
+        }
+        let start = byte_index(self.line(), self.column);
+        let end = byte_index(self.line(), end);
+        self.lines[self.row].replace_range(start..end, "");
+    }
+
+    pub fn delete_to_line_start(&mut self) {
+        let end = byte_index(self.line(), self.column);
+        self.lines[self.row].replace_range(..end, "");
+        self.column = 0;
+    }
+
+    pub fn delete_to_line_end(&mut self) {
+        let start = byte_index(self.line(), self.column);
+        self.lines[self.row].truncate(start);
+    }
+
+    pub fn delete_line(&mut self) {
+        self.checkpoint();
+        match self.lines.len() {
+            1 => self.lines[0].clear(),
+            _ => {
+                self.lines.remove(self.row);
+                self.row = self.row.min(self.lines.len() - 1);
+            }
+        }
+        self.move_to_indentation();
+    }
+
+    // the bracket matching the one under the cursor, or the one before it
+    fn matching_bracket(&self) -> Option<((usize, usize), (usize, usize))> {
+        let characters: Vec<char> = self.line().chars().collect();
+        let column = [Some(self.column), self.column.checked_sub(1)]
+            .into_iter()
+            .flatten()
+            .find(|column| {
+                characters.get(*column).is_some_and(|character| {
+                    BRACKETS
+                        .iter()
+                        .any(|(open, close)| open == character || close == character)
+                })
+            })?;
+        let bracket = characters[column];
+        let (open, close, forward) = BRACKETS.iter().find_map(|(open, close)| match bracket {
+            _ if bracket == *open => Some((*open, *close, true)),
+            _ if bracket == *close => Some((*open, *close, false)),
+            _ => None,
+        })?;
+
+        let mut depth = 0;
+        let mut position = (self.row, column);
+        loop {
+            let character = self.lines[position.0].chars().nth(position.1);
+            match character {
+                Some(character) if character == open => depth += 1,
+                Some(character) if character == close => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some(((self.row, column), position));
+            }
+            position = match forward {
+                true if position.1 + 1 < char_count(&self.lines[position.0]) => {
+                    (position.0, position.1 + 1)
+                }
+                true if position.0 + 1 < self.lines.len() => (position.0 + 1, 0),
+                false if position.1 > 0 => (position.0, position.1 - 1),
+                false if position.0 > 0 => (
+                    position.0 - 1,
+                    char_count(&self.lines[position.0 - 1]).saturating_sub(1),
+                ),
+                _ => return None,
+            };
+        }
+    }
+
+    pub fn jump_to_matching_bracket(&mut self) {
+        if let Some((_, (row, column))) = self.matching_bracket() {
+            self.row = row;
+            self.column = column;
+        }
+    }
+
+    /// Draws the text wrapped to the width of `area` with line numbers in front, keeping the
+    /// cursor in view. JSON gets its syntax colored.
+    pub fn draw(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        current_theme: &theme::Config,
+        is_json: bool,
+        is_editing: bool,
+    ) {
+        let gutter = self.lines.len().to_string().len() + 1;
+        let width = (area.width as usize).saturating_sub(gutter);
+        let height = area.height as usize;
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let brackets = self.matching_bracket();
+        let bracket_style = Style::default().add_modifier(Modifier::REVERSED);
+        let number_style = Style::default().fg(Color::from(current_theme.normal.border));
+
+        // every character with its style, so the lines can be wrapped anywhere
+        let mut rows: Vec<Line> = vec![];
+        let mut cursor = (0, 0);
+        for (row, line) in self.lines.iter().enumerate() {
+            let mut cells: Vec<(char, Style)> = match is_json {
+                true => syntax::highlight_json(line, &current_theme.syntax)
+                    .into_iter()
+                    .flat_map(|line| line.spans)
+                    .flat_map(|span| {
+                        let style = span.style;
+                        span.content
+                            .chars()
+                            .map(move |character| (character, style))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect(),
+                false => line
+                    .chars()
+                    .map(|character| (character, Style::default()))
+                    .collect(),
+            };
+            for (bracket_row, column) in brackets.iter().flat_map(|(a, b)| [*a, *b]) {
+                if let Some(cell) = cells.get_mut(column).filter(|_| bracket_row == row) {
+                    cell.1 = cell.1.patch(bracket_style);
+                }
+            }
+
+            if row == self.row {
+                let column = self.column.min(cells.len());
+                cursor = (rows.len() + column / width, column % width);
+            }
+            let chunks: Vec<&[(char, Style)]> = match cells.is_empty() {
+                true => vec![&[]],
+                false => cells.chunks(width).collect(),
+            };
+            for (index, chunk) in chunks.iter().enumerate() {
+                let number = match index {
+                    0 => format!("{:>1$} ", row + 1, gutter - 1),
+                    _ => " ".repeat(gutter),
+                };
+                let mut spans = vec![Span::styled(number, number_style)];
+                spans.extend(
+                    chunk
+                        .iter()
+                        .map(|(character, style)| Span::styled(character.to_string(), *style)),
+                );
+                rows.push(Line::from(spans));
+            }
+        }
+
+        if cursor.0 < self.scroll {
+            self.scroll = cursor.0;
+        } else if cursor.0 >= self.scroll + height {
+            self.scroll = cursor.0 + 1 - height;
+        }
+        let visible: Vec<Line> = rows.into_iter().skip(self.scroll).take(height).collect();
+        frame.render_widget(Paragraph::new(visible), area);
+
+        if is_editing {
+            #[allow(clippy::cast_possible_truncation)]
+            frame.set_cursor_position(Position::new(
+                area.x + (gutter + cursor.1) as u16,
+                area.y + (cursor.0 - self.scroll) as u16,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(editor: &mut TextEditor, text: &str) {
+        for character in text.chars() {
+            match character {
+                '\n' => editor.new_line(),
+                character => editor.insert_char(character),
+            }
+        }
+    }
+
+    #[test]
+    fn new_lines_keep_the_indentation_and_open_brackets() {
+        let mut editor = TextEditor::new("");
+        type_text(&mut editor, "{}");
+        editor.move_left();
+        type_text(&mut editor, "\n\"a\": [\n1,\n2");
+        assert_eq!(editor.text(), "{\n  \"a\": [\n    1,\n    2\n}");
+        assert_eq!((editor.row, editor.column), (3, 5));
+
+        // the cursor lands on the bracket matching the one before it
+        editor.move_to_first_line();
+        editor.move_right();
+        editor.jump_to_matching_bracket();
+        assert_eq!((editor.row, editor.column), (4, 0));
+    }
+
+    #[test]
+    fn deletes_across_lines() {
+        let mut editor = TextEditor::new("héllo\n  wörld");
+        editor.move_down();
+        editor.move_to_indentation();
+        assert_eq!(editor.column, 2);
+        editor.delete_to_line_start();
+        editor.delete_before();
+        assert_eq!(editor.text(), "héllowörld");
+        assert_eq!((editor.row, editor.column), (0, 5));
+        editor.delete_word_before();
+        assert_eq!(editor.text(), "wörld");
+        editor.move_to_line_end();
+        editor.delete_after();
+        editor.move_left();
+        editor.delete_to_line_end();
+        assert_eq!(editor.text(), "wörl");
+
+        let mut editor = TextEditor::new("one\ntwo");
+        editor.move_down();
+        editor.delete_word_before();
+        assert_eq!(editor.text(), "onetwo");
+    }
+
+    #[test]
+    fn moves_by_word_across_lines() {
+        let mut editor = TextEditor::new("let a_b = c.d;\n  next");
+        editor.move_word_right();
+        assert_eq!(editor.column, 4);
+        editor.move_word_right();
+        assert_eq!(editor.column, 8);
+        editor.move_to_line_end();
+        editor.move_word_right();
+        assert_eq!((editor.row, editor.column), (1, 2));
+        editor.move_to_line_start();
+        editor.move_word_left();
+        assert_eq!((editor.row, editor.column), (0, 14));
+        editor.move_word_left();
+        assert_eq!(editor.column, 13);
+        editor.move_word_left();
+        assert_eq!(editor.column, 12);
+    }
+
+    #[test]
+    fn undoes_a_whole_insert_at_once() {
+        let mut editor = TextEditor::new("a");
+        editor.move_to_line_end();
+        editor.checkpoint();
+        type_text(&mut editor, "bc");
+        editor.checkpoint();
+        editor.insert_text("d\ne");
+        assert_eq!(editor.text(), "abcd\ne");
+        assert!(editor.is_modified());
+
+        editor.undo();
+        assert_eq!(editor.text(), "abc");
+        editor.undo();
+        assert_eq!(editor.text(), "a");
+        assert_eq!(editor.column, 1);
+        // nothing is left to undo
+        editor.undo();
+        assert_eq!(editor.text(), "a");
+        assert!(!editor.is_modified());
+
+        editor.redo();
+        editor.redo();
+        assert_eq!(editor.text(), "abcd\ne");
+        editor.mark_saved();
+        assert!(!editor.is_modified());
+
+        // a change drops what could be redone
+        editor.undo();
+        editor.delete_line();
+        editor.redo();
+        assert_eq!(editor.text(), "");
+        editor.undo();
+        assert_eq!(editor.text(), "abc");
+    }
+
+    #[test]
+    fn opens_lines_around_the_cursor() {
+        let mut editor = TextEditor::new("  a");
+        editor.open_line_below();
+        editor.insert_char('b');
+        editor.move_up();
+        editor.open_line_above();
+        editor.insert_char('c');
+        assert_eq!(editor.text(), "  c\n  a\n  b");
+        editor.move_to_last_line();
+        editor.delete_line();
+        editor.delete_line();
+        editor.delete_line();
+        assert_eq!(editor.text(), "");
+    }
+}
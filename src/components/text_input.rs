@@ -0,0 +1,378 @@
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use std::ops::Range;
+
+/// A single line of editable text with a cursor, an optional selection and horizontal
+/// scrolling, used by every input field.
+///
+/// Positions count characters rather than bytes, since a character can take several bytes.
+#[derive(Debug, Default, Clone)]
+pub struct TextInput {
+    text: String,
+    cursor: usize,
+    // where the selection started, it ends at the cursor
+    anchor: Option<usize>,
+    // the first character shown, keeps the cursor inside the field
+    scroll: usize,
+}
+
+fn is_word_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+impl TextInput {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text and puts the cursor at its end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.len();
+        self.anchor = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text("");
+        self.scroll = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, position: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(position)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+
+    /// The selected characters as a range of positions, when anything is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.cursor)?;
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    // moving with shift held grows the selection, moving without it drops the selection
+    fn move_to(&mut self, position: usize, select: bool) {
+        match select {
+            true => {
+                self.anchor.get_or_insert(self.cursor);
+            }
+            false => self.anchor = None,
+        }
+        self.cursor = position.min(self.len());
+    }
+
+    fn remove_range(&mut self, start: usize, end: usize) {
+        let range = self.byte_index(start)..self.byte_index(end);
+        self.text.replace_range(range, "");
+        self.cursor = start;
+        self.anchor = None;
+    }
+
+    // typing and deleting replace the selection
+    fn remove_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                self.remove_range(start, end);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn insert_char(&mut self, character: char) {
+        self.remove_selection();
+        let index = self.byte_index(self.cursor);
+        self.text.insert(index, character);
+        self.cursor += 1;
+    }
+
+    /// Inserts pasted text. The field holds one line, so the lines are joined with spaces,
+    /// dropping the `\` which continues a shell command onto the next line.
+    pub fn insert_str(&mut self, text: &str) {
+        self.remove_selection();
+        let line = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line.trim_end().trim_end_matches('\\').trim_end();
+                // the indentation of continued lines is not part of the text
+                match index {
+                    0 => line,
+                    _ => line.trim_start(),
+                }
+            })
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let index = self.byte_index(self.cursor);
+        self.text.insert_str(index, &line);
+        self.cursor += line.chars().count();
+    }
+
+    pub fn move_left(&mut self, select: bool) {
+        // without shift the cursor leaves a selection at its start
+        let position = match (self.selection(), select) {
+            (Some((start, _)), false) => start,
+            _ => self.cursor.saturating_sub(1),
+        };
+        self.move_to(position, select);
+    }
+
+    pub fn move_right(&mut self, select: bool) {
+        let position = match (self.selection(), select) {
+            (Some((_, end)), false) => end,
+            _ => self.cursor + 1,
+        };
+        self.move_to(position, select);
+    }
+
+    pub fn move_to_start(&mut self, select: bool) {
+        self.move_to(0, select);
+    }
+
+    pub fn move_to_end(&mut self, select: bool) {
+        self.move_to(self.len(), select);
+    }
+
+    // the start of the word before the cursor, skipping the separators in between
+    fn word_start_before(&self) -> usize {
+        let characters: Vec<char> = self.text.chars().take(self.cursor).collect();
+        let mut position = characters.len();
+        while position > 0 && !is_word_character(characters[position - 1]) {
+            position -= 1;
+        }
+        while position > 0 && is_word_character(characters[position - 1]) {
+            position -= 1;
+        }
+        position
+    }
+
+    fn word_end_after(&self) -> usize {
+        let characters: Vec<char> = self.text.chars().collect();
+        let mut position = self.cursor;
+        while position < characters.len() && !is_word_character(characters[position]) {
+            position += 1;
+        }
+        while position < characters.len() && is_word_character(characters[position]) {
+            position += 1;
+        }
+        position
+    }
+
+    pub fn move_word_left(&mut self, select: bool) {
+        self.move_to(self.word_start_before(), select);
+    }
+
+    pub fn move_word_right(&mut self, select: bool) {
+        self.move_to(self.word_end_after(), select);
+    }
+
+    /// Deletes the character before the cursor, like backspace.
+    pub fn delete_before(&mut self) {
+        if !self.remove_selection() && self.cursor > 0 {
+            self.remove_range(self.cursor - 1, self.cursor);
+        }
+    }
+
+    /// Deletes the character under the cursor, like the delete key.
+    pub fn delete_after(&mut self) {
+        if !self.remove_selection() && self.cursor < self.len() {
+            self.remove_range(self.cursor, self.cursor + 1);
+        }
+    }
+
+    pub fn delete_word_before(&mut self) {
+        if !self.remove_selection() {
+            self.remove_range(self.word_start_before(), self.cursor);
+        }
+    }
+
+    pub fn delete_to_start(&mut self) {
+        self.remove_range(0, self.cursor);
+    }
+
+    pub fn delete_to_end(&mut self) {
+        let end = self.len();
+        self.remove_range(self.cursor, end);
+    }
+
+    /// Draws the part of the text around the cursor, and the cursor itself when the field
+    /// is being edited.
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect, style: Style, is_editing: bool) {
+        self.draw_highlighted(frame, area, style, is_editing, &[]);
+    }
+
+    /// Draws like [`TextInput::draw`], with the given ranges of characters styled.
+    pub fn draw_highlighted(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        style: Style,
+        is_editing: bool,
+        highlights: &[(Range<usize>, Style)],
+    ) {
+        let width = area.width as usize;
+        if width == 0 {
+            return;
+        }
+        // one column is left for the cursor behind the last character
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + width {
+            self.scroll = self.cursor + 1 - width;
+        }
+        self.scroll = self.scroll.min(self.len().saturating_sub(width - 1));
+
+        let (start, end) = self.selection().unwrap_or((self.cursor, self.cursor));
+        let visible = |from: usize, to: usize| -> String {
+            let from = from.max(self.scroll);
+            let to = to.min(self.scroll + width);
+            match from < to {
+                true => self.text.chars().skip(from).take(to - from).collect(),
+                false => String::new(),
+            }
+        };
+        let line = Line::from(vec![
+            Span::raw(visible(0, start)),
+            Span::styled(
+                visible(start, end),
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(visible(end, self.len())),
+        ]);
+        frame.render_widget(Paragraph::new(line).style(style), area);
+        for (range, highlight) in highlights {
+            let start = range.start.max(self.scroll);
+            let end = range.end.min(self.scroll + width);
+            if start < end {
+                #[allow(clippy::cast_possible_truncation)]
+                let visible = Rect::new(
+                    area.x + (start - self.scroll) as u16,
+                    area.y,
+                    (end - start) as u16,
+                    1,
+                );
+                frame.buffer_mut().set_style(visible, *highlight);
+            }
+        }
+
+        if is_editing {
+            #[allow(clippy::cast_possible_truncation)]
+            frame.set_cursor_position(Position::new(
+                area.x + (self.cursor - self.scroll) as u16,
+                area.y,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn input(text: &str) -> TextInput {
+        let mut input = TextInput::default();
+        input.set_text(text);
+        input
+    }
+
+    #[test]
+    fn edits_characters_not_bytes() {
+        let mut input = input("héllo");
+        input.move_left(false);
+        input.move_left(false);
+        input.insert_char('ü');
+        assert_eq!(input.text(), "hélülo");
+        input.move_to_start(false);
+        input.move_right(false);
+        input.delete_after();
+        assert_eq!(input.text(), "hlülo");
+        input.delete_before();
+        input.delete_before();
+        assert_eq!(input.text(), "lülo");
+        input.move_to_end(false);
+        input.delete_after();
+        assert_eq!(input.text(), "lülo");
+    }
+
+    #[test]
+    fn typing_replaces_the_selection() {
+        let mut input = input("hello world");
+        input.move_word_left(true);
+        assert_eq!(input.selection(), Some((6, 11)));
+        input.insert_char('x');
+        assert_eq!(input.text(), "hello x");
+        assert_eq!(input.selection(), None);
+
+        // moving without shift leaves the selection at its side
+        input.move_to_start(true);
+        input.move_right(false);
+        assert_eq!(input.cursor, 7);
+        input.move_left(true);
+        input.move_left(true);
+        input.move_left(false);
+        assert_eq!(input.cursor, 5);
+        assert_eq!(input.selection(), None);
+
+        input.move_to_start(false);
+        input.move_right(true);
+        input.delete_before();
+        assert_eq!(input.text(), "ello x");
+    }
+
+    #[test]
+    fn moves_and_deletes_by_word() {
+        let mut input = input("GET https://api.example.com/users_list");
+        input.delete_word_before();
+        assert_eq!(input.text(), "GET https://api.example.com/");
+        input.move_word_left(false);
+        input.move_word_left(false);
+        assert_eq!(input.cursor, 16);
+        input.move_word_right(false);
+        assert_eq!(input.cursor, 23);
+        input.delete_to_end();
+        assert_eq!(input.text(), "GET https://api.example");
+        input.move_word_left(false);
+        input.delete_to_start();
+        assert_eq!(input.text(), "example");
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn pastes_continued_lines_as_one() {
+        let mut input = input("");
+        input.insert_str("curl https://example.com \\\n    -H 'Accept: */*' \\\n\n  -d '{}'\n");
+        assert_eq!(
+            input.text(),
+            "curl https://example.com -H 'Accept: */*' -d '{}'"
+        );
+        assert_eq!(input.cursor, input.len());
+    }
+
+    #[test]
+    fn scrolls_to_keep_the_cursor_visible() {
+        let mut terminal = Terminal::new(TestBackend::new(5, 1)).unwrap();
+        let mut input = input("abcdefgh");
+        let mut draw = |input: &mut TextInput| {
+            terminal
+                .draw(|frame| input.draw(frame, frame.area(), Style::default(), true))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..5).map(|x| buffer[(x, 0)].symbol()).collect::<String>()
+        };
+        assert_eq!(draw(&mut input), "efgh ");
+        input.move_to_start(false);
+        assert_eq!(draw(&mut input), "abcde");
+        input.move_word_right(false);
+        assert_eq!(draw(&mut input), "efgh ");
+    }
+}
@@ -1,7 +1,30 @@
+use ratatui::layout::Rect;
+use ratatui::text::Text;
 use ratatui::widgets::Paragraph;
+use ratatui::Frame;
 
-pub fn render<'a>() -> Result<(), Box<dyn std::error::Error>> {
-    Paragraph::new("");
+use crate::components::structs::App;
+use crate::core::enums::{BodyType, FocusedWindow};
+use crate::core::syntax;
 
+pub fn render(
+    state: &mut App,
+    frame: &mut Frame,
+    area: Rect,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let is_json = state.request_data.body_type == BodyType::Json;
+    let is_editing = state.focused_window == FocusedWindow::Editor;
+    if let Some(editor) = state.body_editor.as_mut() {
+        editor.draw(frame, area, &state.theme, is_json, is_editing);
+        return Ok(());
+    }
+
+    let body = &state.request_data.body;
+    let text = match state.request_data.body_type {
+        BodyType::Json => Text::from(syntax::highlight_json(body, &state.theme.syntax)),
+        _ => Text::from(body.as_str()),
+    };
+
+    frame.render_widget(Paragraph::new(text), area);
     Ok(())
 }
@@ -1,27 +1,62 @@
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::Line;
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation};
+use ratatui::widgets::{Row, Scrollbar, ScrollbarOrientation, Table};
 use ratatui::Frame;
 
 use crate::components::structs::App;
+use crate::core::enums::{FocusedWindow, ThemeState, WidgetType};
+use crate::core::theme;
 
 pub fn render<'a>(
     state: &mut App,
     frame: &mut Frame,
-    mut scroll_items: Vec<Line>,
+    _scroll_items: Vec<Line>,
     area: Rect,
 ) -> Result<(), Box<dyn std::error::Error>> {
     state.vertical_scroll_state = state
         .vertical_scroll_state
-        .content_length(state.request_data.headers.len());
+        .content_length(state.request_data.headers.len())
+        .position(state.header_table_state.selected().unwrap_or_default());
 
-    // render header values onto terminal
-    for header in state.request_data.headers.clone() {
-        scroll_items.push(Line::from(format!("{}\t\t{}", header.0, header.1)));
-    }
-
-    let paragraph = Paragraph::new(scroll_items).scroll((0, 0));
-    frame.render_widget(paragraph, area);
+    // render header values onto terminal, disabled ones dimmed
+    let rows = state.request_data.headers.iter().map(|header| {
+        let row = Row::new([
+            match header.enabled {
+                true => "[x]".to_string(),
+                false => "[ ]".to_string(),
+            },
+            header.name.clone(),
+            header.value.clone(),
+            header.description.clone(),
+        ]);
+        match header.enabled {
+            true => row,
+            false => row.style(Style::default().add_modifier(Modifier::DIM)),
+        }
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Fill(2),
+            Constraint::Fill(3),
+            Constraint::Fill(2),
+        ],
+    );
+    // the selection only shows while the headers can be worked on
+    let table = match state.focused_window {
+        FocusedWindow::Request => table.row_highlight_style(
+            theme::match_color_theme_for_widgets(
+                state.theme.clone(),
+                ThemeState::Focus,
+                WidgetType::List,
+            )
+            .unwrap(),
+        ),
+        _ => table,
+    };
+    frame.render_stateful_widget(table, area, &mut state.header_table_state);
 
     frame.render_stateful_widget(
         Scrollbar::new(ScrollbarOrientation::VerticalRight)
@@ -1,7 +1,12 @@
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Wrap};
 
-pub fn render<'a>() -> Result<Paragraph<'a>, Box<dyn std::error::Error>> {
-    Ok(Paragraph::new("Body").block(
+use crate::core::http::Response;
+
+pub fn render<'a>(
+    response: Option<&Response>,
+) -> Result<Paragraph<'a>, Box<dyn std::error::Error>> {
+    let body = response.map(Response::display_body).unwrap_or_default();
+    Ok(Paragraph::new(body).wrap(Wrap { trim: false }).block(
         Block::bordered()
             .border_type(BorderType::Rounded)
             .borders(Borders::TOP),
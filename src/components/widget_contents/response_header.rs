@@ -1,7 +1,21 @@
+use ratatui::text::Line;
 use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 
-pub fn render<'a>() -> Result<Paragraph<'a>, Box<dyn std::error::Error>> {
-    Ok(Paragraph::new("Header").block(
+use crate::core::http::Response;
+
+pub fn render<'a>(
+    response: Option<&Response>,
+) -> Result<Paragraph<'a>, Box<dyn std::error::Error>> {
+    let headers: Vec<Line> = response
+        .map(|response| {
+            response
+                .headers
+                .iter()
+                .map(|(name, value)| Line::from(format!("{}: {}", name, value)))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Paragraph::new(headers).block(
         Block::bordered()
             .border_type(BorderType::Rounded)
             .borders(Borders::TOP),
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
 use ratatui::text::Line;
-use ratatui::widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Tabs};
+use ratatui::widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Tabs, Wrap};
 use ratatui::Frame;
 use strum::IntoEnumIterator;
 
@@ -19,7 +20,10 @@ pub fn draw_request_widget(frame: &mut Frame, state: &mut App, area: Rect) {
         .select(state.selected_tab)
         .block(
             theme::set_border_style(
-                state.focused_window == FocusedWindow::Request,
+                matches!(
+                    state.focused_window,
+                    FocusedWindow::Request | FocusedWindow::Editor
+                ),
                 state.theme.clone(),
             )
             .unwrap()
@@ -64,6 +68,19 @@ pub fn draw_request_widget(frame: &mut Frame, state: &mut App, area: Rect) {
     //    request_widget_child_container_content,
     //);
 
+    // a request which failed to load shows why instead of its tabs
+    if let Some(err) = &state.request_error {
+        let error_widget = Paragraph::new(vec![
+            Line::from("Failed to load the request")
+                .style(Style::default().fg(Color::from(state.theme.error.foreground))),
+            Line::from(""),
+            Line::from(err.describe(&state.workspace.collections_path())),
+        ])
+        .wrap(Wrap { trim: false });
+        frame.render_widget(error_widget, request_widget_child_container_content);
+        return;
+    }
+
     manager::match_request_widget_with_opened_tab(
         state,
         frame,
@@ -82,8 +99,11 @@ pub fn draw_request_widget(frame: &mut Frame, state: &mut App, area: Rect) {
                     drawable::editheader::draw(
                         frame,
                         request_widget_child_container_input,
+                        &state.theme,
                         state.sub_focus_element,
                         &mut state.input_buffer,
+                        state.completion.as_ref(),
+                        state.editing_header.is_none(),
                     );
                 }
             }
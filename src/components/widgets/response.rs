@@ -1,15 +1,20 @@
 use ratatui::layout::{Margin, Rect};
+use ratatui::style::Style;
+use ratatui::text::Line;
 use ratatui::widgets::Tabs;
 use ratatui::Frame;
 use strum::IntoEnumIterator;
 
 use crate::components::manager;
 use crate::core::enums::{FocusedWindow, ResponseWidgetTabs, ThemeState, WidgetType};
+use crate::core::http::Response;
 use crate::core::theme;
 
 pub fn draw_response_widget(
     current_theme: &theme::Config,
     selected_tab: usize,
+    response: Option<&Response>,
+    is_sending: bool,
     frame: &mut Frame,
     focused_window: &FocusedWindow,
     area: Rect,
@@ -22,7 +27,14 @@ pub fn draw_response_widget(
                 current_theme.clone(),
             )
             .unwrap()
-            .title("[3] Response"),
+            .title("[3] Response")
+            .title(match response {
+                _ if is_sending => Line::from(" sending… ").right_aligned(),
+                Some(response) => Line::from(format!(" {} {} ", response.status, response.reason))
+                    .style(Style::new().fg(current_theme.status_color(response.status)))
+                    .right_aligned(),
+                None => Line::from(""),
+            }),
         )
         .divider("")
         .style(
@@ -47,6 +59,7 @@ pub fn draw_response_widget(
     // select the right content to display using the select tab
     let current_response_widget_content = manager::match_response_widget_with_opened_tab(
         ResponseWidgetTabs::iter().nth(selected_tab).unwrap(),
+        response,
     )
     .unwrap();
 
@@ -1,3 +1,16 @@
 pub const CONFIG_PATH: &str = "~/.config/fetched/";
 pub const ENVIRONMENT_FILE_NAME: &str = "environment.toml";
 pub const THEME_FILE: &str = "theme.toml";
+pub const KEYMAP_FILE: &str = "keymap.toml";
+pub const WORKSPACE_FILE_NAME: &str = "fetched.toml";
+// the workspaces opened before, kept in the config folder
+pub const RECENT_WORKSPACES_FILE: &str = "workspaces.toml";
+// deleted entries are kept here until they are restored or purged
+pub const TRASH_DIR_NAME: &str = ".trash";
+// the schema version written into request files, older files are migrated on load
+pub const REQUEST_FILE_VERSION: u32 = 3;
+// settings of the user like command aliases, kept in the config folder
+pub const CONFIG_FILE: &str = "config.toml";
+// every sent request with its answer, one json object per line in a file per workspace,
+// kept in the data folder of the user
+pub const HISTORY_DIR_NAME: &str = "history";
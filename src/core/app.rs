@@ -1,12 +1,20 @@
-use crate::components::structs::{App, RequestStructure};
+use crate::components::structs::{App, Header, PendingRequest, RequestStructure, RequestTab};
+use crate::components::text_editor::TextEditor;
+use crate::components::text_input::TextInput;
 use crate::components::{drawable, events, manager, widgets};
+use crate::constants::CONFIG_FILE;
+use crate::core::commands::Completion;
 use crate::core::enums::{
-    FocusedWindow, InputMode, InputStrategy, LogTypes, RequestWidgetTabs, ResponseWidgetTabs,
-    ThemeState, WidgetType, WindowMotion, WindowOperation,
+    Action, FocusedWindow, InputMode, InputStrategy, LogTypes, Popup, RequestWidgetTabs,
+    ResponseWidgetTabs, SnippetFormat, ThemeState, WidgetType, WindowMotion, WindowOperation,
 };
+use crate::core::external_editor::{self, EditTarget};
+use crate::core::keymap::{self, KeyChord, Keymap, Resolution};
 use crate::core::request_parser;
 use crate::core::theme;
-use crate::core::{handler, helpers};
+use crate::core::workspace::{self, Workspace};
+use crate::core::{commands, config, environment, exporters, formats, importers, trash};
+use crate::core::{handler, headers, helpers, history, http};
 use color_eyre::Result;
 use crossterm::event::KeyModifiers;
 use crossterm::terminal::{
@@ -15,115 +23,136 @@ use crossterm::terminal::{
 use crossterm::ExecutableCommand;
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::Flex;
-use ratatui::widgets::{BorderType, Clear, ScrollbarState};
+use ratatui::widgets::{BorderType, Clear, ScrollbarState, TableState, Wrap};
 use ratatui::{
-    crossterm::event::{self, Event, KeyCode, KeyEventKind},
-    layout::{Alignment, Constraint, Layout, Position, Rect},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, List, ListState, Paragraph, Tabs},
     DefaultTerminal, Frame,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::io::stdout;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 use strum::IntoEnumIterator;
 
 type Terminal = ratatui::Terminal<CrosstermBackend<std::io::Stdout>>;
 
+// how long to wait for a key before checking the theme files for changes
+const THEME_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+// how often the answer to a request being sent is looked for
+const RESPONSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// rows PageUp and PageDown scroll the help by
+const HELP_PAGE: u16 = 10;
+
 impl App {
-    pub fn new() -> Self {
-        Self {
+    pub fn new(workspace: Workspace) -> Self {
+        let (theme, theme_errors) = theme::load_theme(None, &workspace.root);
+        let (keymap, keymap_errors) = keymap::load_keymap();
+        let (config, config_error) = config::load_config();
+        let mut app = Self {
+            request_path: None,
             request_data: RequestStructure::default(),
+            is_request_modified: false,
+            request_error: None,
             rectangles: HashMap::new(),
             input_buffer: HashMap::new(),
-            theme: theme::get_theme().unwrap(),
-            input: String::new(),
+            theme,
+            keymap,
+            pending_keys: vec![],
+            theme_name: None,
+            theme_modified: theme::modified_times(&theme::theme_files(None, &workspace.root)),
+            input: TextInput::default(),
             input_mode: InputMode::Normal,
             input_strategy: InputStrategy::Command,
-            character_index: 0,
             focused_window: FocusedWindow::Collections,
             //state
-            collections: handler::list_collections(),
+            collections: handler::list_collections(&workspace),
             collection_window_list_state: ListState::default().with_selected(Some(0)),
             selected_collection: "".to_string(),
             selected_request: "".to_string(),
             show_collection_children: false,
+            broken_requests: HashSet::new(),
+            collection_folders: HashSet::new(),
+            expanded_folders: HashSet::new(),
             // request tabs
             selected_tab: 0,
-            vertical_scroll: 0,
             vertical_scroll_state: ScrollbarState::default(),
+            header_table_state: TableState::default(),
+            editing_header: None,
             //response tabs
             selected_response_tab: 0,
+            response: None,
             current_operation: WindowOperation::Null,
             sub_focus_element: 0,
             is_show_popup: false,
             popup_msg: "".to_string(),
             popup_type: LogTypes::Info,
+            is_show_export_popup: false,
+            selected_export_format: 0,
+            export_request: RequestStructure::default(),
+            undo_stack: vec![],
+            delete_contents: vec![],
+            is_show_trash_popup: false,
+            trash_entries: vec![],
+            trash_list_state: ListState::default(),
+            workspace,
+            active_environment: None,
+            is_show_workspace_popup: false,
+            recent_workspaces: vec![],
+            workspace_list_state: ListState::default(),
+            config,
+            command_history: vec![],
+            history_index: None,
+            history_draft: String::new(),
+            completion: None,
+            body_editor: None,
+            is_show_help_popup: false,
+            help_filter: String::new(),
+            help_table_state: TableState::default(),
+            is_show_method_popup: false,
+            method_list_state: ListState::default(),
+            url_input: None,
+            tabs: vec![RequestTab::default()],
+            active_tab: 0,
+            is_show_history_popup: false,
+            request_history: vec![],
+            request_history_filter: String::new(),
+            request_history_state: ListState::default(),
+            http_client: http::Client::default(),
+            pending_request: None,
+        };
+        if !theme_errors.is_empty() {
+            app.show_error_popup(format!(
+                "Failed to load the theme:\n{}",
+                theme_errors.join("\n")
+            ));
+        } else if !keymap_errors.is_empty() {
+            app.show_error_popup(format!(
+                "Problems with the key bindings:\n{}",
+                keymap_errors.join("\n")
+            ));
+        } else if let Some(err) = config_error {
+            app.show_error_popup(format!("Failed to load the config:\n{}", err));
         }
+        app
     }
 
-    fn move_cursor_left(&mut self) {
-        let cursor_moved_left = self.character_index.saturating_sub(1);
-        self.character_index = self.clamp_cursor(cursor_moved_left);
-    }
-
-    fn move_cursor_right(&mut self) {
-        let cursor_moved_right = self.character_index.saturating_add(1);
-        self.character_index = self.clamp_cursor(cursor_moved_right);
-    }
-
-    fn enter_char(&mut self, new_char: char) {
-        let index = self.byte_index();
-        self.input.insert(index, new_char);
-        self.move_cursor_right();
-        self.input_buffer
-            .insert(self.sub_focus_element, self.input.clone());
-    }
-
-    /// Returns the byte index based on the character position.
-    ///
-    /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
-    /// the byte index based on the index of the character.
-    fn byte_index(&self) -> usize {
-        self.input
-            .char_indices()
-            .map(|(i, _)| i)
-            .nth(self.character_index)
-            .unwrap_or(self.input.len())
-    }
-
-    fn delete_char(&mut self) {
-        let is_not_cursor_leftmost = self.character_index != 0;
-        if is_not_cursor_leftmost {
-            // Method "remove" is not used on the saved text for deleting the selected char.
-            // Reason: Using remove on String works on bytes instead of the chars.
-            // Using remove would require special care because of char boundaries.
-
-            let current_index = self.character_index;
-            let from_left_to_current_index = current_index - 1;
-
-            // Getting all characters before the selected character.
-            let before_char_to_delete = self.input.chars().take(from_left_to_current_index);
-            // Getting all characters after selected character.
-            let after_char_to_delete = self.input.chars().skip(current_index);
-
-            // Put all characters together except the selected one.
-            // By leaving the selected one out, it is forgotten and therefore deleted.
-            self.input = before_char_to_delete.chain(after_char_to_delete).collect();
-            self.move_cursor_left();
+    // the field keys edit, the prompt or the focused field of the request
+    fn active_input(&mut self) -> &mut TextInput {
+        match self.input_mode {
+            InputMode::Insert => match self.url_input {
+                Some(ref mut input) => input,
+                None => self.input_buffer.entry(self.sub_focus_element).or_default(),
+            },
+            _ => &mut self.input,
         }
-        self.input_buffer
-            .insert(self.sub_focus_element, self.input.clone());
-    }
-
-    fn clamp_cursor(&self, new_cursor_pos: usize) -> usize {
-        new_cursor_pos.clamp(0, self.input.chars().count())
-    }
-
-    fn reset_cursor(&mut self) {
-        self.character_index = 0;
     }
 
     fn prompt(&mut self, operation: WindowOperation) {
@@ -133,27 +162,118 @@ impl App {
         self.current_operation = operation;
     }
 
+    // rename and duplicate start from the name of the highlighted entry, without its extension
+    fn prompt_with_selected_name(&mut self, operation: WindowOperation) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let file_name = entry.split('#').next().unwrap_or_default();
+        let file_name = file_name.rsplit('/').next().unwrap_or_default();
+        let name = match file_name.rsplit_once('.') {
+            Some((stem, _)) if formats::has_request_extension(file_name) => stem,
+            _ => file_name,
+        };
+
+        self.prompt(operation);
+        self.input.set_text(match operation {
+            WindowOperation::Duplicate => format!("{}-copy", name),
+            _ => name.to_string(),
+        });
+    }
+
     fn get_rectangle(&self, key: String) -> Rect {
         *self.rectangles.get_key_value(&key).unwrap().1
     }
 
     fn get_selected_value(&self) -> Result<String, Box<dyn std::error::Error>> {
-        helpers::logger(self.collections.get(0));
-        Ok(self
-            .collections
-            .get(self.collection_window_list_state.selected().unwrap())
-            .unwrap_or(self.collections.get(0).unwrap())
-            .to_string())
+        let index = self.collection_window_list_state.selected().unwrap_or(0);
+        self.collections
+            .get(index)
+            .or(self.collections.first())
+            .cloned()
+            .ok_or_else(|| "Nothing is selected".into())
     }
 
-    fn run_editor(&self, terminal: &mut Terminal, file: String) -> Result<()> {
+    // hands the terminal to the editor until it exits, telling whether the edit was
+    // finished rather than aborted, like with `:cq` in vim
+    fn run_editor(&self, terminal: &mut Terminal, file: &Path) -> anyhow::Result<bool> {
+        let Some(command) = external_editor::editor_command(self.config.editor.as_deref()) else {
+            anyhow::bail!(
+                "No editor found, set $VISUAL, $EDITOR or `editor` in {}",
+                CONFIG_FILE
+            );
+        };
         stdout().execute(LeaveAlternateScreen)?;
         disable_raw_mode()?;
-        Command::new("nvim").arg(file).status()?;
+        let status = Command::new(&command[0])
+            .args(&command[1..])
+            .arg(file)
+            .status();
+        // the screen comes back even when the editor could not be started
         stdout().execute(EnterAlternateScreen)?;
         enable_raw_mode()?;
         terminal.clear()?;
-        Ok(())
+        let status =
+            status.map_err(|err| anyhow::anyhow!("Could not run {}: {}", command[0], err))?;
+        Ok(status.success())
+    }
+
+    // edits the open request, or a part of it, in the external editor and loads the
+    // result, which may have broken the file
+    fn edit_externally(&mut self, target: EditTarget, terminal: &mut Terminal) {
+        let Some(path) = self.request_path.clone() else {
+            return;
+        };
+        // the editor starts from the file, edits which are not saved would be lost
+        if self.has_unsaved_changes() {
+            self.show_error_popup(
+                "Save or drop the edits of the request before editing it externally".to_string(),
+            );
+            return;
+        }
+        let result = match target {
+            EditTarget::File => {
+                // requests sharing a file are edited in the whole file
+                let (file_path, _) = request_parser::split_request_path(&path);
+                self.run_editor(terminal, &file_path).map(|_| ())
+            }
+            _ => self.edit_part_externally(target, &path, terminal),
+        };
+        if let Err(err) = result {
+            self.show_error_popup(err.to_string());
+            return;
+        }
+
+        self.reload_collections();
+        self.load_request();
+        if let Some(err) = &self.request_error {
+            let err = err.describe(&self.workspace.collections_path());
+            self.show_error_popup(format!("The request is not valid anymore: {}", err));
+        }
+    }
+
+    fn edit_part_externally(
+        &mut self,
+        target: EditTarget,
+        path: &Path,
+        terminal: &mut Terminal,
+    ) -> anyhow::Result<()> {
+        // a part of a broken file can not be merged back
+        let mut request = request_parser::read_request(path)?;
+        let temp_file = external_editor::TempFile::create(
+            target,
+            &request.body_type,
+            &external_editor::part_text(target, &request),
+        )?;
+        let finished = self.run_editor(terminal, &temp_file.path);
+        let text = std::fs::read_to_string(&temp_file.path);
+        drop(temp_file);
+        if !finished? {
+            return Ok(());
+        }
+
+        external_editor::merge(target, &mut request, &text?)?;
+        request_parser::update_request(path, request)
     }
 
     fn execute_operation_on_selected_window(
@@ -165,18 +285,23 @@ impl App {
         match self.focused_window {
             FocusedWindow::Collections => match operation {
                 WindowOperation::Open => {
-                    if self.show_collection_children {
-                        let file_path = handler::get_file_path(
-                            self.selected_collection.clone(),
-                            self.get_selected_value().unwrap(),
-                        )
-                        .unwrap();
-                        self.run_editor(&mut terminal.unwrap(), file_path).unwrap()
+                    if let Some(terminal) = terminal {
+                        self.edit_externally(EditTarget::File, terminal);
                     }
                 }
                 _ => {}
             },
             FocusedWindow::Request => match operation {
+                WindowOperation::Edit
+                    if RequestWidgetTabs::iter().nth(self.selected_tab)
+                        == Some(RequestWidgetTabs::Body) =>
+                {
+                    self.open_body_editor()
+                }
+                WindowOperation::Edit if self.is_header_tab_selected() => {
+                    let selected = self.header_table_state.selected();
+                    self.edit_header(selected.filter(|_| !self.request_data.headers.is_empty()))
+                }
                 WindowOperation::Edit => self.input_mode = InputMode::Insert,
                 _ => {}
             },
@@ -184,68 +309,730 @@ impl App {
         }
     }
 
+    fn is_header_tab_selected(&self) -> bool {
+        self.focused_window == FocusedWindow::Request
+            && RequestWidgetTabs::iter().nth(self.selected_tab) == Some(RequestWidgetTabs::Header)
+    }
+
+    // fills the edit fields with the header at `index`, or leaves them empty for a new one
+    fn edit_header(&mut self, index: Option<usize>) {
+        if !self.is_request_open() {
+            return;
+        }
+        self.input_buffer.clear();
+        if let Some(header) = index.and_then(|index| self.request_data.headers.get(index)) {
+            for (field, text) in [&header.name, &header.value, &header.description]
+                .into_iter()
+                .enumerate()
+            {
+                let mut input = TextInput::default();
+                input.set_text(text.as_str());
+                self.input_buffer.insert(field as u8, input);
+            }
+        }
+        self.editing_header = index;
+        self.sub_focus_element = 0;
+        self.current_operation = WindowOperation::Edit;
+        self.input_mode = InputMode::Insert;
+    }
+
+    // toggling, deleting and moving apply to the selected header, `change` tells which
+    // header to select afterwards
+    fn change_selected_header(&mut self, change: impl FnOnce(&mut Vec<Header>, usize) -> usize) {
+        if !self.is_header_tab_selected() {
+            return;
+        }
+        let Some(index) = self
+            .header_table_state
+            .selected()
+            .filter(|index| *index < self.request_data.headers.len())
+        else {
+            return;
+        };
+        let mut request = self.request_data.clone();
+        let selected = change(&mut request.headers, index);
+        self.change_request(request);
+        self.header_table_state.select(Some(selected));
+    }
+
+    fn is_request_open(&self) -> bool {
+        self.request_error.is_none() && self.request_path.is_some()
+    }
+
+    // the request is saved to the file it was loaded from, whatever is highlighted by now
+    fn loaded_request_path(&self) -> anyhow::Result<PathBuf> {
+        self.request_path
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("no request is open"))
+    }
+
+    // takes the shown request out of the app, for its tab to keep
+    fn stash_tab(&mut self) -> RequestTab {
+        self.url_input = None;
+        RequestTab {
+            path: self.request_path.take(),
+            request_data: std::mem::take(&mut self.request_data),
+            is_request_modified: std::mem::take(&mut self.is_request_modified),
+            request_error: self.request_error.take(),
+            body_editor: self.body_editor.take(),
+            response: self.response.take(),
+            selected_tab: self.selected_tab,
+            header_table_state: std::mem::take(&mut self.header_table_state),
+        }
+    }
+
+    fn restore_tab(&mut self, tab: RequestTab) {
+        self.request_path = tab.path;
+        self.request_data = tab.request_data;
+        self.is_request_modified = tab.is_request_modified;
+        self.request_error = tab.request_error;
+        self.body_editor = tab.body_editor;
+        self.response = tab.response;
+        self.selected_tab = tab.selected_tab;
+        self.header_table_state = tab.header_table_state;
+        self.current_operation = WindowOperation::Null;
+        self.input_buffer.clear();
+        self.input_mode = InputMode::Normal;
+        // an editor still holds the edits of the tab, so it is shown again
+        if self.body_editor.is_some() {
+            self.focused_window = FocusedWindow::Editor;
+        } else if self.focused_window == FocusedWindow::Editor {
+            self.focused_window = FocusedWindow::Request;
+        }
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        self.tabs[self.active_tab] = self.stash_tab();
+        let tab = std::mem::take(&mut self.tabs[index]);
+        self.active_tab = index;
+        self.restore_tab(tab);
+    }
+
+    // a request which is open already is switched to instead
+    fn open_tab(&mut self) {
+        let Some(path) = self
+            .get_request_file_path()
+            .ok()
+            .filter(|_| !self.is_folder_selected())
+        else {
+            return;
+        };
+        if self.request_path.as_ref() == Some(&path) {
+            return;
+        }
+        if let Some(index) = self
+            .tabs
+            .iter()
+            .position(|tab| tab.path.as_ref() == Some(&path))
+        {
+            return self.switch_tab(index);
+        }
+        self.tabs[self.active_tab] = self.stash_tab();
+        self.tabs.push(RequestTab::default());
+        self.active_tab = self.tabs.len() - 1;
+        self.restore_tab(RequestTab::default());
+        self.refresh_request_data();
+    }
+
+    fn close_tab(&mut self) {
+        match self.has_unsaved_changes() {
+            true => self.prompt(WindowOperation::CloseTab),
+            false => self.drop_active_tab(),
+        }
+    }
+
+    // the last tab is not closed but left empty
+    fn drop_active_tab(&mut self) {
+        self.stash_tab();
+        if self.tabs.len() > 1 {
+            self.tabs.remove(self.active_tab);
+            self.active_tab = self.active_tab.min(self.tabs.len() - 1);
+        }
+        let tab = std::mem::take(&mut self.tabs[self.active_tab]);
+        self.restore_tab(tab);
+    }
+
+    // the title and whether there are unsaved changes of every tab, the active tab is
+    // described by the fields of the app
+    fn tab_titles(&self) -> Vec<(String, bool)> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| match index == self.active_tab {
+                true => (
+                    tab_title(self.request_path.as_ref()),
+                    self.has_unsaved_changes(),
+                ),
+                false => (tab_title(tab.path.as_ref()), tab.is_modified()),
+            })
+            .collect()
+    }
+
+    // tells whether everything is saved, the failures are shown together
+    fn save_all_tabs(&mut self) -> bool {
+        if !self.save_request() {
+            return false;
+        }
+        let failures: Vec<_> = self
+            .tabs
+            .iter_mut()
+            .filter(|tab| tab.is_modified())
+            .filter_map(|tab| {
+                save_tab(tab)
+                    .err()
+                    .map(|err| format!("{}: {}", tab_title(tab.path.as_ref()), err))
+            })
+            .collect();
+        if !failures.is_empty() {
+            self.show_error_popup(format!("Failed to save:\n{}", failures.join("\n")));
+        }
+        failures.is_empty()
+    }
+
+    /// Quits, after asking what to do with the requests which have unsaved changes.
+    pub fn quit(&mut self) {
+        match self.tab_titles().iter().any(|(_, modified)| *modified) {
+            true => self.prompt(WindowOperation::Quit),
+            false => handler::exit_app(),
+        }
+    }
+
+    /// Sends the open request, unsaved edits of its body included, and keeps it in the
+    /// history along with the answer.
+    pub fn send_request(&mut self) {
+        if !self.is_request_open() {
+            self.show_error_popup("Open a request to send it".to_string());
+            return;
+        }
+        let request = environment::resolve_request(&self.edited_request(), &self.variables());
+        self.start_request(request, false);
+    }
+
+    // the request as it is shown, with the text of the body editor which is not saved yet
+    fn edited_request(&self) -> RequestStructure {
+        let mut request = self.request_data.clone();
+        if let Some(editor) = self.body_editor.as_ref() {
+            request.body = editor.text();
+        }
+        request
+    }
+
+    // the variables of the workspace, with the ones of the active environment winning
+    pub fn variables(&self) -> HashMap<String, String> {
+        environment::load_variables(&self.workspace.root, self.active_environment.as_deref())
+    }
+
+    // sends on a worker thread, the answer is picked up by `receive_response`
+    fn start_request(&mut self, request: RequestStructure, is_replay: bool) {
+        if self.pending_request.is_some() {
+            self.show_error_popup("Wait for the request being sent to be answered".to_string());
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let client = self.http_client.clone();
+        let environment = self.active_environment.clone();
+        thread::spawn(move || {
+            // the app may be gone by the time the answer comes
+            _ = sender.send(history::send(&client, request, environment));
+        });
+        self.pending_request = Some(PendingRequest {
+            path: self.request_path.clone(),
+            is_replay,
+            receiver,
+        });
+    }
+
+    pub fn is_sending(&self) -> bool {
+        self.pending_request.is_some()
+    }
+
+    // the answer of the pending request, shown in the tab it was sent from
+    fn receive_response(&mut self) {
+        let Some(pending) = self.pending_request.as_ref() else {
+            return;
+        };
+        let entry = match pending.receiver.try_recv() {
+            Ok(entry) => entry,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.pending_request = None;
+                self.show_error_popup("The request stopped without an answer".to_string());
+                return;
+            }
+        };
+        let pending = self.pending_request.take().unwrap();
+        if let Err(err) = history::record(&self.workspace.root, &entry) {
+            self.show_error_popup(format!("Failed to add the request to the history: {}", err));
+        }
+        if pending.is_replay {
+            if self.is_show_history_popup {
+                self.request_history = history::list(&self.workspace.root);
+                self.request_history_filter.clear();
+                self.request_history_state.select(Some(0));
+            }
+            return;
+        }
+        if let Some(err) = &entry.error {
+            self.show_error_popup(format!("Failed to send the request: {}", err));
+        }
+        if pending.path == self.request_path {
+            self.response = entry.response;
+        } else if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.path == pending.path) {
+            tab.response = entry.response;
+        }
+    }
+
+    pub fn show_history_popup(&mut self) {
+        self.request_history = history::list(&self.workspace.root);
+        self.request_history_filter.clear();
+        self.request_history_state.select(Some(0));
+        self.is_show_history_popup = true;
+    }
+
+    // the entries of the history matching what was typed
+    fn filtered_history(&self) -> Vec<&history::HistoryEntry> {
+        self.request_history
+            .iter()
+            .filter(|entry| entry.matches(&self.request_history_filter))
+            .collect()
+    }
+
+    fn selected_history_entry(&self) -> Option<history::HistoryEntry> {
+        let index = self.request_history_state.selected()?;
+        self.filtered_history()
+            .get(index)
+            .map(|entry| (*entry).clone())
+    }
+
+    // sends the request of the entry again, exactly as it was sent, the new entry is shown
+    fn replay_history_entry(&mut self) {
+        let Some(entry) = self.selected_history_entry() else {
+            return;
+        };
+        self.start_request(entry.request, true);
+    }
+
+    fn prompt_save_history_entry(&mut self) {
+        if self.selected_history_entry().is_none() {
+            return;
+        }
+        self.prompt(WindowOperation::SaveHistory);
+        // the opened collection is the likely place for it
+        if self.show_collection_children {
+            self.input
+                .set_text(format!("{}/", self.selected_collection));
+        }
+    }
+
+    // the entries matching the filter, with what came back, go into a HAR file
+    fn export_history(&mut self, file_path: &str) {
+        let entries: Vec<history::HistoryEntry> =
+            self.filtered_history().into_iter().cloned().collect();
+        handler::export_har(self, file_path, &entries.iter().collect::<Vec<_>>());
+    }
+
+    // `path` is like `collection/folder/name`, an existing request is not replaced
+    fn save_history_entry(&mut self, path: &str) {
+        let Some(entry) = self.selected_history_entry() else {
+            return;
+        };
+        let saved = match path.trim_matches('/').split_once('/') {
+            Some((collection, name)) => {
+                let file_path = self.workspace.collections_path().join(collection).join(
+                    request_parser::with_request_extension(
+                        name,
+                        self.workspace.config.request_format,
+                    ),
+                );
+                let mut request = entry.request;
+                request.version = RequestStructure::default().version;
+                match file_path.exists() {
+                    true => Err(anyhow::anyhow!("{} already exists", path)),
+                    false => file_path
+                        .parent()
+                        .map_or(Ok(()), std::fs::create_dir_all)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|_| request_parser::update_request(&file_path, request)),
+                }
+            }
+            None => Err(anyhow::anyhow!("save it as <collection>/<name>")),
+        };
+        match saved {
+            Ok(_) => {
+                self.reload_collections();
+                self.show_popup(format!("Saved the request as {}", path));
+            }
+            Err(err) => self.show_error_popup(format!("Failed to save the request: {}", err)),
+        }
+    }
+
+    pub fn show_method_popup(&mut self) {
+        if !self.is_request_open() {
+            return;
+        }
+        let method = self.request_data.method.to_uppercase();
+        let index = drawable::methods::METHODS
+            .iter()
+            .position(|known| *known == method)
+            // anything else was a custom method
+            .unwrap_or(drawable::methods::METHODS.len());
+        self.method_list_state.select(Some(index));
+        self.is_show_method_popup = true;
+    }
+
+    fn pick_method(&mut self) {
+        self.is_show_method_popup = false;
+        let selected = self.method_list_state.selected().unwrap_or_default();
+        match drawable::methods::METHODS.get(selected) {
+            Some(method) => self.set_method(method),
+            None => {
+                self.prompt(WindowOperation::Method);
+                self.input.set_text(self.request_data.method.as_str());
+            }
+        }
+    }
+
+    fn set_method(&mut self, method: &str) {
+        let method = method.trim().to_uppercase();
+        if !helpers::is_token(&method) {
+            self.show_error_popup(format!("{:?} is not a valid method", method));
+            return;
+        }
+        let mut request = self.request_data.clone();
+        request.method = method;
+        self.change_request(request);
+    }
+
+    // the url is edited in place, with the query parameters shown in their own tab
+    fn edit_url(&mut self) {
+        if !self.is_request_open() {
+            return;
+        }
+        let mut input = TextInput::default();
+        input.set_text(self.request_data.url.as_str());
+        self.url_input = Some(input);
+        self.current_operation = WindowOperation::Null;
+        self.input_mode = InputMode::Insert;
+    }
+
+    fn apply_url(&mut self) {
+        let Some(text) = self
+            .url_input
+            .as_ref()
+            .map(|input| input.text().trim().to_string())
+        else {
+            return;
+        };
+        if let Err(err) = helpers::validate_url(&text, &self.variables()) {
+            self.show_error_popup(err.to_string());
+            return;
+        }
+        // a query typed into the url moves to the query parameters
+        let (url, query_parameters) = importers::split_query(&text);
+        let mut request = self.request_data.clone();
+        request.url = url;
+        request.query_parameters.extend(query_parameters);
+        self.change_request(request);
+        self.url_input = None;
+        self.input_mode = InputMode::Normal;
+    }
+
     fn execute_operation_on_selected_window_with_promt(
         &mut self,
         operation: WindowOperation,
         promt: String,
     ) {
+        // the method and the tabs belong to the request whichever window is focused
+        match operation {
+            WindowOperation::Method => return self.set_method(&promt),
+            WindowOperation::SaveHistory => return self.save_history_entry(promt.trim()),
+            WindowOperation::ExportHistory => return self.export_history(promt.trim()),
+            WindowOperation::CloseTab => {
+                match promt.trim() {
+                    "y" if self.save_request() => self.drop_active_tab(),
+                    "n" => self.drop_active_tab(),
+                    _ => {}
+                }
+                return;
+            }
+            WindowOperation::Quit => {
+                match promt.trim() {
+                    "y" if self.save_all_tabs() => handler::exit_app(),
+                    "n" => handler::exit_app(),
+                    _ => {}
+                }
+                return;
+            }
+            _ => {}
+        }
         match self.focused_window {
             FocusedWindow::Collections => match operation {
                 WindowOperation::Create => {
                     if self.show_collection_children {
-                        handler::create_request(&self.selected_collection, promt).unwrap()
-                    } else {
-                        handler::create_collection(promt).unwrap();
+                        // new entries go into the highlighted folder, a trailing `/` makes a folder
+                        let folder = self.current_folder();
+                        let created = match promt.strip_suffix('/') {
+                            Some(name) => handler::create_folder(
+                                &self.workspace,
+                                &self.selected_collection,
+                                &handler::child_path(&folder, name),
+                            ),
+                            None => handler::create_request(
+                                &self.workspace,
+                                &self.selected_collection,
+                                handler::child_path(&folder, &promt),
+                            )
+                            .map_err(|err| anyhow::anyhow!(err.to_string())),
+                        };
+                        match created {
+                            Ok(_) => self.expand_to(&handler::child_path(&folder, "")),
+                            Err(err) => self
+                                .show_error_popup(format!("Failed to create {}: {}", promt, err)),
+                        }
+                    } else if let Err(err) =
+                        handler::create_collection(&self.workspace, promt.trim())
+                    {
+                        self.show_error_popup(format!("Failed to create {}: {}", promt, err));
                     }
                 }
                 WindowOperation::Delete => {
+                    self.is_show_popup = false;
                     if promt == "y" {
-                        if self.show_collection_children {
-                            handler::delete_collection_children(
-                                self.selected_collection.clone(),
-                                self.collections
-                                    .get(self.collection_window_list_state.selected().unwrap())
-                                    .unwrap()
-                                    .to_string(),
-                            )
-                            .unwrap();
-                        } else {
-                            _ = handler::delete_collection(
-                                self.collections
-                                    .get(self.collection_window_list_state.selected().unwrap())
-                                    .unwrap()
-                                    .to_string(),
-                            );
+                        // deleted entries go into the trash, so they can be brought back with undo
+                        if let Some(entry) = self.selected_entry() {
+                            match trash::move_to_trash(&self.workspace, &entry) {
+                                Ok(id) => self.undo_stack.push(id),
+                                Err(err) => self.show_error_popup(format!(
+                                    "Failed to delete {}: {}",
+                                    entry, err
+                                )),
+                            }
                         }
                     }
                 }
-                _ => todo!(),
+                WindowOperation::Rename | WindowOperation::Duplicate | WindowOperation::Move => {
+                    let Some(entry) = self.selected_entry() else {
+                        return;
+                    };
+                    let (action, changed) = match operation {
+                        WindowOperation::Rename => (
+                            "rename",
+                            handler::rename_entry(&self.workspace, &entry, &promt),
+                        ),
+                        WindowOperation::Duplicate => (
+                            "duplicate",
+                            handler::duplicate_entry(&self.workspace, &entry, &promt),
+                        ),
+                        _ => (
+                            "move",
+                            handler::move_entry(&self.workspace, &entry, promt.trim_matches('/')),
+                        ),
+                    };
+                    match changed {
+                        Ok(changed) => self.follow_entry(&changed),
+                        Err(err) => self
+                            .show_error_popup(format!("Failed to {} {}: {}", action, entry, err)),
+                    }
+                }
+                WindowOperation::Edit | WindowOperation::Open | WindowOperation::Null => {}
+                // handled before the windows are told apart
+                WindowOperation::Method
+                | WindowOperation::SaveHistory
+                | WindowOperation::ExportHistory
+                | WindowOperation::CloseTab
+                | WindowOperation::Quit => {}
             },
-            _ => todo!(),
+            // the other windows do not prompt for anything
+            FocusedWindow::Request
+            | FocusedWindow::Response
+            | FocusedWindow::Editor
+            | FocusedWindow::Input => {}
+        }
+        self.reload_collections();
+    }
+
+    // list the collections, or the tree of the opened one along with its broken requests
+    pub fn reload_collections(&mut self) {
+        self.broken_requests.clear();
+        self.collection_folders.clear();
+        if !self.show_collection_children {
+            self.collections = handler::list_collections(&self.workspace);
+            return;
+        }
+
+        let tree = handler::list_collection_tree(
+            &self.workspace,
+            &self.selected_collection,
+            &self.expanded_folders,
+        );
+        let collection_path = self
+            .workspace
+            .collections_path()
+            .join(&self.selected_collection);
+        for entry in &tree {
+            if entry.is_folder {
+                self.collection_folders.insert(entry.path.clone());
+            } else if request_parser::load_request(&collection_path.join(&entry.path)).is_err() {
+                self.broken_requests.insert(entry.path.clone());
+            }
+        }
+        self.collections = tree.into_iter().map(|entry| entry.path).collect();
+    }
+
+    pub fn is_folder_selected(&self) -> bool {
+        self.get_selected_value()
+            .is_ok_and(|entry| self.collection_folders.contains(&entry))
+    }
+
+    // the folder of the opened collection new entries go into, empty for its root
+    fn current_folder(&self) -> String {
+        match self.get_selected_value() {
+            Ok(entry) if self.collection_folders.contains(&entry) => entry,
+            Ok(entry) => entry
+                .rsplit_once('/')
+                .map(|(folder, _)| folder.to_string())
+                .unwrap_or_default(),
+            Err(_) => "".to_string(),
+        }
+    }
+
+    // expand every folder leading to `path`, so it is visible in the tree
+    pub fn expand_to(&mut self, path: &str) {
+        let mut folder = String::new();
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            folder = handler::child_path(&folder, name);
+            self.expanded_folders.insert(folder.clone());
+        }
+    }
+
+    pub fn select_collection_entry(&mut self, path: &str) {
+        if let Some(index) = self.collections.iter().position(|entry| entry == path) {
+            self.collection_window_list_state.select(Some(index));
+        }
+    }
+
+    // the highlighted collection, folder or request as a path relative to the workspace
+    pub fn selected_entry(&self) -> Option<String> {
+        let selected = self.get_selected_value().ok()?;
+        match self.show_collection_children {
+            true => Some(handler::child_path(&self.selected_collection, &selected)),
+            false => Some(selected),
         }
+    }
+
+    // show and highlight an entry after it was renamed, duplicated or moved
+    pub fn follow_entry(&mut self, entry: &str) {
+        match entry.split_once('/') {
+            Some((collection, path)) => {
+                if !self.show_collection_children || self.selected_collection != collection {
+                    self.expanded_folders.clear();
+                }
+                self.selected_collection = collection.to_string();
+                self.show_collection_children = true;
+                if let Some((parent, _)) = path.rsplit_once('/') {
+                    self.expand_to(parent);
+                }
+                self.reload_collections();
+                self.select_collection_entry(path);
+            }
+            None => {
+                self.show_collection_children = false;
+                self.reload_collections();
+                self.select_collection_entry(entry);
+            }
+        }
+        self.refresh_request_data();
+    }
+
+    // `api › users › admin` for the folder the selection is in
+    fn breadcrumbs(&self) -> String {
+        let mut crumbs = vec![self.selected_collection.clone()];
         if self.show_collection_children {
-            self.collections = handler::list_collection_children(self.selected_collection.clone())
-        } else {
-            self.collections = handler::list_collections()
+            crumbs.extend(
+                self.current_folder()
+                    .split('/')
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+            );
         }
+        crumbs.join(" \u{203a} ")
     }
 
     fn get_request_file_path(&self) -> anyhow::Result<PathBuf> {
-        let path = std::env::current_dir()
-            .unwrap()
-            .join(&self.selected_collection)
-            .join(self.get_selected_value().unwrap());
+        let request_name = self
+            .get_selected_value()
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
 
-        helpers::logger(self.collection_window_list_state.selected());
-        Ok(path)
+        Ok(self
+            .workspace
+            .collections_path()
+            .join(&self.selected_collection)
+            .join(request_name))
     }
 
+    // the highlighted request replaces the one of the active tab, a request with edits
+    // which are not saved keeps its tab and the highlighted one opens in another
     fn refresh_request_data(&mut self) {
-        let json_data = request_parser::read_json_file(&self.get_request_file_path().unwrap());
+        let request_path = match self.get_request_file_path() {
+            Ok(path) if !self.is_folder_selected() => Some(path),
+            _ => None,
+        };
+        if let Some(index) = (0..self.tabs.len()).find(|index| {
+            *index != self.active_tab
+                && request_path.is_some()
+                && self.tabs[*index].path == request_path
+        }) {
+            return self.switch_tab(index);
+        }
+        // the edits are dropped along with the file, when it is gone
+        let is_still_there = self.request_path.as_ref().is_some_and(|path| {
+            let (file_path, _) = request_parser::split_request_path(path);
+            file_path.is_file()
+        });
+        if self.has_unsaved_changes() && is_still_there {
+            if request_path.is_none() || request_path == self.request_path {
+                return;
+            }
+            self.tabs[self.active_tab] = self.stash_tab();
+            self.tabs.push(RequestTab::default());
+            self.active_tab = self.tabs.len() - 1;
+            self.restore_tab(RequestTab::default());
+        }
+        self.request_path = request_path;
+        self.load_request();
+    }
+
+    // a request which fails to load is replaced by its error, the app keeps running
+    fn load_request(&mut self) {
+        let loaded = match &self.request_path {
+            Some(path) => request_parser::load_request(path).map(Some),
+            None => Ok(None),
+        };
 
-        self.request_data = json_data.unwrap();
+        match loaded {
+            Ok(request_data) => {
+                self.request_data = request_data.unwrap_or_default();
+                self.request_error = None;
+            }
+            Err(err) => {
+                self.request_data = RequestStructure::default();
+                self.request_error = Some(err);
+            }
+        }
+        self.is_request_modified = false;
         self.current_operation = WindowOperation::Null;
         self.input_buffer.clear();
+        self.url_input = None;
+        self.header_table_state
+            .select((!self.request_data.headers.is_empty()).then_some(0));
+        // the editor belonged to the request shown before
+        self.body_editor = None;
+        if self.focused_window == FocusedWindow::Editor {
+            self.focused_window = FocusedWindow::Request;
+        }
     }
 
     fn select_collection_to_send_motion(&mut self, motion: WindowMotion) {
@@ -258,29 +1045,50 @@ impl App {
                     self.collection_window_list_state.select_previous();
                 }
                 WindowMotion::Left => {
-                    if self.show_collection_children {
+                    let entry = self.get_selected_value().unwrap_or_default();
+                    if self.show_collection_children && self.expanded_folders.contains(&entry) {
+                        self.select_collection_to_send_motion(WindowMotion::Collapse);
+                    } else if let Some((parent, _)) = entry.rsplit_once('/') {
+                        self.select_collection_entry(parent);
+                    } else if self.show_collection_children {
+                        // back to the collections, keeping the one we came from selected
+                        let collection = std::mem::take(&mut self.selected_collection);
                         self.show_collection_children = false;
-                        self.selected_collection = "".to_string();
-                        self.collections = super::handler::list_collections()
+                        self.reload_collections();
+                        self.select_collection_entry(&collection);
+                    } else {
+                        self.collection_window_list_state.select(Some(0));
                     }
-                    self.collection_window_list_state.select(Some(0));
                 }
                 WindowMotion::Right => {
                     if !self.show_collection_children {
-                        self.show_collection_children = true;
-                        self.selected_collection = self
-                            .collections
-                            .get(self.collection_window_list_state.selected().unwrap())
-                            .unwrap()
-                            .to_string();
-
-                        self.collections = super::handler::list_collection_children(
-                            self.selected_collection.clone(),
-                        )
+                        if let Ok(collection) = self.get_selected_value() {
+                            self.show_collection_children = true;
+                            self.selected_collection = collection;
+                            self.expanded_folders.clear();
+                            self.reload_collections();
+                            self.collection_window_list_state.select(Some(0));
+                        }
+                    } else {
+                        self.select_collection_to_send_motion(WindowMotion::Expand);
                     }
-                    self.collection_window_list_state.select(Some(0));
                 }
-                _ => {}
+                WindowMotion::Expand => {
+                    let entry = self.get_selected_value().unwrap_or_default();
+                    if self.collection_folders.contains(&entry) {
+                        self.expanded_folders.insert(entry.clone());
+                        self.reload_collections();
+                        self.select_collection_entry(&entry);
+                    }
+                }
+                WindowMotion::Collapse => {
+                    let entry = self.get_selected_value().unwrap_or_default();
+                    self.expanded_folders.remove(&entry);
+                    self.reload_collections();
+                    self.select_collection_entry(&entry);
+                }
+                WindowMotion::First => self.collection_window_list_state.select_first(),
+                WindowMotion::Last => self.collection_window_list_state.select_last(),
             },
             FocusedWindow::Request => match motion {
                 WindowMotion::Left => {
@@ -297,11 +1105,12 @@ impl App {
                         self.selected_tab = self.selected_tab + 1;
                     };
                 }
-                WindowMotion::Down => {
-                    self.vertical_scroll = self.vertical_scroll.saturating_add(1);
-                    self.vertical_scroll_state =
-                        self.vertical_scroll_state.position(self.vertical_scroll)
-                }
+                // the headers are the only tab with a list yet
+                _ if !self.is_header_tab_selected() => {}
+                WindowMotion::Up => self.header_table_state.select_next(),
+                WindowMotion::Down => self.header_table_state.select_previous(),
+                WindowMotion::First => self.header_table_state.select_first(),
+                WindowMotion::Last => self.header_table_state.select_last(),
                 _ => {}
             },
             FocusedWindow::Response => match motion {
@@ -330,117 +1139,600 @@ impl App {
         }
     }
 
+    // keys of a sequence like `g g` are collected until they make up a binding
+    // adds the key to the ones pressed so far, `resolve` tells what they do
+    fn resolve_key(
+        &mut self,
+        chord: KeyChord,
+        resolve: impl Fn(&Keymap, &[KeyChord]) -> Resolution,
+    ) -> Resolution {
+        self.pending_keys.push(chord);
+        let resolution = resolve(&self.keymap, &self.pending_keys);
+        // a sequence which went nowhere starts over with its last key
+        if resolution == Resolution::Unbound && self.pending_keys.len() > 1 {
+            self.pending_keys = vec![chord];
+            return resolve(&self.keymap, &self.pending_keys);
+        }
+        resolution
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, terminal: &mut DefaultTerminal) {
+        let chord = KeyChord::from(key);
+        let (mode, window) = (self.input_mode.clone(), self.focused_window.clone());
+        let resolution =
+            self.resolve_key(chord, |keymap, keys| keymap.resolve(&mode, &window, keys));
+
+        // any other key ends cycling through the completions
+        if resolution != Resolution::Action(Action::Complete) {
+            self.completion = None;
+        }
+        match resolution {
+            Resolution::Pending => return,
+            Resolution::Action(action) => self.perform(action, terminal),
+            Resolution::Unbound => {
+                // unbound characters are typed into the prompt or the edited field
+                let is_typing = matches!(self.input_mode, InputMode::Control | InputMode::Insert);
+                let is_plain = !chord
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+                if let (true, true, KeyCode::Char(to_insert)) = (is_typing, is_plain, chord.code) {
+                    match self.body_editor.as_mut() {
+                        Some(editor) if self.focused_window == FocusedWindow::Editor => {
+                            editor.insert_char(to_insert)
+                        }
+                        _ => self.active_input().insert_char(to_insert),
+                    }
+                }
+            }
+        }
+        self.pending_keys.clear();
+    }
+
+    // the popup taking the keys, only one of them is shown at a time
+    fn shown_popup(&self) -> Option<Popup> {
+        if self.input_mode != InputMode::Normal {
+            return None;
+        }
+        [
+            (self.is_show_export_popup, Popup::Export),
+            (self.is_show_trash_popup, Popup::Trash),
+            (self.is_show_method_popup, Popup::Method),
+            (self.is_show_workspace_popup, Popup::Workspace),
+            (self.is_show_history_popup, Popup::History),
+            (self.is_show_help_popup, Popup::Help),
+        ]
+        .into_iter()
+        .find_map(|(is_shown, popup)| is_shown.then_some(popup))
+    }
+
+    fn close_popup(&mut self, popup: Popup) {
+        match popup {
+            Popup::Export => self.is_show_export_popup = false,
+            Popup::Trash => self.is_show_trash_popup = false,
+            Popup::Method => self.is_show_method_popup = false,
+            Popup::Workspace => self.is_show_workspace_popup = false,
+            Popup::History => self.is_show_history_popup = false,
+            Popup::Help => self.is_show_help_popup = false,
+        }
+    }
+
+    // the list of the popup moved through with move-up and move-down
+    fn popup_list_state(&mut self, popup: Popup) -> Option<&mut ListState> {
+        match popup {
+            Popup::Trash => Some(&mut self.trash_list_state),
+            Popup::Method => Some(&mut self.method_list_state),
+            Popup::Workspace => Some(&mut self.workspace_list_state),
+            Popup::History => Some(&mut self.request_history_state),
+            Popup::Export | Popup::Help => None,
+        }
+    }
+
+    // the search of the history or the help, typed while the popup is shown
+    fn popup_filter(&mut self, popup: Popup) -> Option<&mut String> {
+        match popup {
+            Popup::History => {
+                self.request_history_state.select(Some(0));
+                Some(&mut self.request_history_filter)
+            }
+            Popup::Help => {
+                self.help_table_state.select(Some(0));
+                Some(&mut self.help_filter)
+            }
+            _ => None,
+        }
+    }
+
+    // popups have bindings of their own, characters which are not bound search the history
+    // and the help
+    fn handle_popup_key(&mut self, popup: Popup, key: KeyEvent) {
+        let chord = KeyChord::from(key);
+        match self.resolve_key(chord, |keymap, keys| keymap.resolve_popup(popup, keys)) {
+            Resolution::Pending => return,
+            Resolution::Action(action) => self.perform_in_popup(popup, action),
+            Resolution::Unbound => {
+                let is_plain = !chord
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+                if let (true, KeyCode::Char(character)) = (is_plain, chord.code) {
+                    if let Some(filter) = self.popup_filter(popup) {
+                        filter.push(character);
+                    }
+                }
+            }
+        }
+        self.pending_keys.clear();
+    }
+
+    fn perform_in_popup(&mut self, popup: Popup, action: Action) {
+        match (popup, action) {
+            // a search is cleared before the popup is closed
+            (_, Action::Cancel) => match self.popup_filter(popup) {
+                Some(filter) if !filter.is_empty() => filter.clear(),
+                _ => self.close_popup(popup),
+            },
+            (_, Action::DeleteChar) => {
+                self.popup_filter(popup).and_then(String::pop);
+            }
+            (Popup::Export, Action::PreviousFormat) => {
+                let count = SnippetFormat::iter().count();
+                self.selected_export_format = (self.selected_export_format + count - 1) % count;
+            }
+            (Popup::Export, Action::NextFormat) => {
+                self.selected_export_format =
+                    (self.selected_export_format + 1) % SnippetFormat::iter().count();
+            }
+            (Popup::Export, Action::Copy) => self.copy_export_snippet(),
+            (Popup::Trash, Action::Restore) => self.restore_from_trash(),
+            (Popup::Trash, Action::Purge) => self.purge_from_trash(false),
+            (Popup::Trash, Action::PurgeAll) => self.purge_from_trash(true),
+            (Popup::Method, Action::Submit) => self.pick_method(),
+            (Popup::Workspace, Action::Submit) => self.open_selected_workspace(),
+            (Popup::History, Action::Submit) => self.replay_history_entry(),
+            (Popup::History, Action::SaveAsRequest) => self.prompt_save_history_entry(),
+            (Popup::History, Action::ExportHistory) => self.prompt(WindowOperation::ExportHistory),
+            (Popup::Help, Action::MoveUp) => self.help_table_state.select_previous(),
+            (Popup::Help, Action::MoveDown) => self.help_table_state.select_next(),
+            (Popup::Help, Action::PageUp) => self.help_table_state.scroll_up_by(HELP_PAGE),
+            (Popup::Help, Action::PageDown) => self.help_table_state.scroll_down_by(HELP_PAGE),
+            (_, Action::MoveUp) => {
+                if let Some(list_state) = self.popup_list_state(popup) {
+                    list_state.select_previous();
+                }
+            }
+            (_, Action::MoveDown) => {
+                if let Some(list_state) = self.popup_list_state(popup) {
+                    list_state.select_next();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // the body is edited in place, typing starts right away
+    fn open_body_editor(&mut self) {
+        if !self.is_request_open() {
+            return;
+        }
+        let mut editor = TextEditor::new(&self.request_data.body);
+        editor.checkpoint();
+        self.body_editor = Some(editor);
+        self.focused_window = FocusedWindow::Editor;
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Shows the changed request, which waits in its tab until it is saved.
+    pub fn change_request(&mut self, request: RequestStructure) {
+        self.request_data = request;
+        self.is_request_modified = true;
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.is_request_modified
+            || self
+                .body_editor
+                .as_ref()
+                .is_some_and(TextEditor::is_modified)
+    }
+
+    // tells whether the request is saved now, which it is when nothing was changed
+    fn save_request(&mut self) -> bool {
+        if !self.has_unsaved_changes() {
+            return true;
+        }
+        let mut request = self.request_data.clone();
+        if let Some(editor) = self.body_editor.as_ref() {
+            request.body = editor.text();
+        }
+        let saved = self
+            .loaded_request_path()
+            .and_then(|path| request_parser::update_request(&path, request.clone()));
+        match saved {
+            Ok(_) => {
+                self.request_data = request;
+                self.is_request_modified = false;
+                if let Some(editor) = self.body_editor.as_mut() {
+                    editor.mark_saved();
+                }
+                true
+            }
+            Err(err) => {
+                self.show_error_popup(format!("Failed to save the request: {}", err));
+                false
+            }
+        }
+    }
+
+    // the edits of the body stay with the request until it is saved
+    fn close_body_editor(&mut self) {
+        if let Some(editor) = self.body_editor.take() {
+            if editor.is_modified() {
+                self.request_data.body = editor.text();
+                self.is_request_modified = true;
+            }
+        }
+        self.input_mode = InputMode::Normal;
+        if self.focused_window == FocusedWindow::Editor {
+            self.focused_window = FocusedWindow::Request;
+        }
+    }
+
+    // the keys of the editor, actions it has no use for are left to the app
+    fn perform_in_editor(&mut self, action: Action) -> bool {
+        let Some(editor) = self.body_editor.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::MoveUp => editor.move_up(),
+            Action::MoveDown => editor.move_down(),
+            Action::MoveLeft | Action::CursorLeft => editor.move_left(),
+            Action::MoveRight | Action::CursorRight => editor.move_right(),
+            Action::MoveFirst => editor.move_to_first_line(),
+            Action::MoveLast => editor.move_to_last_line(),
+            Action::CursorStart => editor.move_to_line_start(),
+            Action::CursorEnd => editor.move_to_line_end(),
+            Action::WordLeft => editor.move_word_left(),
+            Action::WordRight => editor.move_word_right(),
+            Action::MatchBracket => editor.jump_to_matching_bracket(),
+            Action::DeleteChar => editor.delete_before(),
+            Action::DeleteWord => editor.delete_word_before(),
+            Action::DeleteToStart => editor.delete_to_line_start(),
+            Action::DeleteToEnd => editor.delete_to_line_end(),
+            Action::NewLine => editor.new_line(),
+            Action::Indent => editor.indent(),
+            Action::DeleteLine => editor.delete_line(),
+            Action::UndoEdit => editor.undo(),
+            Action::RedoEdit => editor.redo(),
+            // in normal mode every change is a step of its own
+            Action::DeleteCharForward => {
+                if self.input_mode == InputMode::Normal {
+                    editor.checkpoint();
+                }
+                editor.delete_after()
+            }
+            Action::InsertMode
+            | Action::Append
+            | Action::InsertLineStart
+            | Action::AppendLineEnd
+            | Action::OpenLineBelow
+            | Action::OpenLineAbove => {
+                editor.checkpoint();
+                match action {
+                    Action::Append => editor.move_right(),
+                    Action::InsertLineStart => editor.move_to_indentation(),
+                    Action::AppendLineEnd => editor.move_to_line_end(),
+                    Action::OpenLineBelow => editor.open_line_below(),
+                    Action::OpenLineAbove => editor.open_line_above(),
+                    _ => {}
+                }
+                self.input_mode = InputMode::Insert;
+            }
+            Action::NormalMode => self.input_mode = InputMode::Normal,
+            Action::Save => {
+                self.save_request();
+            }
+            Action::CloseEditor => self.close_body_editor(),
+            // moving to another window leaves the editor
+            Action::FocusCollections | Action::FocusRequest | Action::FocusResponse => {
+                self.close_body_editor();
+                return false;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn perform(&mut self, action: Action, terminal: &mut DefaultTerminal) {
+        if self.focused_window == FocusedWindow::Editor && self.perform_in_editor(action) {
+            return;
+        }
+        match action {
+            Action::Quit => self.quit(),
+            Action::LogRequest => helpers::logger(format!("{:?}", self.request_data)),
+            Action::CommandMode => {
+                self.input_strategy = InputStrategy::Command;
+                self.input_mode = InputMode::Control;
+            }
+            Action::SearchMode => {
+                self.input_strategy = InputStrategy::Search;
+                self.input_mode = InputMode::Control;
+            }
+            Action::Help => self.show_help_popup(),
+            Action::FocusCollections => self.focused_window = FocusedWindow::Collections,
+            Action::FocusRequest => self.focused_window = FocusedWindow::Request,
+            Action::FocusResponse => self.focused_window = FocusedWindow::Response,
+            // the motions are named after the direction of the list, not of the selection
+            Action::MoveUp => self.select_collection_to_send_motion(WindowMotion::Down),
+            Action::MoveDown => self.select_collection_to_send_motion(WindowMotion::Up),
+            Action::MoveLeft => self.select_collection_to_send_motion(WindowMotion::Left),
+            Action::MoveRight => self.select_collection_to_send_motion(WindowMotion::Right),
+            Action::MoveFirst => self.select_collection_to_send_motion(WindowMotion::First),
+            Action::MoveLast => self.select_collection_to_send_motion(WindowMotion::Last),
+            Action::Create if self.is_header_tab_selected() => self.edit_header(None),
+            Action::Create if self.focused_window == FocusedWindow::Collections => {
+                self.prompt(WindowOperation::Create)
+            }
+            // nothing else is created from a prompt
+            Action::Create => {}
+            Action::Delete if self.is_header_tab_selected() => {
+                self.change_selected_header(|headers, index| {
+                    headers.remove(index);
+                    index.min(headers.len().saturating_sub(1))
+                })
+            }
+            Action::Delete if self.focused_window == FocusedWindow::Collections => {
+                self.prompt_delete()
+            }
+            Action::Delete => {}
+            Action::ToggleHeader => self.change_selected_header(|headers, index| {
+                headers[index].enabled = !headers[index].enabled;
+                index
+            }),
+            Action::MoveHeaderUp => self.change_selected_header(|headers, index| {
+                headers.swap(index, index.saturating_sub(1));
+                index.saturating_sub(1)
+            }),
+            Action::MoveHeaderDown => self.change_selected_header(|headers, index| {
+                let below = (index + 1).min(headers.len() - 1);
+                headers.swap(index, below);
+                below
+            }),
+            Action::Undo => self.undo_delete(),
+            Action::Rename => self.prompt_with_selected_name(WindowOperation::Rename),
+            Action::Duplicate => self.prompt_with_selected_name(WindowOperation::Duplicate),
+            Action::Move => self.prompt(WindowOperation::Move),
+            Action::Open => {
+                self.execute_operation_on_selected_window(WindowOperation::Open, Some(terminal))
+            }
+            Action::Edit => self.execute_operation_on_selected_window(WindowOperation::Edit, None),
+            Action::EditExternally => {
+                let target = match RequestWidgetTabs::iter().nth(self.selected_tab) {
+                    _ if self.focused_window != FocusedWindow::Request => EditTarget::File,
+                    Some(RequestWidgetTabs::Body) => EditTarget::Body,
+                    Some(RequestWidgetTabs::Header) => EditTarget::Headers,
+                    _ => EditTarget::File,
+                };
+                self.edit_externally(target, terminal)
+            }
+            Action::OpenTab => self.open_tab(),
+            Action::NextTab => self.switch_tab((self.active_tab + 1) % self.tabs.len()),
+            Action::PreviousTab => {
+                self.switch_tab((self.active_tab + self.tabs.len() - 1) % self.tabs.len())
+            }
+            Action::CloseTab => self.close_tab(),
+            Action::Export => self.show_export_popup(),
+            Action::Send => self.send_request(),
+            Action::ShowHistory => self.show_history_popup(),
+            Action::PickMethod => self.show_method_popup(),
+            Action::EditUrl => self.edit_url(),
+            Action::Cancel => {
+                self.is_show_popup = false;
+                self.url_input = None;
+                self.input_mode = InputMode::Normal;
+                self.reset_input();
+            }
+            Action::Submit => match self.input_mode {
+                InputMode::Control => self.submit_message(),
+                InputMode::Insert => self.handle_enter_on_insert_mode(),
+                InputMode::Normal => {}
+            },
+            Action::CursorLeft => self.active_input().move_left(false),
+            Action::CursorRight => self.active_input().move_right(false),
+            Action::CursorStart => self.active_input().move_to_start(false),
+            Action::CursorEnd => self.active_input().move_to_end(false),
+            Action::WordLeft => self.active_input().move_word_left(false),
+            Action::WordRight => self.active_input().move_word_right(false),
+            Action::SelectLeft => self.active_input().move_left(true),
+            Action::SelectRight => self.active_input().move_right(true),
+            Action::SelectStart => self.active_input().move_to_start(true),
+            Action::SelectEnd => self.active_input().move_to_end(true),
+            Action::DeleteChar => self.active_input().delete_before(),
+            Action::DeleteCharForward => self.active_input().delete_after(),
+            Action::DeleteWord => self.active_input().delete_word_before(),
+            Action::DeleteToStart => self.active_input().delete_to_start(),
+            Action::DeleteToEnd => self.active_input().delete_to_end(),
+            Action::NextField => self.handle_tab_key(),
+            Action::Complete => match self.input_mode {
+                InputMode::Insert if self.url_input.is_none() => self.complete_header_field(),
+                _ => self.complete_command(),
+            },
+            // only the editor knows what to do with these
+            Action::InsertMode
+            | Action::Append
+            | Action::InsertLineStart
+            | Action::AppendLineEnd
+            | Action::OpenLineBelow
+            | Action::OpenLineAbove
+            | Action::NormalMode
+            | Action::NewLine
+            | Action::Indent
+            | Action::DeleteLine
+            | Action::UndoEdit
+            | Action::RedoEdit
+            | Action::MatchBracket
+            | Action::CloseEditor => {}
+            // only the popups know what to do with these
+            Action::PreviousFormat
+            | Action::NextFormat
+            | Action::Copy
+            | Action::Restore
+            | Action::Purge
+            | Action::PurgeAll
+            | Action::SaveAsRequest
+            | Action::ExportHistory
+            | Action::PageUp
+            | Action::PageDown => {}
+            Action::Save => {
+                self.save_request();
+            }
+            Action::HistoryPrevious => self.browse_history(true),
+            Action::HistoryNext => self.browse_history(false),
+        }
+    }
+
+    fn complete_command(&mut self) {
+        if self.input_strategy != InputStrategy::Command {
+            return;
+        }
+        let completion = match self.completion.take() {
+            Some(mut completion) => {
+                completion.next();
+                completion
+            }
+            None => commands::complete(self.input.text(), &self.config.aliases, &self.workspace),
+        };
+        if completion.candidates.is_empty() {
+            return;
+        }
+
+        self.input.set_text(completion.text());
+        if completion.candidates.len() > 1 {
+            self.completion = Some(completion);
+        }
+    }
+
+    // the name of a header completes to the standard names, the value to the values
+    // common for the name
+    fn complete_header_field(&mut self) {
+        let completion = match self.completion.take() {
+            Some(mut completion) => {
+                completion.next();
+                completion
+            }
+            None => {
+                let field = |key: u8| {
+                    self.input_buffer
+                        .get(&key)
+                        .map(|input| input.text().to_string())
+                        .unwrap_or_default()
+                };
+                let candidates = match self.sub_focus_element {
+                    0 => headers::complete_name(&field(0)),
+                    1 => headers::complete_value(&field(0), &field(1)),
+                    _ => vec![],
+                };
+                Completion {
+                    base: String::new(),
+                    candidates,
+                    index: 0,
+                }
+            }
+        };
+        if completion.candidates.is_empty() {
+            return;
+        }
+
+        let candidate = completion.candidates[completion.index].clone();
+        self.active_input().set_text(candidate);
+        if completion.candidates.len() > 1 {
+            self.completion = Some(completion);
+        }
+    }
+
+    // up goes to older commands, down back to newer ones and finally to what was typed
+    fn browse_history(&mut self, older: bool) {
+        if self.input_strategy != InputStrategy::Command || self.command_history.is_empty() {
+            return;
+        }
+        let newest = self.command_history.len() - 1;
+        self.history_index = match (self.history_index, older) {
+            (None, true) => {
+                self.history_draft = self.input.text().to_string();
+                Some(newest)
+            }
+            (None, false) => return,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index < newest => Some(index + 1),
+            (Some(_), false) => None,
+        };
+
+        let text = match self.history_index {
+            Some(index) => self.command_history[index].clone(),
+            None => std::mem::take(&mut self.history_draft),
+        };
+        self.input.set_text(text);
+    }
+
     fn submit_message(&mut self) {
+        if self.input_strategy == InputStrategy::Command {
+            let command = self.input.text().trim().to_string();
+            if !command.is_empty() && self.command_history.last() != Some(&command) {
+                self.command_history.push(command);
+            }
+            self.history_index = None;
+        }
+        // the input is done with before the command runs, which may prompt again
+        let input = self.input.text().to_string();
+        self.input.clear();
+        self.input_mode = InputMode::Normal;
         if self.input_strategy == InputStrategy::Prompt {
-            self.execute_operation_on_selected_window_with_promt(
-                self.current_operation,
-                self.input.clone(),
-            );
+            self.execute_operation_on_selected_window_with_promt(self.current_operation, input);
         } else {
-            handler::event_handler(self.input_strategy.clone(), self.input.clone(), self);
+            handler::event_handler(self.input_strategy.clone(), input, self);
         }
         //match self.input_strategy{
         //    InputStrategy::Search => {}
         //    InputStrategy::Prompt =>{}
         //    InputStrategy::Command =>{}
         //}
-        self.input.clear();
-        self.reset_cursor();
-        self.input_mode = InputMode::Normal;
     }
 
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         loop {
+            self.receive_response();
             terminal.draw(|frame| self.draw(frame))?;
 
-            if let Event::Key(key) = event::read()? {
-                // keybindings
-                match self.input_mode {
-                    InputMode::Normal => match key.code {
-                        KeyCode::Char('c') => {
-                            if key.modifiers == KeyModifiers::CONTROL {
-                                super::handler::exit_app();
-                            }
-                        }
-                        KeyCode::Char('v') => {
-                            if key.modifiers == KeyModifiers::CONTROL {
-                                helpers::logger(format!("{:?}", self.request_data))
-                            }
-                        }
-                        KeyCode::Char(':') => {
-                            self.input_strategy = InputStrategy::Command;
-                            self.input_mode = InputMode::Control;
-                        }
-                        KeyCode::Char('/') => {
-                            self.input_strategy = InputStrategy::Search;
-                            self.input_mode = InputMode::Control;
-                        }
-                        KeyCode::Char('1') => {
-                            self.focused_window = FocusedWindow::Collections;
-                        }
-                        KeyCode::Char('2') => {
-                            self.focused_window = FocusedWindow::Request;
-                        }
-                        KeyCode::Char('3') => {
-                            self.focused_window = FocusedWindow::Response;
-                        }
-                        KeyCode::Char('k') => {
-                            self.select_collection_to_send_motion(WindowMotion::Down)
-                        }
-                        KeyCode::Char('j') => {
-                            self.select_collection_to_send_motion(WindowMotion::Up)
-                        }
-                        KeyCode::Char('h') => {
-                            self.select_collection_to_send_motion(WindowMotion::Left)
-                        }
-                        KeyCode::Char('l') => {
-                            self.select_collection_to_send_motion(WindowMotion::Right)
-                        }
-                        KeyCode::Char('a') => self.prompt(WindowOperation::Create),
-                        KeyCode::Char('d') => self.prompt(WindowOperation::Delete),
-                        KeyCode::Char('o') => self.execute_operation_on_selected_window(
-                            WindowOperation::Open,
-                            Some(&mut terminal),
-                        ),
-                        KeyCode::Char('i') => {
-                            self.execute_operation_on_selected_window(WindowOperation::Edit, None)
-                        }
-                        KeyCode::Esc => {
-                            self.is_show_popup = false;
-                            self.input_mode = InputMode::Normal;
-                            self.reset_input();
+            // theme files are checked while waiting for keys, so changes show up right away,
+            // and so is the answer to a request being sent
+            let interval = match self.is_sending() {
+                true => RESPONSE_POLL_INTERVAL,
+                false => THEME_RELOAD_INTERVAL,
+            };
+            if !event::poll(interval)? {
+                self.reload_theme_if_changed();
+                continue;
+            }
+            let key = match event::read()? {
+                Event::Key(key) => key,
+                Event::Paste(text) => {
+                    match self.body_editor.as_mut() {
+                        Some(editor) if self.focused_window == FocusedWindow::Editor => {
+                            editor.insert_text(&text)
                         }
-                        _ => {}
-                    },
-                    InputMode::Control if key.kind == KeyEventKind::Press => match key.code {
-                        KeyCode::Enter => self.submit_message(),
-                        KeyCode::Char(to_insert) => self.enter_char(to_insert),
-                        KeyCode::Backspace => self.delete_char(),
-                        KeyCode::Left => self.move_cursor_left(),
-                        KeyCode::Right => self.move_cursor_right(),
-                        KeyCode::Esc => {
-                            self.is_show_popup = false;
-                            self.input_mode = InputMode::Normal;
-                            self.reset_input();
+                        _ if matches!(self.input_mode, InputMode::Control | InputMode::Insert) => {
+                            self.active_input().insert_str(&text)
                         }
                         _ => {}
-                    },
-                    InputMode::Insert if key.kind == KeyEventKind::Press => match key.code {
-                        // register keypresses in insert mode too
-                        KeyCode::Char(to_insert) => self.enter_char(to_insert),
-                        KeyCode::Backspace => self.delete_char(),
-                        KeyCode::Esc => {
-                            self.is_show_popup = false;
-                            self.input_mode = InputMode::Normal;
-                            self.reset_input();
-                        }
-                        KeyCode::Tab => self.handle_tab_key(),
-                        KeyCode::Enter => self.handle_enter_on_insert_mode(),
-                        _ => {}
-                    },
-                    InputMode::Control => {}
-                    InputMode::Insert => {}
+                    }
+                    continue;
                 }
+                _ => continue,
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.shown_popup() {
+                Some(popup) => self.handle_popup_key(popup, key),
+                None => self.handle_key(key, &mut terminal),
             }
         }
     }
@@ -455,21 +1747,219 @@ impl App {
 
     pub fn show_popup(&mut self, msg: String) {
         self.is_show_popup = true;
+        self.popup_type = LogTypes::Info;
         self.popup_msg = msg
     }
 
+    pub fn show_error_popup(&mut self, msg: String) {
+        self.show_popup(msg);
+        self.popup_type = LogTypes::Error;
+    }
+
+    // resolve the selected request and show it as client code snippets
+    pub fn show_export_popup(&mut self) {
+        if !self.show_collection_children || self.is_folder_selected() {
+            self.show_error_popup("Select a request to export".to_string());
+            return;
+        }
+        self.export_request =
+            environment::resolve_request(&self.edited_request(), &self.variables());
+        self.is_show_export_popup = true;
+    }
+
+    // collections and folders list everything going to the trash with them before asking
+    fn prompt_delete(&mut self) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        self.delete_contents = match !self.show_collection_children || self.is_folder_selected() {
+            true => handler::list_collection_children(&self.workspace, entry.clone()),
+            false => vec![],
+        };
+        if !self.delete_contents.is_empty() {
+            self.show_popup(format!(
+                "{} holds:\n{}",
+                entry,
+                self.delete_contents.join("\n")
+            ));
+        }
+        self.prompt(WindowOperation::Delete);
+    }
+
+    // bring back what was deleted last in this session
+    fn undo_delete(&mut self) {
+        let Some(id) = self.undo_stack.pop() else {
+            self.show_popup("Nothing to undo".to_string());
+            return;
+        };
+        match trash::restore(&self.workspace, &id) {
+            Ok(restored) => self.follow_entry(&restored),
+            Err(err) => self.show_error_popup(format!("Failed to undo: {}", err)),
+        }
+    }
+
+    pub fn show_trash_popup(&mut self) {
+        self.trash_entries = trash::list(&self.workspace);
+        self.trash_list_state.select(Some(0));
+        self.is_show_trash_popup = true;
+    }
+
+    fn selected_trash_entry(&self) -> Option<trash::TrashEntry> {
+        self.trash_list_state
+            .selected()
+            .and_then(|index| self.trash_entries.get(index))
+            .cloned()
+    }
+
+    fn restore_from_trash(&mut self) {
+        let Some(entry) = self.selected_trash_entry() else {
+            return;
+        };
+        self.is_show_trash_popup = false;
+        self.undo_stack.retain(|id| *id != entry.id);
+        match trash::restore(&self.workspace, &entry.id) {
+            Ok(restored) => self.follow_entry(&restored),
+            Err(err) => self.show_error_popup(format!("Failed to restore {}: {}", entry.path, err)),
+        }
+    }
+
+    // remove the highlighted entry, or everything, from the trash for good
+    fn purge_from_trash(&mut self, all: bool) {
+        let purged = match (all, self.selected_trash_entry()) {
+            (true, _) => trash::purge_all(&self.workspace),
+            (false, Some(entry)) => trash::purge(&self.workspace, &entry.id),
+            (false, None) => return,
+        };
+        if let Err(err) = purged {
+            self.is_show_trash_popup = false;
+            self.show_error_popup(format!("Failed to purge the trash: {}", err));
+            return;
+        }
+        self.trash_entries = trash::list(&self.workspace);
+        self.undo_stack
+            .retain(|id| self.trash_entries.iter().any(|entry| entry.id == *id));
+    }
+
+    pub fn show_help_popup(&mut self) {
+        self.help_filter.clear();
+        self.help_table_state.select(Some(0));
+        self.is_show_help_popup = true;
+    }
+
+    pub fn show_workspace_popup(&mut self) {
+        self.recent_workspaces = workspace::recent();
+        self.workspace_list_state.select(Some(0));
+        self.is_show_workspace_popup = true;
+    }
+
+    fn open_selected_workspace(&mut self) {
+        self.is_show_workspace_popup = false;
+        if let Some(root) = self
+            .workspace_list_state
+            .selected()
+            .and_then(|index| self.recent_workspaces.get(index))
+            .cloned()
+        {
+            self.switch_workspace(&root);
+        }
+    }
+
+    // everything shown belongs to the previous workspace, start over at its collections
+    pub fn switch_workspace(&mut self, path: &Path) {
+        match workspace::open(path) {
+            Ok(workspace) => {
+                self.workspace = workspace;
+                self.show_collection_children = false;
+                self.selected_collection.clear();
+                self.expanded_folders.clear();
+                self.undo_stack.clear();
+                // the environments belong to the workspace
+                self.active_environment = None;
+                // the open requests belong to the workspace as well
+                self.tabs = vec![RequestTab::default()];
+                self.active_tab = 0;
+                self.restore_tab(RequestTab::default());
+                self.focused_window = FocusedWindow::Collections;
+                self.collection_window_list_state.select(Some(0));
+                self.reload_collections();
+                self.show_popup(format!(
+                    "Opened the workspace {}",
+                    self.workspace.root.display()
+                ));
+            }
+            Err(err) => self.show_error_popup(format!("Failed to open the workspace: {}", err)),
+        }
+    }
+
+    /// Switches to the theme picked by name, or back to the configured theme without one.
+    pub fn set_theme(&mut self, name: Option<String>) {
+        if let Some(name) = &name {
+            let names = theme::theme_names();
+            if !names.contains(name) {
+                self.show_error_popup(format!(
+                    "Unknown theme {}, pick one of: {}",
+                    name,
+                    names.join(", ")
+                ));
+                return;
+            }
+        }
+        let (config, errors) = theme::load_theme(name.as_deref(), &self.workspace.root);
+        self.theme = config;
+        self.theme_modified =
+            theme::modified_times(&theme::theme_files(name.as_deref(), &self.workspace.root));
+        self.theme_name = name;
+        if !errors.is_empty() {
+            self.show_error_popup(format!("Failed to load the theme:\n{}", errors.join("\n")));
+        }
+    }
+
+    fn reload_theme_if_changed(&mut self) {
+        let files = theme::theme_files(self.theme_name.as_deref(), &self.workspace.root);
+        if theme::modified_times(&files) != self.theme_modified {
+            self.set_theme(self.theme_name.clone());
+        }
+    }
+
+    fn copy_export_snippet(&mut self) {
+        let format = SnippetFormat::iter()
+            .nth(self.selected_export_format)
+            .unwrap_or_default();
+        let snippet = exporters::render(format, &self.export_request);
+
+        self.is_show_export_popup = false;
+        match helpers::copy_to_clipboard(&snippet) {
+            Ok(_) => self.show_popup(format!("Copied as {}", format)),
+            Err(err) => self.show_error_popup(format!("Failed to copy: {}", err)),
+        }
+    }
+
     // saving the header in the json
     fn handle_enter_on_insert_mode(&mut self) {
+        if self.url_input.is_some() {
+            self.apply_url();
+            return;
+        }
         match self.focused_window {
             FocusedWindow::Request => match self.selected_tab {
                 0 => {
-                    // accept enter only when focused on the add element
-                    if self.sub_focus_element == 2 {
-                        let _ = events::enter::request_widget_edit_headers_enter_event(
-                            &self.input_buffer,
-                            &self.get_request_file_path().unwrap(),
-                        )
-                        .unwrap();
+                    let mut request = self.request_data.clone();
+                    let changed = events::enter::request_widget_edit_headers_enter_event(
+                        &self.input_buffer,
+                        &mut request,
+                        self.editing_header,
+                    );
+                    match changed {
+                        Ok(index) => {
+                            self.change_request(request);
+                            self.current_operation = WindowOperation::Null;
+                            self.input_buffer.clear();
+                            self.input_mode = InputMode::Normal;
+                            self.header_table_state.select(Some(index));
+                        }
+                        Err(err) => {
+                            self.show_error_popup(format!("Failed to change the header: {}", err))
+                        }
                     }
                 }
                 _ => {}
@@ -478,10 +1968,9 @@ impl App {
         }
     }
 
-    // use this method to update the input buufer when the sub_focus_element value changes
+    // every field keeps its own text, so moving between them loses nothing
     fn update_sub_focus_element(&mut self, value: u8) {
         self.sub_focus_element = value;
-        self.input.clear();
     }
 
     fn handle_tab_key(&mut self) {
@@ -490,7 +1979,8 @@ impl App {
                 FocusedWindow::Request => match self.current_operation {
                     WindowOperation::Edit => {
                         self.update_sub_focus_element(self.sub_focus_element + 1);
-                        if self.sub_focus_element > 2 {
+                        // the name, value and description fields and the button
+                        if self.sub_focus_element > 3 {
                             self.update_sub_focus_element(0);
                         }
                     }
@@ -503,30 +1993,90 @@ impl App {
     }
 
     fn reset_input(&mut self) {
+        self.history_index = None;
+        self.completion = None;
         self.input.clear();
-        self.delete_char();
     }
 
     fn decide_input_title(&self) -> Result<String, Box<dyn Error>> {
         if self.input_strategy == InputStrategy::Command {
-            Ok("Command".to_string())
+            // the matches tab cycles through
+            match &self.completion {
+                Some(completion) => Ok(format!("Command ({})", completion.candidates.join(" "))),
+                None => Ok("Command".to_string()),
+            }
         } else if self.input_strategy == InputStrategy::Search {
             Ok("Search".to_string())
         } else {
             match self.current_operation {
                 WindowOperation::Create => {
                     if self.show_collection_children {
-                        Ok("Request Name".to_string())
+                        Ok("Request Name (end with / for a folder)".to_string())
                     } else {
                         Ok("Collection Name".to_string())
                     }
                 }
-                WindowOperation::Delete => Ok("Delete Collection [y/N]".to_string()),
-                _ => todo!(),
+                WindowOperation::Delete => {
+                    let entry = self.selected_entry().unwrap_or_default();
+                    match self.delete_contents.len() {
+                        0 => Ok(format!("Delete {} [y/N]", entry)),
+                        count => Ok(format!(
+                            "Delete {} and the {} requests in it [y/N]",
+                            entry, count
+                        )),
+                    }
+                }
+                WindowOperation::Rename => Ok("Rename to".to_string()),
+                WindowOperation::Duplicate => Ok("Duplicate as".to_string()),
+                WindowOperation::Move => Ok("Move to (collection/folder)".to_string()),
+                WindowOperation::Method => Ok("Method".to_string()),
+                WindowOperation::SaveHistory => Ok("Save as (collection/name)".to_string()),
+                WindowOperation::ExportHistory => Ok("Export to (HAR file)".to_string()),
+                WindowOperation::CloseTab => Ok(format!(
+                    "Save the changes to {} before closing it [y/n]",
+                    tab_title(self.request_path.as_ref())
+                )),
+                WindowOperation::Quit => {
+                    let modified: Vec<_> = self
+                        .tab_titles()
+                        .into_iter()
+                        .filter_map(|(title, modified)| modified.then_some(title))
+                        .collect();
+                    Ok(format!(
+                        "Save the changes to {} before quitting [y/n]",
+                        modified.join(", ")
+                    ))
+                }
+                // these never prompt
+                WindowOperation::Edit | WindowOperation::Open | WindowOperation::Null => {
+                    Ok(String::new())
+                }
             }
         }
     }
 
+    // collections are listed flat, the opened collection as an indented tree
+    fn collection_list_item(&self, item: &str) -> String {
+        if !self.show_collection_children {
+            return format!("\u{f024b} {}", item);
+        }
+
+        let (depth, name) = match item.rsplit_once('/') {
+            Some((folder, name)) => (folder.split('/').count(), name),
+            None => (0, item),
+        };
+        let icon = if self.expanded_folders.contains(item) {
+            "\u{f07c}"
+        } else if self.collection_folders.contains(item) {
+            "\u{f07b}"
+        } else if self.broken_requests.contains(item) {
+            "\u{f071}"
+        } else {
+            "\u{f323}"
+        };
+        format!("{}{} {}", "  ".repeat(depth), icon, name)
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         let [header, content, footer] = Layout::vertical([
             Constraint::Length(1),
@@ -551,8 +2101,21 @@ impl App {
             .areas(content);
         }
 
-        drawable::header::draw(frame, header);
-        drawable::footer::draw(frame, footer, &self.focused_window);
+        drawable::header::draw(
+            frame,
+            header,
+            &self.theme,
+            &self.tab_titles(),
+            self.active_tab,
+        );
+        drawable::footer::draw(
+            frame,
+            footer,
+            &self.theme,
+            &self.keymap,
+            &self.input_mode,
+            &self.focused_window,
+        );
 
         self.rectangles
             .insert("v0".into(), *vertical_layout.get(0).unwrap());
@@ -561,37 +2124,20 @@ impl App {
         self.rectangles
             .insert("v2".into(), *vertical_layout.get(2).unwrap());
 
-        let input = Paragraph::new(self.input.as_str())
-            .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Control => {
-                    Style::default().fg(Color::from_u32(self.theme.focus.foreground))
-                }
-                _ => Style::default(),
-            })
-            .block(
-                Block::bordered()
-                    .border_type(BorderType::Rounded)
-                    .title(self.decide_input_title().unwrap()),
-            );
         // render the input field only in editing mode
         if self.input_mode == InputMode::Control {
-            frame.render_widget(input, self.get_rectangle("v0".into()))
-        }
-        match self.input_mode {
-            // Hide the cursor. `Frame` does this by default, so we don't need to do anything here
-            InputMode::Normal => {}
-            // Make the cursor visible and ask ratatui to put it at the specified coordinates after
-            // rendering
-            #[allow(clippy::cast_possible_truncation)]
-            InputMode::Control => frame.set_cursor_position(Position::new(
-                // Draw the cursor at the current position in the input field.
-                // This position is can be controlled via the left and right arrow key
-                self.get_rectangle("v0".into()).x + self.character_index as u16 + 1,
-                // Move one line down, from the border to the input line
-                self.get_rectangle("v0".into()).y + 1,
-            )),
-            _ => {}
+            let area = self.get_rectangle("v0".into());
+            let block = Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title(self.decide_input_title().unwrap());
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            self.input.draw(
+                frame,
+                inner,
+                Style::default().fg(Color::from(self.theme.focus.foreground)),
+                true,
+            );
         }
 
         // 1st horizontal layout split the 2nd vertical layout horizontally
@@ -605,22 +2151,29 @@ impl App {
             .insert("h1".into(), *horizontal_layout.get(1).unwrap());
 
         // http method widget
-        let http_method_widget = Paragraph::new("POST")
-            .style(Style::default().add_modifier(Modifier::BOLD))
+        let http_method_widget = Paragraph::new(self.request_data.method.clone())
+            .style(
+                Style::default()
+                    .fg(self.theme.method_color(&self.request_data.method))
+                    .add_modifier(Modifier::BOLD),
+            )
             .alignment(Alignment::Center)
             .block(
                 Block::bordered()
                     .border_type(BorderType::Rounded)
-                    .style(Color::from_u32(self.theme.focus.border)),
+                    .border_style(Color::from(self.theme.focus.border)),
             );
         frame.render_widget(http_method_widget, self.get_rectangle("h0".into()));
         // url
-        let url_widget = Paragraph::new(self.request_data.url.clone()).block(
-            Block::bordered()
-                .border_type(BorderType::Rounded)
-                .style(Color::White),
+        let variables = self.variables();
+        drawable::url::draw(
+            frame,
+            self.get_rectangle("h1".into()),
+            &self.theme,
+            &self.request_data.url,
+            self.url_input.as_mut(),
+            &variables,
         );
-        frame.render_widget(url_widget, self.get_rectangle("h1".into()));
 
         // 2st horizontal layout
         // split the 3nd vertical layout horizontally
@@ -639,21 +2192,15 @@ impl App {
             self.collections
                 .clone()
                 .iter()
-                .map(|item|
-                   if self.show_collection_children{
-                   "\u{f323} ".to_string() 
-                   }else{
-                   "\u{f024b} ".to_string() 
-                   }
-                    + item),
+                .map(|item| self.collection_list_item(item)),
         )
         .block(
             theme::set_border_style(
                 self.focused_window == FocusedWindow::Collections,
-                self.theme.clone()
+                self.theme.clone(),
             )
             .unwrap()
-            .title(format!("[1] Collections  {}", self.selected_collection)),
+            .title(format!("[1] Collections  {}", self.breadcrumbs())),
         )
         .style(
             theme::match_color_theme_for_widgets(
@@ -705,25 +2252,267 @@ impl App {
         widgets::response::draw_response_widget(
             &self.theme,
             self.selected_response_tab,
+            self.response.as_ref(),
+            self.is_sending(),
             frame,
             &self.focused_window,
             response_widget_parent_container,
         );
 
+        if self.is_show_export_popup {
+            let area = self.popup_area(frame.area(), 70, 60);
+            drawable::export::draw(
+                frame,
+                area,
+                &self.theme,
+                &self.keymap,
+                self.selected_export_format,
+                &self.export_request,
+            );
+        }
+
+        if self.is_show_trash_popup {
+            let area = self.popup_area(frame.area(), 60, 50);
+            drawable::trash::draw(
+                frame,
+                area,
+                &self.theme,
+                &self.keymap,
+                &self.trash_entries,
+                &mut self.trash_list_state,
+            );
+        }
+
+        if self.is_show_method_popup {
+            let area = self.popup_area(frame.area(), 20, 40);
+            drawable::methods::draw(
+                frame,
+                area,
+                &self.theme,
+                &self.keymap,
+                &mut self.method_list_state,
+            );
+        }
+
+        if self.is_show_workspace_popup {
+            let area = self.popup_area(frame.area(), 60, 50);
+            drawable::workspaces::draw(
+                frame,
+                area,
+                &self.theme,
+                &self.keymap,
+                &self.workspace.root,
+                &self.recent_workspaces,
+                &mut self.workspace_list_state,
+            );
+        }
+
+        if self.is_show_history_popup {
+            let area = self.popup_area(frame.area(), 90, 80);
+            let entries: Vec<_> = self
+                .request_history
+                .iter()
+                .filter(|entry| entry.matches(&self.request_history_filter))
+                .collect();
+            drawable::history::draw(
+                frame,
+                area,
+                &self.theme,
+                &self.keymap,
+                &entries,
+                &self.request_history_filter,
+                &mut self.request_history_state,
+            );
+        }
+
+        if self.is_show_help_popup {
+            let area = self.popup_area(frame.area(), 80, 70);
+            drawable::help::draw(
+                frame,
+                area,
+                &self.theme,
+                &self.keymap,
+                &self.help_filter,
+                &mut self.help_table_state,
+            );
+        }
+
         if self.is_show_popup {
             //let cowsay = Command::new("cowsay")
             //    .arg(self.popup_msg.clone())
             //    .output()
             //    .unwrap()
             //    .stdout;
-            let msg = Paragraph::new(self.popup_msg.clone()).block(
-                Block::bordered()
-                    .border_type(BorderType::Rounded)
-                    .title_top(self.popup_type.to_string()),
-            );
+            let msg = Paragraph::new(self.popup_msg.clone())
+                .wrap(Wrap { trim: false })
+                .block(
+                    theme::popup_block(&self.theme, matches!(self.popup_type, LogTypes::Error))
+                        .title_top(self.popup_type.to_string()),
+                );
             let area = self.popup_area(frame.area(), 60, 20);
             frame.render_widget(Clear, area); //this clears out the background
             frame.render_widget(msg, area);
         }
     }
 }
+
+// `users.json`, or `api.http#2` for a request sharing its file
+fn tab_title(path: Option<&PathBuf>) -> String {
+    path.and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "[no request]".to_string())
+}
+
+// writes the edits of a tab which is not shown
+fn save_tab(tab: &mut RequestTab) -> anyhow::Result<()> {
+    let Some(path) = tab.path.as_ref().filter(|_| tab.is_modified()) else {
+        return Ok(());
+    };
+    let mut request = tab.request_data.clone();
+    if let Some(editor) = tab.body_editor.as_ref() {
+        request.body = editor.text();
+    }
+    request_parser::update_request(path, request.clone())?;
+    if let Some(editor) = tab.body_editor.as_mut() {
+        editor.mark_saved();
+    }
+    tab.request_data = request;
+    tab.is_request_modified = false;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // an app showing a request with one header, written to a file of its own
+    fn app_with_request(name: &str) -> (App, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("fetched-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("users.json");
+        let request = RequestStructure {
+            headers: vec![Header::new("Accept", "application/json")],
+            ..RequestStructure::default()
+        };
+        request_parser::update_request(&path, request).unwrap();
+
+        let mut app = App {
+            request_path: Some(path.clone()),
+            tabs: vec![RequestTab::default()],
+            focused_window: FocusedWindow::Request,
+            ..App::default()
+        };
+        app.load_request();
+        (app, path)
+    }
+
+    fn edit_first_header_value(app: &mut App, value: &str) {
+        app.edit_header(Some(0));
+        app.input_buffer.get_mut(&1).unwrap().set_text(value);
+        app.handle_enter_on_insert_mode();
+    }
+
+    #[test]
+    fn closing_a_tab_after_a_header_edit_asks_to_save_it() {
+        let (mut app, path) = app_with_request("close-tab");
+        edit_first_header_value(&mut app, "text/plain");
+        assert_eq!(app.tab_titles(), [("users.json".to_string(), true)]);
+        // nothing is written until it is saved
+        let saved = request_parser::read_request(&path).unwrap();
+        assert_eq!(saved.headers[0].value, "application/json");
+
+        app.close_tab();
+        assert!(matches!(app.current_operation, WindowOperation::CloseTab));
+        app.execute_operation_on_selected_window_with_promt(
+            WindowOperation::CloseTab,
+            "y".to_string(),
+        );
+        let saved = request_parser::read_request(&path).unwrap();
+        assert_eq!(saved.headers[0].value, "text/plain");
+        assert_eq!(app.request_path, None);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn closing_a_tab_without_saving_drops_the_header_edit() {
+        let (mut app, path) = app_with_request("drop-tab");
+        edit_first_header_value(&mut app, "text/plain");
+
+        app.close_tab();
+        app.execute_operation_on_selected_window_with_promt(
+            WindowOperation::CloseTab,
+            "n".to_string(),
+        );
+        let saved = request_parser::read_request(&path).unwrap();
+        assert_eq!(saved.headers[0].value, "application/json");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn moving_away_from_an_edited_request_keeps_the_edits_in_its_tab() {
+        let dir = std::env::temp_dir().join(format!("fetched-switch-away-{}", std::process::id()));
+        let workspace = Workspace::load(dir.clone());
+        let collection = workspace.collections_path().join("api");
+        fs::create_dir_all(&collection).unwrap();
+        for name in ["posts.json", "users.json"] {
+            let request = RequestStructure {
+                headers: vec![Header::new("Accept", "application/json")],
+                ..RequestStructure::default()
+            };
+            request_parser::update_request(&collection.join(name), request).unwrap();
+        }
+        let mut app = App {
+            workspace,
+            tabs: vec![RequestTab::default()],
+            show_collection_children: true,
+            selected_collection: "api".to_string(),
+            ..App::default()
+        };
+        app.reload_collections();
+        let users = app
+            .collections
+            .iter()
+            .position(|entry| entry == "users.json");
+        let posts = app
+            .collections
+            .iter()
+            .position(|entry| entry == "posts.json");
+        app.collection_window_list_state.select(users);
+        app.refresh_request_data();
+        app.focused_window = FocusedWindow::Request;
+        edit_first_header_value(&mut app, "text/plain");
+
+        app.collection_window_list_state.select(posts);
+        app.refresh_request_data();
+        let saved = request_parser::read_request(&collection.join("users.json")).unwrap();
+        assert_eq!(saved.headers[0].value, "application/json");
+        assert_eq!(
+            app.tab_titles(),
+            [
+                ("users.json".to_string(), true),
+                ("posts.json".to_string(), false)
+            ]
+        );
+
+        // the edits are still there when it is highlighted again
+        app.collection_window_list_state.select(users);
+        app.refresh_request_data();
+        assert_eq!(app.active_tab, 0);
+        assert_eq!(app.request_data.headers[0].value, "text/plain");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exports_the_body_as_it_is_edited() {
+        let (mut app, path) = app_with_request("export-edited");
+        app.show_collection_children = true;
+        app.body_editor = Some(TextEditor::new("{\"name\": \"ada\"}"));
+
+        app.show_export_popup();
+        assert!(app.is_show_export_popup);
+        assert_eq!(app.export_request.body, "{\"name\": \"ada\"}");
+        assert_eq!(app.export_request.headers[0].value, "application/json");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use crate::components::structs::RequestOptions;
+use crate::core::workspace::Workspace;
+use crate::core::{environment, handler, theme};
+
+/// Where the candidates for completing an argument come from.
+#[derive(Clone, Copy)]
+pub enum ArgumentKind {
+    Collection,
+    Environment,
+    Theme,
+    ExportFormat,
+    ImportSource,
+    RequestOption,
+    // anything, like a path or a value
+    Free,
+}
+
+/// A command of the `:` prompt.
+pub struct Command {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub arguments: &'static [ArgumentKind],
+    pub usage: &'static str,
+}
+
+pub const COMMANDS: [Command; 13] = [
+    Command {
+        name: "quit",
+        aliases: &["q", "exit"],
+        arguments: &[],
+        usage: "quit",
+    },
+    Command {
+        name: "send",
+        aliases: &[],
+        arguments: &[],
+        usage: "send",
+    },
+    Command {
+        name: "history",
+        aliases: &[],
+        arguments: &[],
+        usage: "history",
+    },
+    Command {
+        name: "env",
+        aliases: &["environment"],
+        arguments: &[ArgumentKind::Environment],
+        usage: "env [name|-]",
+    },
+    Command {
+        name: "new",
+        aliases: &[],
+        arguments: &[ArgumentKind::Collection, ArgumentKind::Free],
+        usage: "new <collection> <name>",
+    },
+    Command {
+        name: "import",
+        aliases: &[],
+        arguments: &[ArgumentKind::ImportSource, ArgumentKind::Free],
+        usage: "import curl|postman|insomnia|bruno|openapi|har <source>",
+    },
+    Command {
+        name: "export",
+        aliases: &[],
+        arguments: &[ArgumentKind::ExportFormat, ArgumentKind::Free],
+        usage: "export [curl|httpie|reqwest|python|fetch|har <file>]",
+    },
+    Command {
+        name: "theme",
+        aliases: &[],
+        arguments: &[ArgumentKind::Theme],
+        usage: "theme [name]",
+    },
+    Command {
+        name: "set",
+        aliases: &[],
+        arguments: &[ArgumentKind::RequestOption, ArgumentKind::Free],
+        usage: "set <option> <value>",
+    },
+    Command {
+        name: "move",
+        aliases: &["mv"],
+        arguments: &[ArgumentKind::Collection],
+        usage: "move <collection/folder>",
+    },
+    Command {
+        name: "trash",
+        aliases: &[],
+        arguments: &[],
+        usage: "trash",
+    },
+    Command {
+        name: "help",
+        aliases: &[],
+        arguments: &[],
+        usage: "help",
+    },
+    Command {
+        name: "workspace",
+        aliases: &["ws"],
+        arguments: &[ArgumentKind::Free],
+        usage: "workspace [path]",
+    },
+];
+
+const IMPORT_SOURCES: [&str; 7] = [
+    "curl", "postman", "insomnia", "bruno", "openapi", "swagger", "har",
+];
+const EXPORT_FORMATS: [&str; 6] = ["curl", "httpie", "reqwest", "python", "fetch", "har"];
+
+// the options of a request, named like in the request files
+const REQUEST_OPTIONS: [&str; 5] = [
+    "attach_cookies",
+    "follow_redirect",
+    "proxy",
+    "timeout_ms",
+    "validate_ssl",
+];
+
+/// The command called `name` or one of its aliases.
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS
+        .iter()
+        .find(|command| command.name == name || command.aliases.contains(&name))
+}
+
+/// Replaces an alias of the user at the start of the input with what it stands for, the
+/// arguments typed after it are kept. Built in commands can not be replaced.
+pub fn expand_alias(input: &str, aliases: &HashMap<String, String>) -> String {
+    let (name, rest) = input.split_once(' ').unwrap_or((input, ""));
+    match aliases.get(name) {
+        Some(expansion) if find(name).is_none() => {
+            format!("{} {}", expansion, rest).trim_end().to_string()
+        }
+        _ => input.to_string(),
+    }
+}
+
+/// The candidates for the word being typed, cycled through by pressing tab again.
+pub struct Completion {
+    // the input before the completed word
+    pub base: String,
+    pub candidates: Vec<String>,
+    pub index: usize,
+}
+
+impl Completion {
+    /// Moves on to the next candidate, back to the first after the last one.
+    pub fn next(&mut self) {
+        self.index = (self.index + 1) % self.candidates.len();
+    }
+
+    pub fn text(&self) -> String {
+        let candidate = &self.candidates[self.index];
+        // a single candidate is done, the next argument can be typed right away
+        match self.candidates.len() {
+            1 => format!("{}{} ", self.base, candidate),
+            _ => format!("{}{}", self.base, candidate),
+        }
+    }
+}
+
+fn candidates_for(kind: ArgumentKind, workspace: &Workspace) -> Vec<String> {
+    match kind {
+        ArgumentKind::Collection => handler::list_collections(workspace),
+        ArgumentKind::Environment => environment::names(&workspace.root),
+        ArgumentKind::Theme => theme::theme_names(),
+        ArgumentKind::ExportFormat => EXPORT_FORMATS.map(str::to_string).to_vec(),
+        ArgumentKind::ImportSource => IMPORT_SOURCES.map(str::to_string).to_vec(),
+        ArgumentKind::RequestOption => REQUEST_OPTIONS.map(str::to_string).to_vec(),
+        ArgumentKind::Free => vec![],
+    }
+}
+
+/// Completes the last word of the input, a command name or an argument of the command.
+/// Collections and environments are the ones of `workspace`.
+pub fn complete(
+    input: &str,
+    aliases: &HashMap<String, String>,
+    workspace: &Workspace,
+) -> Completion {
+    let split_at = input.rfind(' ').map_or(0, |index| index + 1);
+    let (base, word) = input.split_at(split_at);
+    let typed: Vec<_> = base.split_whitespace().collect();
+
+    let mut candidates = match typed.split_first() {
+        None => COMMANDS
+            .iter()
+            .flat_map(|command| std::iter::once(&command.name).chain(command.aliases))
+            .map(|name| name.to_string())
+            .chain(aliases.keys().cloned())
+            .collect(),
+        Some((name, arguments)) => {
+            let expanded = expand_alias(name, aliases);
+            let name = expanded.split(' ').next().unwrap_or_default();
+            find(name)
+                .and_then(|command| command.arguments.get(arguments.len()))
+                .map(|kind| candidates_for(*kind, workspace))
+                .unwrap_or_default()
+        }
+    };
+    candidates.retain(|candidate| candidate.starts_with(word));
+    candidates.sort();
+    candidates.dedup();
+
+    Completion {
+        base: base.to_string(),
+        candidates,
+        index: 0,
+    }
+}
+
+fn parse_flag(value: &str) -> anyhow::Result<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "on" | "yes" => Ok(true),
+        "false" | "off" | "no" => Ok(false),
+        _ => anyhow::bail!("{:?} is not true or false", value),
+    }
+}
+
+/// Changes an option of a request, like `timeout_ms 5000` or `validate_ssl off`.
+pub fn set_request_option(
+    options: &mut RequestOptions,
+    name: &str,
+    value: &str,
+) -> anyhow::Result<()> {
+    match name {
+        "attach_cookies" => options.attach_cookies = parse_flag(value)?,
+        "follow_redirect" => options.follow_redirect = parse_flag(value)?,
+        "validate_ssl" => options.validate_ssl = parse_flag(value)?,
+        "proxy" => options.proxy = value.to_string(),
+        "timeout_ms" => {
+            options.timeout_ms = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("{:?} is not a number of milliseconds", value))?
+        }
+        _ => anyhow::bail!(
+            "Unknown option {:?}, use one of {}",
+            name,
+            REQUEST_OPTIONS.join(", ")
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn aliases(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, expansion)| (name.to_string(), expansion.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn expands_aliases_keeping_the_arguments() {
+        let aliases = aliases(&[("gb", "theme gruvbox"), ("e", "export"), ("q", "send")]);
+        assert_eq!(expand_alias("gb", &aliases), "theme gruvbox");
+        assert_eq!(
+            expand_alias("e har out.har", &aliases),
+            "export har out.har"
+        );
+        // built in commands and their aliases win
+        assert_eq!(expand_alias("q", &aliases), "q");
+        assert_eq!(expand_alias("gbx", &aliases), "gbx");
+    }
+
+    #[test]
+    fn completes_commands_then_their_arguments() {
+        let root = std::env::temp_dir().join(format!("fetched-commands-{}", std::process::id()));
+        let workspace = Workspace::load(root.clone());
+        for collection in ["api", "admin", "billing"] {
+            fs::create_dir_all(workspace.collections_path().join(collection)).unwrap();
+        }
+        let aliases = aliases(&[("mk", "new")]);
+
+        let completion = complete("ne", &aliases, &workspace);
+        assert_eq!(completion.candidates, ["new"]);
+        assert_eq!(completion.text(), "new ");
+
+        let mut completion = complete("new a", &aliases, &workspace);
+        assert_eq!(completion.candidates, ["admin", "api"]);
+        assert_eq!(completion.text(), "new admin");
+        completion.next();
+        assert_eq!(completion.text(), "new api");
+        completion.next();
+        assert_eq!(completion.text(), "new admin");
+
+        // aliases complete the arguments of what they stand for
+        let completion = complete("mk b", &aliases, &workspace);
+        assert_eq!(completion.text(), "mk billing ");
+        // the second argument is free
+        assert!(complete("new api u", &aliases, &workspace)
+            .candidates
+            .is_empty());
+        assert!(complete("m", &aliases, &workspace)
+            .candidates
+            .contains(&"mk".to_string()));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn sets_request_options_from_text() {
+        let mut options = RequestOptions::default();
+        set_request_option(&mut options, "validate_ssl", "off").unwrap();
+        set_request_option(&mut options, "follow_redirect", "Yes").unwrap();
+        set_request_option(&mut options, "timeout_ms", "5000").unwrap();
+        set_request_option(&mut options, "proxy", "http://localhost:3128").unwrap();
+        assert!(!options.validate_ssl);
+        assert!(options.follow_redirect);
+        assert_eq!(options.timeout_ms, 5000);
+        assert_eq!(options.proxy, "http://localhost:3128");
+
+        let err = set_request_option(&mut options, "timeout_ms", "soon").unwrap_err();
+        assert_eq!(err.to_string(), "\"soon\" is not a number of milliseconds");
+        let err = set_request_option(&mut options, "attach_cookies", "maybe").unwrap_err();
+        assert_eq!(err.to_string(), "\"maybe\" is not true or false");
+        assert!(set_request_option(&mut options, "retries", "3").is_err());
+    }
+}
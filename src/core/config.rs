@@ -0,0 +1,32 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+use crate::constants::{CONFIG_FILE, CONFIG_PATH};
+use crate::core::helpers;
+
+/// Settings of the user, read from `~/.config/fetched/config.toml`.
+#[derive(Default, Deserialize)]
+pub struct UserConfig {
+    // `name = "command and arguments"`, typed as `:name` in the command prompt
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    // the command opening request files, when neither `$VISUAL` nor `$EDITOR` is set
+    pub editor: Option<String>,
+}
+
+/// Loads the settings of the user. A missing file means the defaults, a broken one is
+/// reported along with them.
+pub fn load_config() -> (UserConfig, Option<String>) {
+    let file = helpers::expand_path(CONFIG_PATH).join(CONFIG_FILE);
+    let Ok(raw_data) = fs::read_to_string(&file) else {
+        return (UserConfig::default(), None);
+    };
+    match toml::from_str(&raw_data) {
+        Ok(config) => (config, None),
+        Err(err) => (
+            UserConfig::default(),
+            Some(format!("{}: {}", file.display(), err.message())),
+        ),
+    }
+}
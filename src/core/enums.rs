@@ -1,4 +1,5 @@
-use strum_macros::{Display, EnumIter, FromRepr};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumMessage, EnumString, FromRepr};
 
 #[derive(Default, Clone, PartialEq, Display)]
 pub enum InputMode {
@@ -23,6 +24,8 @@ pub enum FocusedWindow {
     Request,
     Response,
     Input,
+    // the editor of the request body, inside the request window
+    Editor,
 }
 
 pub enum WindowMotion {
@@ -32,6 +35,190 @@ pub enum WindowMotion {
     Right,
     Expand,
     Collapse,
+    First,
+    Last,
+}
+
+/// What a key binding does, named in the keymap file like `move-down` or `command-mode`.
+/// The message is the description shown in the help.
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumString, EnumIter, EnumMessage)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Action {
+    #[strum(message = "Quit fetched")]
+    Quit,
+    #[strum(message = "Write the opened request to the log")]
+    LogRequest,
+    #[strum(message = "Type a command like :theme or :export")]
+    CommandMode,
+    #[strum(message = "Search the collections")]
+    SearchMode,
+    #[strum(message = "Show the help for every key binding")]
+    Help,
+    #[strum(message = "Focus the collections window")]
+    FocusCollections,
+    #[strum(message = "Focus the request window")]
+    FocusRequest,
+    #[strum(message = "Focus the response window")]
+    FocusResponse,
+    #[strum(message = "Select the entry above")]
+    MoveUp,
+    #[strum(message = "Select the entry below")]
+    MoveDown,
+    #[strum(message = "Go back out of a collection, or to the previous tab")]
+    MoveLeft,
+    #[strum(message = "Go into a collection, or to the next tab")]
+    MoveRight,
+    #[strum(message = "Select the first entry")]
+    MoveFirst,
+    #[strum(message = "Select the last entry")]
+    MoveLast,
+    #[strum(message = "Create a collection, folder or request")]
+    Create,
+    #[strum(message = "Move the selected entry to the trash")]
+    Delete,
+    #[strum(message = "Bring back the last deleted entry")]
+    Undo,
+    #[strum(message = "Rename the selected entry")]
+    Rename,
+    #[strum(message = "Copy the selected entry")]
+    Duplicate,
+    #[strum(message = "Move the selected entry into another collection or folder")]
+    Move,
+    #[strum(message = "Open the selected request in the editor")]
+    Open,
+    #[strum(message = "Edit the fields of the opened request")]
+    Edit,
+    #[strum(message = "Edit the body or headers of the tab, or the whole file, in $EDITOR")]
+    EditExternally,
+    #[strum(message = "Send the selected header or not")]
+    ToggleHeader,
+    #[strum(message = "Move the selected header up")]
+    MoveHeaderUp,
+    #[strum(message = "Move the selected header down")]
+    MoveHeaderDown,
+    #[strum(message = "Pick the method of the request")]
+    PickMethod,
+    #[strum(message = "Edit the URL of the request")]
+    EditUrl,
+    #[strum(message = "Open the selected request in a new tab")]
+    OpenTab,
+    #[strum(message = "Show the next request tab")]
+    NextTab,
+    #[strum(message = "Show the previous request tab")]
+    PreviousTab,
+    #[strum(message = "Close the request tab, asking to save unsaved changes")]
+    CloseTab,
+    #[strum(message = "Show the selected request as a code snippet")]
+    Export,
+    #[strum(message = "Send the open request")]
+    Send,
+    #[strum(message = "Browse the sent requests, replay them or save them as requests")]
+    ShowHistory,
+    #[strum(message = "Close the popup or leave the input")]
+    Cancel,
+    #[strum(message = "Run the command, save the input or pick the selected entry")]
+    Submit,
+    #[strum(message = "Move the cursor to the left")]
+    CursorLeft,
+    #[strum(message = "Move the cursor to the right")]
+    CursorRight,
+    #[strum(message = "Move the cursor to the start of the input")]
+    CursorStart,
+    #[strum(message = "Move the cursor to the end of the input")]
+    CursorEnd,
+    #[strum(message = "Move the cursor to the previous word")]
+    WordLeft,
+    #[strum(message = "Move the cursor to the next word")]
+    WordRight,
+    #[strum(message = "Select the character to the left")]
+    SelectLeft,
+    #[strum(message = "Select the character to the right")]
+    SelectRight,
+    #[strum(message = "Select up to the start of the input")]
+    SelectStart,
+    #[strum(message = "Select up to the end of the input")]
+    SelectEnd,
+    #[strum(message = "Delete the character before the cursor")]
+    DeleteChar,
+    #[strum(message = "Delete the character under the cursor")]
+    DeleteCharForward,
+    #[strum(message = "Delete the word before the cursor")]
+    DeleteWord,
+    #[strum(message = "Delete everything before the cursor")]
+    DeleteToStart,
+    #[strum(message = "Delete everything after the cursor")]
+    DeleteToEnd,
+    #[strum(message = "Go to the next field")]
+    NextField,
+    #[strum(message = "Start typing in the editor")]
+    InsertMode,
+    #[strum(message = "Start typing after the cursor")]
+    Append,
+    #[strum(message = "Start typing at the start of the line")]
+    InsertLineStart,
+    #[strum(message = "Start typing at the end of the line")]
+    AppendLineEnd,
+    #[strum(message = "Start typing on a new line below")]
+    OpenLineBelow,
+    #[strum(message = "Start typing on a new line above")]
+    OpenLineAbove,
+    #[strum(message = "Stop typing in the editor")]
+    NormalMode,
+    #[strum(message = "Break the line, keeping its indentation")]
+    NewLine,
+    #[strum(message = "Indent at the cursor")]
+    Indent,
+    #[strum(message = "Delete the line")]
+    DeleteLine,
+    #[strum(message = "Undo the last change in the editor")]
+    UndoEdit,
+    #[strum(message = "Redo the last undone change in the editor")]
+    RedoEdit,
+    #[strum(message = "Jump to the matching bracket")]
+    MatchBracket,
+    #[strum(message = "Save the changes of the request")]
+    Save,
+    #[strum(message = "Save and leave the editor")]
+    CloseEditor,
+    #[strum(message = "Complete the command or argument, again for the next match")]
+    Complete,
+    #[strum(message = "Show the previous command")]
+    HistoryPrevious,
+    #[strum(message = "Show the next command")]
+    HistoryNext,
+    #[strum(message = "Show the snippet in the previous format")]
+    PreviousFormat,
+    #[strum(message = "Show the snippet in the next format")]
+    NextFormat,
+    #[strum(message = "Copy the snippet to the clipboard")]
+    Copy,
+    #[strum(message = "Put the selected entry of the trash back")]
+    Restore,
+    #[strum(message = "Delete the selected entry of the trash for good")]
+    Purge,
+    #[strum(message = "Empty the trash")]
+    PurgeAll,
+    #[strum(message = "Save the selected history entry as a request")]
+    SaveAsRequest,
+    #[strum(message = "Export the listed history entries as a HAR file")]
+    ExportHistory,
+    #[strum(message = "Scroll up a page")]
+    PageUp,
+    #[strum(message = "Scroll down a page")]
+    PageDown,
+}
+
+/// The popups which take the keys while they are shown, each with bindings of its own
+/// under `[popup.<name>]` in the keymap.
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter)]
+#[strum(serialize_all = "lowercase")]
+pub enum Popup {
+    Export,
+    Trash,
+    Method,
+    Workspace,
+    History,
+    Help,
 }
 
 #[derive(Default, Clone, Copy)]
@@ -40,8 +227,19 @@ pub enum WindowOperation {
     Create,
     Delete,
     Rename,
+    Duplicate,
+    Move,
     Edit,
     Open,
+    // typing a method which is not in the list
+    Method,
+    // whether to save the changes of a tab before it is dropped
+    CloseTab,
+    Quit,
+    // naming the request a history entry is saved as
+    SaveHistory,
+    // the HAR file the listed history entries are exported to
+    ExportHistory,
     Null,
 }
 
@@ -90,7 +288,7 @@ pub enum LogTypes {
 }
 
 // widgets
-#[derive(Default, Debug, Clone, Copy, Display, FromRepr, EnumIter)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Display, FromRepr, EnumIter)]
 pub enum RequestWidgetTabs {
     #[default]
     #[strum(to_string = "Header")]
@@ -114,3 +312,59 @@ pub enum ResponseWidgetTabs {
     //#[strum(to_string = "Authentication")]
     //Authentication,
 }
+
+#[derive(Default, Debug, Clone, Copy, Display, FromRepr, EnumIter)]
+pub enum SnippetFormat {
+    #[default]
+    #[strum(to_string = "cURL")]
+    Curl,
+    #[strum(to_string = "HTTPie")]
+    Httpie,
+    #[strum(to_string = "Rust reqwest")]
+    Reqwest,
+    #[strum(to_string = "Python requests")]
+    Python,
+    #[strum(to_string = "JavaScript fetch")]
+    Fetch,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Display, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum BodyType {
+    #[default]
+    None,
+    Json,
+    Xml,
+    Text,
+    Form,
+    Multipart,
+}
+
+impl BodyType {
+    // body types used to be free form, map the names other clients and older files use
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "" | "none" | "null" => BodyType::None,
+            "json" | "application/json" | "graphql" => BodyType::Json,
+            "xml" | "application/xml" | "text/xml" => BodyType::Xml,
+            "form"
+            | "urlencoded"
+            | "x-www-form-urlencoded"
+            | "application/x-www-form-urlencoded" => BodyType::Form,
+            "multipart" | "formdata" | "form-data" | "multipart/form-data" => BodyType::Multipart,
+            _ => BodyType::Text,
+        }
+    }
+}
+
+// how new request files of a workspace are stored
+#[derive(Default, Debug, Clone, Copy, PartialEq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum RequestFileFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
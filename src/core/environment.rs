@@ -0,0 +1,210 @@
+use base64::Engine;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::components::structs::RequestStructure;
+use crate::constants::ENVIRONMENT_FILE_NAME;
+
+fn read_environment_file(workspace_root: &Path) -> toml::Table {
+    fs::read_to_string(workspace_root.join(ENVIRONMENT_FILE_NAME))
+        .ok()
+        .and_then(|raw_data| raw_data.parse::<toml::Table>().ok())
+        .unwrap_or_default()
+}
+
+// the plain values of a table, tables and arrays are skipped
+fn variables_of(table: toml::Table) -> impl Iterator<Item = (String, String)> {
+    table.into_iter().filter_map(|(key, value)| match value {
+        toml::Value::String(value) => Some((key, value)),
+        toml::Value::Table(_) | toml::Value::Array(_) => None,
+        value => Some((key, value.to_string())),
+    })
+}
+
+// read the `key = "value"` pairs of the environment file into a lookup table, the values
+// of the named environment `active` win over the shared ones
+pub fn load_variables(workspace_root: &Path, active: Option<&str>) -> HashMap<String, String> {
+    let mut table = read_environment_file(workspace_root);
+    let environment = active.and_then(|name| match table.remove(name) {
+        Some(toml::Value::Table(environment)) => Some(environment),
+        _ => None,
+    });
+
+    let mut variables: HashMap<String, String> = variables_of(table).collect();
+    variables.extend(environment.into_iter().flat_map(variables_of));
+    variables
+}
+
+/// The named environments, the tables of the environment file.
+pub fn names(workspace_root: &Path) -> Vec<String> {
+    read_environment_file(workspace_root)
+        .into_iter()
+        .filter(|(_, value)| value.is_table())
+        .map(|(name, _)| name)
+        .collect()
+}
+
+// replace every `{{name}}` with its value, unknown variables are left untouched
+pub fn interpolate(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        match rest[start..].find("}}") {
+            Some(end) => {
+                let name = rest[start + 2..start + end].trim();
+                match variables.get(name) {
+                    Some(value) => result.push_str(value),
+                    None => result.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &rest[start + end + 2..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+// `Basic user:password` is sent encoded, base64 has no `:` so encoded values stay as they are
+fn encode_basic_credentials(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, credentials))
+            if scheme.eq_ignore_ascii_case("basic") && credentials.contains(':') =>
+        {
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(credentials.trim())
+            )
+        }
+        _ => value.to_string(),
+    }
+}
+
+pub fn resolve_request(
+    request: &RequestStructure,
+    variables: &HashMap<String, String>,
+) -> RequestStructure {
+    let mut resolved = request.clone();
+
+    // variables defined in the request file itself, like `@host` in .http files, win
+    let mut variables = variables.clone();
+    if let Some(serde_json::Value::Object(file_variables)) = request.metadata.get("variables") {
+        for (name, value) in file_variables {
+            if let Some(value) = value.as_str() {
+                let value = interpolate(value, &variables);
+                variables.insert(name.clone(), value);
+            }
+        }
+    }
+    let variables = &variables;
+
+    resolved.url = interpolate(&request.url, variables);
+    for header in &mut resolved.headers {
+        header.name = interpolate(&header.name, variables);
+        header.value = interpolate(&header.value, variables);
+        if header.name.eq_ignore_ascii_case("authorization") {
+            header.value = encode_basic_credentials(&header.value);
+        }
+    }
+    resolved.query_parameters = request
+        .query_parameters
+        .iter()
+        .map(|(name, value)| (interpolate(name, variables), interpolate(value, variables)))
+        .collect();
+    resolved.body = interpolate(&request.body, variables);
+    resolved.options.proxy = interpolate(&request.options.proxy, variables);
+
+    resolved
+}
+
+// add imported variables to the environment file, existing values are never overwritten
+// and the names of variables which already hold a different value are returned.
+// top level keys are shared by every environment, named environments are tables
+pub fn merge_variables(
+    workspace_root: &Path,
+    environment_name: Option<&str>,
+    variables: &[(String, String)],
+) -> anyhow::Result<Vec<String>> {
+    let file_path = workspace_root.join(ENVIRONMENT_FILE_NAME);
+    let mut root = match fs::read_to_string(&file_path) {
+        Ok(raw_data) => raw_data.parse::<toml::Table>()?,
+        Err(_) => toml::Table::new(),
+    };
+
+    let table = match environment_name {
+        Some(name) => match root
+            .entry(name.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        {
+            toml::Value::Table(table) => table,
+            _ => anyhow::bail!("{} is already used as a variable name", name),
+        },
+        None => &mut root,
+    };
+
+    let mut conflicts = vec![];
+    for (name, value) in variables {
+        match table.get(name) {
+            Some(toml::Value::String(existing)) if existing == value => {}
+            Some(_) => conflicts.push(name.clone()),
+            None => {
+                table.insert(name.clone(), toml::Value::String(value.clone()));
+            }
+        }
+    }
+
+    fs::write(&file_path, toml::to_string(&root)?)?;
+    Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::structs::Header;
+
+    #[test]
+    fn encodes_basic_credentials_once_they_are_filled_in() {
+        let request = RequestStructure {
+            headers: vec![Header::new("Authorization", "Basic {{user}}:{{pass}}")],
+            ..RequestStructure::default()
+        };
+        let variables = HashMap::from([
+            ("user".to_string(), "ada".to_string()),
+            ("pass".to_string(), "secret".to_string()),
+        ]);
+        let resolved = resolve_request(&request, &variables);
+        assert_eq!(resolved.headers[0].value, "Basic YWRhOnNlY3JldA==");
+        // encoded credentials are sent as they are
+        assert_eq!(
+            resolve_request(&resolved, &variables).headers[0].value,
+            "Basic YWRhOnNlY3JldA=="
+        );
+    }
+
+    #[test]
+    fn the_active_environment_wins_over_the_shared_variables() {
+        let root = std::env::temp_dir().join(format!("fetched-environment-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join(ENVIRONMENT_FILE_NAME),
+            "host = \"localhost\"\nport = 8080\n\n[staging]\nhost = \"staging.example.com\"\n",
+        )
+        .unwrap();
+
+        let shared = load_variables(&root, None);
+        assert_eq!(shared["host"], "localhost");
+        assert_eq!(shared["port"], "8080");
+        let staging = load_variables(&root, Some("staging"));
+        assert_eq!(staging["host"], "staging.example.com");
+        assert_eq!(staging["port"], "8080");
+        assert_eq!(names(&root), ["staging"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,34 @@
+use super::{full_url, has_body, shell_quote, timeout_seconds};
+use crate::components::structs::RequestStructure;
+
+pub fn render(request: &RequestStructure) -> String {
+    let mut lines = vec![format!(
+        "curl -X {} {}",
+        request.method,
+        shell_quote(&full_url(request))
+    )];
+
+    for header in request.enabled_headers() {
+        lines.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", header.name, header.value))
+        ));
+    }
+    if has_body(request) {
+        lines.push(format!("--data-raw {}", shell_quote(&request.body)));
+    }
+    if !request.options.validate_ssl {
+        lines.push("--insecure".to_string());
+    }
+    if request.options.follow_redirect {
+        lines.push("--location".to_string());
+    }
+    if !request.options.proxy.is_empty() {
+        lines.push(format!("--proxy {}", shell_quote(&request.options.proxy)));
+    }
+    if request.options.timeout_ms > 0 {
+        lines.push(format!("--max-time {}", timeout_seconds(request)));
+    }
+
+    lines.join(" \\\n  ")
+}
@@ -0,0 +1,42 @@
+use super::{full_url, has_body, quote};
+use crate::components::structs::RequestStructure;
+
+pub fn render(request: &RequestStructure) -> String {
+    let mut lines = vec![
+        format!(
+            "const response = await fetch({}, {{",
+            quote(&full_url(request))
+        ),
+        format!("  method: {},", quote(&request.method)),
+    ];
+
+    if request.enabled_headers().next().is_some() {
+        lines.push("  headers: {".to_string());
+        for header in request.enabled_headers() {
+            lines.push(format!(
+                "    {}: {},",
+                quote(&header.name),
+                quote(&header.value)
+            ));
+        }
+        lines.push("  },".to_string());
+    }
+    if has_body(request) {
+        lines.push(format!("  body: {},", quote(&request.body)));
+    }
+    if !request.options.follow_redirect {
+        lines.push("  redirect: \"manual\",".to_string());
+    }
+    if request.options.timeout_ms > 0 {
+        lines.push(format!(
+            "  signal: AbortSignal.timeout({}),",
+            request.options.timeout_ms
+        ));
+    }
+    // certificate validation and proxies are not configurable from fetch
+    lines.push("});".to_string());
+    lines.push("".to_string());
+    lines.push("console.log(await response.text());".to_string());
+
+    lines.join("\n")
+}
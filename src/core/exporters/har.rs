@@ -0,0 +1,153 @@
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::{full_url, has_body, mime_type, sorted};
+use crate::components::structs::RequestStructure;
+use crate::core::helpers;
+use crate::core::history::HistoryEntry;
+use crate::core::http::Response;
+
+/// Renders sent requests as a HAR 1.2 log, with what the server answered and how long it
+/// took. Requests which got no answer carry an empty response with status 0, the way
+/// browsers record them, and the reason in `_error`.
+pub fn render(entries: &[&HistoryEntry]) -> String {
+    let entries: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            let sent_at = UNIX_EPOCH + Duration::from_millis(entry.sent_at);
+            let mut rendered = json!({
+                "startedDateTime": helpers::format_timestamp(sent_at),
+                "time": entry.duration_ms,
+                "request": render_request(&entry.request),
+                "response": render_response(entry.response.as_ref()),
+                "cache": {},
+                // only the whole time is measured
+                "timings": { "send": 0, "wait": entry.duration_ms, "receive": 0 },
+            });
+            if let Some(error) = &entry.error {
+                rendered["_error"] = json!(error);
+            }
+            rendered
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "fetched", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries,
+        }
+    }))
+    .unwrap()
+}
+
+fn render_response(response: Option<&Response>) -> Value {
+    let Some(response) = response else {
+        return json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+        });
+    };
+    // binary bodies are written as base64, the way HAR allows
+    let mut content = match response.text() {
+        Some(text) => json!({ "text": text }),
+        None => json!({ "text": BASE64_STANDARD.encode(&response.body), "encoding": "base64" }),
+    };
+    content["size"] = json!(response.body.len());
+    content["mimeType"] = json!(response.header_value("content-type").unwrap_or_default());
+    json!({
+        "status": response.status,
+        "statusText": response.reason,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": response
+            .headers
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect::<Vec<_>>(),
+        "content": content,
+        "redirectURL": response.header_value("location").unwrap_or_default(),
+        "headersSize": -1,
+        "bodySize": response.body.len(),
+    })
+}
+
+fn render_request(request: &RequestStructure) -> Value {
+    let pairs = |map| -> Vec<Value> {
+        sorted(map)
+            .into_iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect()
+    };
+
+    let mut rendered = json!({
+        "method": request.method,
+        "url": full_url(request),
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": request
+            .enabled_headers()
+            .map(|header| json!({ "name": header.name, "value": header.value }))
+            .collect::<Vec<_>>(),
+        "queryString": pairs(&request.query_parameters),
+        "headersSize": -1,
+        "bodySize": if has_body(request) { request.body.len() as i64 } else { 0 },
+    });
+
+    if has_body(request) {
+        let mime_type = request
+            .header_value("content-type")
+            .unwrap_or(mime_type(&request.body_type));
+        rendered["postData"] = json!({ "mimeType": mime_type, "text": request.body });
+    }
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_answer_and_the_timing_of_an_entry() {
+        let entry = HistoryEntry {
+            sent_at: 0,
+            duration_ms: 42,
+            environment: None,
+            request: RequestStructure {
+                method: "GET".to_string(),
+                url: "http://localhost/logo".to_string(),
+                ..RequestStructure::default()
+            },
+            response: Some(Response {
+                status: 200,
+                reason: "OK".to_string(),
+                headers: vec![("Content-Type".to_string(), "image/png".to_string())],
+                body: vec![0x89, 0x50],
+                truncated: false,
+            }),
+            error: None,
+        };
+        let log: Value = serde_json::from_str(&render(&[&entry])).unwrap();
+        let rendered = &log["log"]["entries"][0];
+        assert_eq!(rendered["time"], 42);
+        assert_eq!(rendered["response"]["status"], 200);
+        assert_eq!(
+            rendered["response"]["headers"],
+            json!([{ "name": "Content-Type", "value": "image/png" }])
+        );
+        assert_eq!(
+            rendered["response"]["content"],
+            json!({ "size": 2, "mimeType": "image/png", "text": "iVA=", "encoding": "base64" })
+        );
+    }
+}
@@ -0,0 +1,93 @@
+pub mod curl;
+pub mod fetch;
+pub mod har;
+pub mod httpie;
+pub mod python;
+pub mod reqwest;
+
+use std::collections::HashMap;
+
+use crate::components::structs::RequestStructure;
+use crate::core::enums::{BodyType, SnippetFormat};
+use crate::core::helpers;
+
+pub fn render(format: SnippetFormat, request: &RequestStructure) -> String {
+    match format {
+        SnippetFormat::Curl => curl::render(request),
+        SnippetFormat::Httpie => httpie::render(request),
+        SnippetFormat::Reqwest => reqwest::render(request),
+        SnippetFormat::Python => python::render(request),
+        SnippetFormat::Fetch => fetch::render(request),
+    }
+}
+
+// match the name used on the command line, eg. `fetched export python <file>`
+pub fn find_format(name: &str) -> Option<SnippetFormat> {
+    match name.to_lowercase().as_str() {
+        "curl" => Some(SnippetFormat::Curl),
+        "httpie" | "http" => Some(SnippetFormat::Httpie),
+        "reqwest" | "rust" => Some(SnippetFormat::Reqwest),
+        "python" | "requests" => Some(SnippetFormat::Python),
+        "fetch" | "javascript" | "js" => Some(SnippetFormat::Fetch),
+        _ => None,
+    }
+}
+
+// query parameters are stored in a map, sort them to keep the snippets stable
+fn sorted(map: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    entries
+}
+
+/// The url with the query parameters appended.
+pub fn full_url(request: &RequestStructure) -> String {
+    let query: Vec<String> = sorted(&request.query_parameters)
+        .into_iter()
+        .map(|(name, value)| {
+            format!(
+                "{}={}",
+                helpers::url_encode(name),
+                helpers::url_encode(value)
+            )
+        })
+        .collect();
+
+    if query.is_empty() {
+        request.url.clone()
+    } else if request.url.contains('?') {
+        format!("{}&{}", request.url, query.join("&"))
+    } else {
+        format!("{}?{}", request.url, query.join("&"))
+    }
+}
+
+/// The content type of a body which has no `Content-Type` header.
+pub fn mime_type(body_type: &BodyType) -> &'static str {
+    match body_type {
+        BodyType::Json => "application/json",
+        BodyType::Xml => "application/xml",
+        BodyType::Form => "application/x-www-form-urlencoded",
+        BodyType::Multipart => "multipart/form-data",
+        _ => "text/plain",
+    }
+}
+
+fn has_body(request: &RequestStructure) -> bool {
+    request.body_type != BodyType::None && !request.body.is_empty()
+}
+
+// whole seconds are written without a fraction, like `5` or `0.5`
+fn timeout_seconds(request: &RequestStructure) -> String {
+    (request.options.timeout_ms as f64 / 1000.0).to_string()
+}
+
+// a double quoted string literal, valid in rust, python and javascript alike
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+// a single quoted posix shell word
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
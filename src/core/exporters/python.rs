@@ -0,0 +1,49 @@
+use super::{full_url, has_body, quote, timeout_seconds};
+use crate::components::structs::RequestStructure;
+
+pub fn render(request: &RequestStructure) -> String {
+    let mut lines = vec![
+        "import requests".to_string(),
+        "".to_string(),
+        "response = requests.request(".to_string(),
+        format!("    {},", quote(&request.method)),
+        format!("    {},", quote(&full_url(request))),
+    ];
+
+    if request.enabled_headers().next().is_some() {
+        lines.push("    headers={".to_string());
+        for header in request.enabled_headers() {
+            lines.push(format!(
+                "        {}: {},",
+                quote(&header.name),
+                quote(&header.value)
+            ));
+        }
+        lines.push("    },".to_string());
+    }
+    if has_body(request) {
+        lines.push(format!("    data={},", quote(&request.body)));
+    }
+    if !request.options.validate_ssl {
+        lines.push("    verify=False,".to_string());
+    }
+    // requests follows redirects by default
+    if !request.options.follow_redirect {
+        lines.push("    allow_redirects=False,".to_string());
+    }
+    if !request.options.proxy.is_empty() {
+        let proxy = quote(&request.options.proxy);
+        lines.push(format!(
+            "    proxies={{\"http\": {}, \"https\": {}}},",
+            proxy, proxy
+        ));
+    }
+    if request.options.timeout_ms > 0 {
+        lines.push(format!("    timeout={},", timeout_seconds(request)));
+    }
+    lines.push(")".to_string());
+    lines.push("".to_string());
+    lines.push("print(response.text)".to_string());
+
+    lines.join("\n")
+}
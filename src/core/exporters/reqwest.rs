@@ -0,0 +1,59 @@
+use super::{full_url, has_body, quote};
+use crate::components::structs::RequestStructure;
+
+const STANDARD_METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+pub fn render(request: &RequestStructure) -> String {
+    let mut lines = vec!["let client = reqwest::blocking::Client::builder()".to_string()];
+
+    if !request.options.validate_ssl {
+        lines.push("    .danger_accept_invalid_certs(true)".to_string());
+    }
+    if !request.options.follow_redirect {
+        lines.push("    .redirect(reqwest::redirect::Policy::none())".to_string());
+    }
+    if !request.options.proxy.is_empty() {
+        lines.push(format!(
+            "    .proxy(reqwest::Proxy::all({})?)",
+            quote(&request.options.proxy)
+        ));
+    }
+    if request.options.timeout_ms > 0 {
+        lines.push(format!(
+            "    .timeout(std::time::Duration::from_millis({}))",
+            request.options.timeout_ms
+        ));
+    }
+    lines.push("    .build()?;".to_string());
+    lines.push("".to_string());
+
+    let method = request.method.to_uppercase();
+    let method = if STANDARD_METHODS.contains(&method.as_str()) {
+        format!("reqwest::Method::{}", method)
+    } else {
+        format!("reqwest::Method::from_bytes(b{})?", quote(&method))
+    };
+    lines.push("let response = client".to_string());
+    lines.push(format!(
+        "    .request({}, {})",
+        method,
+        quote(&full_url(request))
+    ));
+    for header in request.enabled_headers() {
+        lines.push(format!(
+            "    .header({}, {})",
+            quote(&header.name),
+            quote(&header.value)
+        ));
+    }
+    if has_body(request) {
+        lines.push(format!("    .body({})", quote(&request.body)));
+    }
+    lines.push("    .send()?;".to_string());
+    lines.push("".to_string());
+    lines.push("println!(\"{}\", response.text()?);".to_string());
+
+    lines.join("\n")
+}
@@ -0,0 +1,267 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::core::enums::BodyType;
+
+// tried in order when neither the environment nor the config names an editor
+const FALLBACK_EDITORS: [&str; 4] = ["nvim", "vim", "vi", "nano"];
+
+/// What of a request is handed to the external editor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditTarget {
+    // the whole request file
+    File,
+    Body,
+    // one `Key: Value` line per header, disabled ones commented out with `#`
+    Headers,
+}
+
+fn is_on_path(program: &str) -> bool {
+    env::var_os("PATH").is_some_and(|paths| {
+        env::split_paths(&paths).any(|directory| directory.join(program).is_file())
+    })
+}
+
+/// The program and arguments of the editor to use: `$VISUAL`, `$EDITOR`, the `editor` of the
+/// config and then the first of a few common editors which is installed.
+pub fn editor_command(configured: Option<&str>) -> Option<Vec<String>> {
+    let chosen = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .chain(configured.map(str::to_string))
+        .find(|command| !command.trim().is_empty())
+        .or_else(|| {
+            FALLBACK_EDITORS
+                .iter()
+                .find(|editor| is_on_path(editor))
+                .map(|editor| editor.to_string())
+        })?;
+    // like `code --wait`
+    Some(chosen.split_whitespace().map(str::to_string).collect())
+}
+
+// a new directory in the temporary one which only the user can enter, a name somebody
+// else took first is passed over
+fn private_dir() -> io::Result<PathBuf> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    for attempt in 0..100 {
+        let dir = env::temp_dir().join(format!(
+            "fetched-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            attempt
+        ));
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "no temporary directory could be created",
+    ))
+}
+
+/// A file to edit the part in, in a private directory which goes away along with it. The
+/// extension lets the editor pick the right highlighting.
+pub struct TempFile {
+    dir: PathBuf,
+    pub path: PathBuf,
+}
+
+impl TempFile {
+    pub fn create(target: EditTarget, body_type: &BodyType, text: &str) -> io::Result<Self> {
+        let name = match (target, body_type) {
+            (EditTarget::Headers, _) => "headers.txt",
+            (_, BodyType::Json) => "body.json",
+            (_, BodyType::Xml) => "body.xml",
+            _ => "body.txt",
+        };
+        let dir = private_dir()?;
+        let temp_file = TempFile {
+            path: dir.join(name),
+            dir,
+        };
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_file.path)?
+            .write_all(text.as_bytes())?;
+        Ok(temp_file)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// The text of the part to edit.
+pub fn part_text(target: EditTarget, request: &RequestStructure) -> String {
+    match target {
+        EditTarget::File => String::new(),
+        EditTarget::Body => request.body.clone(),
+        EditTarget::Headers => request
+            .headers
+            .iter()
+            .map(|header| match header.enabled {
+                true => format!("{}: {}\n", header.name, header.value),
+                false => format!("# {}: {}\n", header.name, header.value),
+            })
+            .collect(),
+    }
+}
+
+// empty lines and comments are left out, unless the comment is a disabled header. the
+// descriptions are not part of the text, headers keep the one of the header of their name
+fn parse_headers(text: &str, previous: &[Header]) -> anyhow::Result<Vec<Header>> {
+    let mut headers = vec![];
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let (line, enabled) = match line.strip_prefix('#') {
+            Some(line) => (line.trim_start(), false),
+            None => (line, true),
+        };
+        let header = line
+            .split_once(':')
+            .filter(|(name, _)| Header::is_valid_name(name.trim()));
+        match header {
+            Some((name, value)) => {
+                let mut header = Header::new(name.trim(), value.trim());
+                header.enabled = enabled;
+                if let Some(previous) = previous
+                    .iter()
+                    .find(|previous| previous.name == header.name)
+                {
+                    header.description = previous.description.clone();
+                }
+                headers.push(header);
+            }
+            // a comment
+            None if !enabled || line.is_empty() => {}
+            None => anyhow::bail!(
+                "line {}: {:?} is not a `Key: Value` header",
+                number + 1,
+                line
+            ),
+        }
+    }
+    Ok(headers)
+}
+
+/// Puts the edited part back into the request.
+pub fn merge(target: EditTarget, request: &mut RequestStructure, text: &str) -> anyhow::Result<()> {
+    match target {
+        EditTarget::File => {}
+        EditTarget::Body => {
+            // editors end the file with a newline the body did not have
+            let text = match request.body.ends_with('\n') {
+                true => text,
+                false => text.strip_suffix('\n').unwrap_or(text),
+            };
+            request.body = text.to_string();
+        }
+        EditTarget::Headers => request.headers = parse_headers(text, &request.headers)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with(headers: Vec<Header>, body: &str) -> RequestStructure {
+        RequestStructure {
+            headers,
+            body: body.to_string(),
+            ..RequestStructure::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_headers_with_disabled_ones() {
+        let mut disabled = Header::new("X-Debug", "on");
+        disabled.enabled = false;
+        let mut described = Header::new("Accept", "*/*");
+        described.description = "what comes back".to_string();
+        let mut request = request_with(vec![described, disabled], "");
+
+        let text = part_text(EditTarget::Headers, &request);
+        assert_eq!(text, "Accept: */*\n# X-Debug: on\n");
+        let headers = request.headers.clone();
+        merge(EditTarget::Headers, &mut request, &text).unwrap();
+        assert_eq!(request.headers, headers);
+    }
+
+    #[test]
+    fn leaves_comments_and_empty_lines_out() {
+        let mut request = request_with(vec![], "");
+        merge(
+            EditTarget::Headers,
+            &mut request,
+            "# the headers of the request\n\nAccept: */*\n#Cache-Control: no-cache\n",
+        )
+        .unwrap();
+        let mut disabled = Header::new("Cache-Control", "no-cache");
+        disabled.enabled = false;
+        assert_eq!(request.headers, [Header::new("Accept", "*/*"), disabled]);
+    }
+
+    #[test]
+    fn refuses_lines_which_are_not_headers() {
+        let mut request = request_with(vec![Header::new("Accept", "*/*")], "");
+        let err = merge(
+            EditTarget::Headers,
+            &mut request,
+            "Accept: */*\nnot a header\n",
+        );
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "line 2: \"not a header\" is not a `Key: Value` header"
+        );
+        // the request is left as it was
+        assert_eq!(request.headers, [Header::new("Accept", "*/*")]);
+    }
+
+    #[test]
+    fn drops_only_the_newline_the_editor_added() {
+        let mut request = request_with(vec![], "{}");
+        merge(EditTarget::Body, &mut request, "{\"a\": 1}\n").unwrap();
+        assert_eq!(request.body, "{\"a\": 1}");
+
+        let mut request = request_with(vec![], "line\n");
+        merge(EditTarget::Body, &mut request, "line\nmore\n").unwrap();
+        assert_eq!(request.body, "line\nmore\n");
+    }
+
+    #[test]
+    fn writes_the_part_into_a_private_file() {
+        let temp_file = TempFile::create(EditTarget::Body, &BodyType::Json, "{}").unwrap();
+        let path = temp_file.path.clone();
+        assert_eq!(path.extension().unwrap(), "json");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(path.parent().unwrap())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        drop(temp_file);
+        assert!(!path.parent().unwrap().exists());
+    }
+}
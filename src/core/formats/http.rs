@@ -0,0 +1,454 @@
+use anyhow::Result;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+use crate::core::formats::RequestFormat;
+use crate::core::importers::{default_options, split_query};
+
+const HTTP_METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "CONNECT", "TRACE",
+];
+
+// request settings written as comments above the request line
+const NO_REDIRECT: &str = "@no-redirect";
+const NO_COOKIE_JAR: &str = "@no-cookie-jar";
+// the body type, when the content type does not tell it
+const BODY_TYPE: &str = "@body-type";
+// marks a header comment as a disabled header, like `# @disabled Accept: text/plain`
+const DISABLED: &str = "@disabled";
+
+/// The `.http` files of the VS Code and JetBrains REST clients, several requests separated
+/// by `###` lines next to `@name = value` variable definitions.
+pub struct Http;
+
+// a request inside the file, `start` is the first line after its `###` separator,
+// `request_line` the `METHOD url` line and `end` is one past its last non blank line
+struct Block {
+    separator: Option<usize>,
+    start: usize,
+    request_line: usize,
+    end: usize,
+}
+
+// the parts of a request which are not part of the request structure
+struct Parsed {
+    request: RequestStructure,
+    http_version: String,
+    comments: Vec<String>,
+}
+
+fn is_separator(line: &str) -> bool {
+    line.trim_start().starts_with("###")
+}
+
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#') || line.starts_with("//")
+}
+
+// a header commented out with `# @disabled`, other comments are kept as they are
+fn parse_disabled_header(line: &str) -> Option<Header> {
+    let comment = line.strip_prefix('#').or_else(|| line.strip_prefix("//"))?;
+    let (name, value) = comment
+        .trim_start()
+        .strip_prefix(DISABLED)?
+        .trim_start()
+        .split_once(':')?;
+    let name = name.trim();
+    if !Header::is_valid_name(name) {
+        return None;
+    }
+    let mut header = Header::new(name, value.trim());
+    header.enabled = false;
+    Some(header)
+}
+
+fn is_variable(line: &str) -> bool {
+    line.trim_start().starts_with('@')
+}
+
+fn is_setting(line: &str) -> bool {
+    is_comment(line)
+        && [NO_REDIRECT, NO_COOKIE_JAR, BODY_TYPE]
+            .iter()
+            .any(|setting| line.contains(setting))
+}
+
+// what the content type says about the body
+fn infer_body_type(body: &str, headers: &[Header]) -> BodyType {
+    let content_type = headers
+        .iter()
+        .find(|header| header.enabled && header.name.eq_ignore_ascii_case("content-type"))
+        .map(|header| header.value.to_lowercase())
+        .unwrap_or_default();
+    if body.is_empty() {
+        BodyType::None
+    } else if content_type.contains("json") {
+        BodyType::Json
+    } else if content_type.contains("xml") {
+        BodyType::Xml
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        BodyType::Form
+    } else if content_type.starts_with("multipart/form-data") {
+        BodyType::Multipart
+    } else {
+        BodyType::Text
+    }
+}
+
+fn blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut separator = None;
+    let mut start = 0;
+
+    for index in 0..=lines.len() {
+        if index < lines.len() && !is_separator(lines[index]) {
+            continue;
+        }
+
+        let section = &lines[start..index];
+        let request_line = section
+            .iter()
+            .position(|line| !line.trim().is_empty() && !is_comment(line) && !is_variable(line));
+        if let Some(request_line) = request_line {
+            let end = section
+                .iter()
+                .rposition(|line| !line.trim().is_empty())
+                .unwrap_or(request_line);
+            blocks.push(Block {
+                separator,
+                start,
+                request_line: start + request_line,
+                end: start + end + 1,
+            });
+        }
+
+        separator = Some(index);
+        start = index + 1;
+    }
+
+    blocks
+}
+
+// `@name = value` definitions anywhere outside of requests apply to the whole file
+fn variables(lines: &[&str], blocks: &[Block]) -> Map<String, Value> {
+    let mut variables = Map::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let is_request = blocks
+            .iter()
+            .any(|block| (block.request_line..block.end).contains(&index));
+        if is_request || !is_variable(line) {
+            continue;
+        }
+        if let Some((name, value)) = line.trim_start()[1..].split_once('=') {
+            variables.insert(
+                name.trim().to_string(),
+                Value::String(value.trim().to_string()),
+            );
+        }
+    }
+
+    variables
+}
+
+fn parse_block(lines: &[&str], block: &Block) -> Parsed {
+    let mut tokens: Vec<&str> = lines[block.request_line].split_whitespace().collect();
+    let http_version = match tokens.last() {
+        Some(token) if token.starts_with("HTTP/") => tokens.pop().unwrap().to_string(),
+        _ => "".to_string(),
+    };
+    let method = match tokens.first() {
+        Some(token) if HTTP_METHODS.contains(&token.to_uppercase().as_str()) => {
+            tokens.remove(0).to_uppercase()
+        }
+        _ => "GET".to_string(),
+    };
+    let mut url = tokens.join(" ");
+
+    let mut headers = vec![];
+    let mut comments = vec![];
+    let mut body_start = block.end;
+    for (index, line) in lines
+        .iter()
+        .enumerate()
+        .take(block.end)
+        .skip(block.request_line + 1)
+    {
+        let line = line.trim();
+        if line.is_empty() {
+            body_start = index + 1;
+            break;
+        } else if line.starts_with('?') || line.starts_with('&') {
+            // long query strings can continue on the following lines
+            url.push_str(line);
+        } else if let Some(header) = parse_disabled_header(line) {
+            headers.push(header);
+        } else if is_comment(line) {
+            comments.push(line.to_string());
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push(Header::new(name.trim(), value.trim()));
+        }
+    }
+    let body = lines[body_start.min(block.end)..block.end].join("\n");
+
+    let mut body_type = infer_body_type(&body, &headers);
+
+    let mut options = default_options();
+    for line in &lines[block.start..block.request_line] {
+        if !is_comment(line) {
+            continue;
+        }
+        if line.contains(NO_REDIRECT) {
+            options.follow_redirect = false;
+        }
+        if line.contains(NO_COOKIE_JAR) {
+            options.attach_cookies = false;
+        }
+        if let Some((_, name)) = line.split_once(BODY_TYPE) {
+            body_type = BodyType::from_name(name);
+        }
+    }
+
+    let (url, query_parameters) = split_query(&url);
+    Parsed {
+        request: RequestStructure {
+            version: REQUEST_FILE_VERSION,
+            method,
+            url,
+            headers,
+            query_parameters,
+            body_type,
+            body,
+            options,
+            metadata: HashMap::new(),
+        },
+        http_version,
+        comments,
+    }
+}
+
+// the request line, headers and body of a request, plus the settings written above it
+fn write_request(
+    request: &RequestStructure,
+    http_version: &str,
+    comments: &[String],
+) -> Vec<String> {
+    let mut lines = vec![];
+    if !request.options.follow_redirect {
+        lines.push(format!("# {}", NO_REDIRECT));
+    }
+    if !request.options.attach_cookies {
+        lines.push(format!("# {}", NO_COOKIE_JAR));
+    }
+    if infer_body_type(&request.body, &request.headers) != request.body_type {
+        lines.push(format!("# {} {}", BODY_TYPE, request.body_type));
+    }
+
+    let mut url = request.url.clone();
+    let mut query: Vec<_> = request.query_parameters.iter().collect();
+    query.sort();
+    if !query.is_empty() {
+        let pairs: Vec<String> = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        url = format!("{}?{}", url, pairs.join("&"));
+    }
+    let request_line = format!("{} {} {}", request.method, url, http_version);
+    lines.push(request_line.trim_end().to_string());
+    lines.extend(comments.iter().cloned());
+
+    // disabled headers are commented out, the file has no other place for them
+    for header in &request.headers {
+        match header.enabled {
+            true => lines.push(format!("{}: {}", header.name, header.value)),
+            false => lines.push(format!("# {} {}: {}", DISABLED, header.name, header.value)),
+        }
+    }
+
+    if !request.body.is_empty() {
+        lines.push("".to_string());
+        lines.extend(request.body.lines().map(str::to_string));
+    }
+
+    lines
+}
+
+fn join(lines: Vec<String>) -> String {
+    let mut raw_data = lines.join("\n").trim_end().to_string();
+    if !raw_data.is_empty() {
+        raw_data.push('\n');
+    }
+    raw_data
+}
+
+impl RequestFormat for Http {
+    // file variables are kept in the `variables` metadata of every request of the file
+    fn parse(&self, raw_data: &str) -> Result<Vec<RequestStructure>> {
+        let lines: Vec<&str> = raw_data.lines().collect();
+        let blocks = blocks(&lines);
+        let variables = variables(&lines, &blocks);
+
+        Ok(blocks
+            .iter()
+            .map(|block| {
+                let mut request = parse_block(&lines, block).request;
+                if !variables.is_empty() {
+                    request
+                        .metadata
+                        .insert("variables".to_string(), Value::Object(variables.clone()));
+                }
+                request
+            })
+            .collect())
+    }
+
+    fn update(&self, raw_data: &str, index: usize, request: &RequestStructure) -> Result<String> {
+        let lines: Vec<&str> = raw_data.lines().collect();
+        let blocks = blocks(&lines);
+        let to_strings = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let result = match blocks.get(index) {
+            Some(block) => {
+                let parsed = parse_block(&lines, block);
+                let mut result = to_strings(&lines[..block.start]);
+                // comments and variables above the request stay, only the settings are rewritten
+                result.extend(
+                    lines[block.start..block.request_line]
+                        .iter()
+                        .filter(|line| !is_setting(line))
+                        .map(|line| line.to_string()),
+                );
+                result.extend(write_request(
+                    request,
+                    &parsed.http_version,
+                    &parsed.comments,
+                ));
+                result.extend(to_strings(&lines[block.end..]));
+                result
+            }
+            None => {
+                let mut result = to_strings(&lines);
+                if !raw_data.trim().is_empty() {
+                    result.push("".to_string());
+                    result.push("###".to_string());
+                }
+                result.extend(write_request(request, "", &[]));
+                result
+            }
+        };
+
+        Ok(join(result))
+    }
+
+    fn is_multi_request(&self) -> bool {
+        true
+    }
+
+    fn remove(&self, raw_data: &str, index: usize) -> Result<String> {
+        let lines: Vec<&str> = raw_data.lines().collect();
+        let blocks = blocks(&lines);
+        let block = blocks
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("No request {} in the file", index + 1))?;
+
+        let mut result: Vec<String> = lines[..block.separator.unwrap_or(block.start)]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        // variables are shared with the other requests of the file
+        result.extend(
+            lines[block.start..block.request_line]
+                .iter()
+                .filter(|line| is_variable(line))
+                .map(|line| line.to_string()),
+        );
+        result.extend(lines[block.end..].iter().map(|line| line.to_string()));
+
+        Ok(join(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // writes the request into an empty file and reads it back
+    fn round_trip(request: &RequestStructure) -> RequestStructure {
+        let raw_data = Http.update("", 0, request).unwrap();
+        Http.parse(&raw_data).unwrap().remove(0)
+    }
+
+    #[test]
+    fn parses_a_request_with_headers_and_a_body() {
+        let raw_data = "\
+@host = localhost
+###
+# @no-redirect
+POST http://{{host}}/users?page=2 HTTP/1.1
+Content-Type: application/json
+# @disabled Accept: text/plain
+
+{\"name\": \"ada\"}
+";
+        let request = Http.parse(raw_data).unwrap().remove(0);
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "http://{{host}}/users");
+        assert_eq!(request.query_parameters["page"], "2");
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(request.body, "{\"name\": \"ada\"}");
+        assert!(!request.options.follow_redirect);
+        assert_eq!(request.headers.len(), 2);
+        assert!(!request.headers[1].enabled);
+        assert_eq!(request.headers[1].value, "text/plain");
+        assert_eq!(request.metadata["variables"]["host"], "localhost");
+    }
+
+    #[test]
+    fn keeps_comments_with_a_colon_as_comments() {
+        let raw_data = "GET http://localhost\n# see: the docs\nAccept: */*\n";
+        let request = Http.parse(raw_data).unwrap().remove(0);
+        assert_eq!(request.headers, [Header::new("Accept", "*/*")]);
+
+        let updated = Http.update(raw_data, 0, &request).unwrap();
+        assert_eq!(updated, raw_data);
+    }
+
+    #[test]
+    fn round_trips_disabled_headers() {
+        let mut disabled = Header::new("X-Debug", "on");
+        disabled.enabled = false;
+        let request = RequestStructure {
+            method: "GET".to_string(),
+            url: "http://localhost".to_string(),
+            headers: vec![Header::new("Accept", "*/*"), disabled],
+            options: default_options(),
+            ..RequestStructure::default()
+        };
+        assert_eq!(round_trip(&request).headers, request.headers);
+    }
+
+    #[test]
+    fn round_trips_the_body_type_without_a_content_type() {
+        let request = RequestStructure {
+            method: "POST".to_string(),
+            url: "http://localhost".to_string(),
+            body_type: BodyType::Json,
+            body: "{}".to_string(),
+            options: default_options(),
+            ..RequestStructure::default()
+        };
+        let parsed = round_trip(&request);
+        assert_eq!(parsed.body_type, BodyType::Json);
+        assert_eq!(parsed.body, "{}");
+    }
+}
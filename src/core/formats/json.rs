@@ -0,0 +1,17 @@
+use anyhow::Result;
+
+use crate::components::structs::RequestStructure;
+use crate::core::formats::{migration, RequestFormat};
+
+// the native format, one request per file
+pub struct Json;
+
+impl RequestFormat for Json {
+    fn parse(&self, raw_data: &str) -> Result<Vec<RequestStructure>> {
+        Ok(vec![migration::load(serde_json::from_str(raw_data)?)?])
+    }
+
+    fn update(&self, _raw_data: &str, _index: usize, request: &RequestStructure) -> Result<String> {
+        Ok(serde_json::to_string_pretty(request)?)
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+
+use crate::components::structs::RequestStructure;
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+
+/// Reads a request document of any known version, upgrading older documents to the
+/// current schema. Only the returned request is upgraded, the file stays as it is until
+/// the request is saved.
+pub fn load(mut document: Value) -> Result<RequestStructure> {
+    // indexing assigns into objects only, anything else would panic
+    let Value::Object(_) = &document else {
+        bail!("expected a mapping at top level");
+    };
+    let version = document.get("version").and_then(Value::as_u64).unwrap_or(1);
+    if version > REQUEST_FILE_VERSION as u64 {
+        bail!(
+            "The request was written by a newer version of fetched (schema version {})",
+            version
+        );
+    }
+
+    if version < 2 {
+        migrate_v1(&mut document);
+    }
+    if version < 3 {
+        migrate_v2(&mut document);
+    }
+    document["version"] = json!(REQUEST_FILE_VERSION);
+
+    Ok(serde_json::from_value(document)?)
+}
+
+// version 1 had a free form body type, an optional body and the timeout in seconds
+fn migrate_v1(document: &mut Value) {
+    let body_type = BodyType::from_name(document["body_type"].as_str().unwrap_or_default());
+    document["body_type"] = json!(body_type);
+
+    if !document["body"].is_string() {
+        document["body"] = json!("");
+    }
+    for field in ["headers", "query_parameters"] {
+        if !document[field].is_object() {
+            document[field] = json!({});
+        }
+    }
+
+    let options = &mut document["options"];
+    if !options.is_object() {
+        *options = json!({
+            "validate_ssl": true,
+            "follow_redirect": true,
+            "attach_cookies": true,
+            "proxy": "",
+        });
+    }
+    let timeout = options["timeout"].as_f64().unwrap_or_default();
+    options["timeout_ms"] = json!((timeout * 1000.0).round() as u64);
+    if let Some(options) = options.as_object_mut() {
+        options.remove("timeout");
+    }
+}
+
+// version 2 kept the headers in a map, which could not hold a name twice, an order or
+// disabled headers
+fn migrate_v2(document: &mut Value) {
+    let headers: Vec<Value> = document["headers"]
+        .as_object()
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect()
+        })
+        .unwrap_or_default();
+    document["headers"] = json!(headers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_version_1_to_the_current_schema() {
+        let request = load(json!({
+            "method": "POST",
+            "url": "http://localhost/users",
+            "headers": { "b-header": "2", "a-header": "1" },
+            "body_type": "application/json",
+            "body": null,
+            "options": {
+                "validate_ssl": true,
+                "follow_redirect": false,
+                "attach_cookies": true,
+                "proxy": "",
+                "timeout": 1.5,
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(request.version, REQUEST_FILE_VERSION);
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(request.body, "");
+        assert_eq!(request.options.timeout_ms, 1500);
+        assert!(!request.options.follow_redirect);
+        let headers: Vec<_> = request
+            .headers
+            .iter()
+            .map(|header| (header.name.as_str(), header.value.as_str(), header.enabled))
+            .collect();
+        assert_eq!(headers, [("a-header", "1", true), ("b-header", "2", true)]);
+    }
+
+    #[test]
+    fn rejects_documents_which_are_not_a_mapping() {
+        for document in [
+            json!([]),
+            json!("GET http://localhost"),
+            json!(3),
+            Value::Null,
+        ] {
+            let err = load(document).unwrap_err();
+            assert_eq!(err.to_string(), "expected a mapping at top level");
+        }
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        assert!(load(json!({ "version": REQUEST_FILE_VERSION + 1 })).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::components::structs::RequestStructure;
+
+pub mod http;
+pub mod json;
+pub mod migration;
+pub mod toml;
+pub mod yaml;
+
+/// A way of storing requests on disk, picked from the extension of the request file.
+pub trait RequestFormat {
+    /// Every request stored in `raw_data`, in file order.
+    fn parse(&self, raw_data: &str) -> Result<Vec<RequestStructure>>;
+
+    /// `raw_data` with the request at `index` replaced by `request`, everything else the
+    /// file holds is kept as it is. An index past the last request appends the request.
+    fn update(&self, raw_data: &str, index: usize, request: &RequestStructure) -> Result<String>;
+
+    /// Whether a single file can hold more than one request.
+    fn is_multi_request(&self) -> bool {
+        false
+    }
+
+    /// `raw_data` without the request at `index`.
+    fn remove(&self, _raw_data: &str, _index: usize) -> Result<String> {
+        bail!("A request file of this format holds a single request")
+    }
+}
+
+// files without a known extension are the native json requests
+pub fn for_path(path: &Path) -> &'static dyn RequestFormat {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("http") | Some("rest") => &http::Http,
+        Some("yaml") | Some("yml") => &yaml::Yaml,
+        Some("toml") => &toml::Toml,
+        _ => &json::Json,
+    }
+}
+
+pub fn has_request_extension(name: &str) -> bool {
+    matches!(
+        Path::new(name)
+            .extension()
+            .and_then(|extension| extension.to_str()),
+        Some("json" | "http" | "rest" | "yaml" | "yml" | "toml")
+    )
+}
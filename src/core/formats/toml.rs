@@ -0,0 +1,18 @@
+use anyhow::Result;
+
+use crate::components::structs::RequestStructure;
+use crate::core::formats::{migration, RequestFormat};
+
+pub struct Toml;
+
+impl RequestFormat for Toml {
+    fn parse(&self, raw_data: &str) -> Result<Vec<RequestStructure>> {
+        let document: toml::Value = toml::from_str(raw_data)?;
+        Ok(vec![migration::load(serde_json::to_value(document)?)?])
+    }
+
+    // toml has no null, metadata holding one can not be written
+    fn update(&self, _raw_data: &str, _index: usize, request: &RequestStructure) -> Result<String> {
+        Ok(toml::to_string_pretty(request)?)
+    }
+}
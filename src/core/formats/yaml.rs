@@ -0,0 +1,16 @@
+use anyhow::Result;
+
+use crate::components::structs::RequestStructure;
+use crate::core::formats::{migration, RequestFormat};
+
+pub struct Yaml;
+
+impl RequestFormat for Yaml {
+    fn parse(&self, raw_data: &str) -> Result<Vec<RequestStructure>> {
+        Ok(vec![migration::load(serde_yaml::from_str(raw_data)?)?])
+    }
+
+    fn update(&self, _raw_data: &str, _index: usize, request: &RequestStructure) -> Result<String> {
+        Ok(serde_yaml::to_string(request)?)
+    }
+}
@@ -1,21 +1,175 @@
-use crate::components::structs::App;
-use crate::core::enums::InputStrategy;
+use crate::components::structs::{App, Header, RequestOptions, RequestStructure};
+use crate::core::enums::{BodyType, InputStrategy};
+use crate::core::history::{self, HistoryEntry};
+use crate::core::request_parser;
+use crate::core::workspace::Workspace;
+use crate::core::{commands, environment, exporters, formats, helpers, importers};
+use crossterm::event::DisableBracketedPaste;
 use crossterm::terminal;
+use crossterm::ExecutableCommand;
 use dirs::{config_dir, home_dir};
+use std::collections::{HashMap, HashSet};
 use std::fs::{self};
-use std::io::prelude::*;
+use std::path::Path;
 
 pub fn event_handler(input_strategy: InputStrategy, input: String, app: &mut App) {
+    if input_strategy != InputStrategy::Command {
+        return;
+    }
+    let input = commands::expand_alias(input.trim(), &app.config.aliases);
+    if input.is_empty() {
+        return;
+    }
     let cmds: Vec<_> = input.split(' ').collect();
-    if input_strategy == InputStrategy::Command {
-        match *cmds.get(0).unwrap() {
-            "q" => exit_app(),
-            _ => app.show_popup("Command not found".to_string()), //app.show_pop_up("Command not found".to_string()),
+    // what was typed after the command, for arguments which may hold spaces
+    let rest = input.split_once(' ').map_or("", |(_, rest)| rest.trim());
+    let Some(command) = commands::find(cmds[0]) else {
+        app.show_popup(format!("Command not found: {}", cmds[0]));
+        return;
+    };
+    let usage = format!("Usage: {}", command.usage);
+
+    match command.name {
+        "quit" => app.quit(),
+        "send" => app.send_request(),
+        "history" => app.show_history_popup(),
+        "env" => set_environment(app, rest),
+        "new" => match rest.split_once(' ') {
+            Some((collection, name)) => new_request(app, collection, name.trim()),
+            None => app.show_error_popup(usage),
+        },
+        "set" => match rest.split_once(' ') {
+            Some((name, value)) => set_request_option(app, name, value.trim()),
+            // an option without a value is cleared, only the proxy can be
+            None if !rest.is_empty() => set_request_option(app, rest, ""),
+            None => app.show_error_popup(usage),
+        },
+        "move" => move_selected_entry(app, rest),
+        "trash" => app.show_trash_popup(),
+        "help" => app.show_help_popup(),
+        "theme" => match rest {
+            "" => app.set_theme(None),
+            name => app.set_theme(Some(name.to_string())),
+        },
+        "workspace" => match rest {
+            "" => app.show_workspace_popup(),
+            path => app.switch_workspace(&helpers::expand_path(path)),
+        },
+        "export" if cmds.get(1) == Some(&"har") => {
+            let entries = history::list(&app.workspace.root);
+            export_har(
+                app,
+                cmds[2..].join(" ").trim(),
+                &entries.iter().collect::<Vec<_>>(),
+            )
+        }
+        "export" => {
+            if let Some(format) = cmds.get(1).and_then(|name| exporters::find_format(name)) {
+                app.selected_export_format = format as usize;
+            }
+            app.show_export_popup()
+        }
+        "import" => match cmds.get(1) {
+            Some(&"curl") => import_curl(app, rest.trim_start_matches("curl")),
+            Some(&"har") => import_har(app, cmds.get(2).unwrap_or(&""), cmds.get(3).copied()),
+            Some(&client @ ("postman" | "insomnia" | "bruno" | "openapi" | "swagger")) => {
+                import_collection(app, client, cmds[2..].join(" ").trim())
+            }
+            _ => app.show_error_popup(usage),
+        },
+        _ => app.show_error_popup(usage),
+    }
+}
+
+// `:env name` picks the variables of a named environment, `:env -` goes back to the shared
+// ones and `:env` alone lists the environments
+fn set_environment(app: &mut App, name: &str) {
+    let names = environment::names(&app.workspace.root);
+    match name {
+        "" => {
+            let active = app
+                .active_environment
+                .clone()
+                .unwrap_or_else(|| "none".to_string());
+            let available = match names.is_empty() {
+                true => "none".to_string(),
+                false => names.join(", "),
+            };
+            app.show_popup(format!("Environment: {}\nAvailable: {}", active, available));
+        }
+        "-" => {
+            app.active_environment = None;
+            app.show_popup("Using the shared variables only".to_string());
         }
+        name if names.iter().any(|known| known == name) => {
+            app.active_environment = Some(name.to_string());
+            app.show_popup(format!("Using the {} environment", name));
+        }
+        name => app.show_error_popup(format!(
+            "Unknown environment {:?}, the environments are: {}",
+            name,
+            names.join(", ")
+        )),
+    }
+}
+
+// `:new <collection> <name>` creates the collection when needed, a trailing `/` makes a folder
+fn new_request(app: &mut App, collection: &str, name: &str) {
+    let checked = validate_name(collection).and_then(|_| {
+        name.trim_end_matches('/')
+            .split('/')
+            .try_for_each(validate_name)
+    });
+    let created = checked.and_then(|_| match name.strip_suffix('/') {
+        Some(folder) => {
+            create_folder(&app.workspace, collection, folder).map(|_| folder.to_string())
+        }
+        None => create_request(&app.workspace, &collection.to_string(), name.to_string())
+            .map(|_| {
+                request_parser::with_request_extension(name, app.workspace.config.request_format)
+            })
+            .map_err(|err| anyhow::anyhow!(err.to_string())),
+    });
+
+    match created {
+        Ok(path) => app.follow_entry(&format!("{}/{}", collection, path)),
+        Err(err) => app.show_error_popup(format!("Failed to create {}: {}", name, err)),
     }
 }
 
+// options wait with the other edits of the request until it is saved
+fn set_request_option(app: &mut App, name: &str, value: &str) {
+    if app.request_path.is_none() || app.request_error.is_some() {
+        app.show_error_popup("Open a request to change its options".to_string());
+        return;
+    }
+
+    let mut request = app.request_data.clone();
+    match commands::set_request_option(&mut request.options, name, value) {
+        Ok(_) => app.change_request(request),
+        Err(err) => app.show_error_popup(format!("Failed to set {}: {}", name, err)),
+    }
+}
+
+// render a request file as a client code snippet, used by `fetched export <format> <file>`
+pub fn export_request_file(
+    workspace: &Workspace,
+    format_name: &str,
+    file_path: &str,
+) -> anyhow::Result<String> {
+    let format = exporters::find_format(format_name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown export format: {}", format_name))?;
+    let request = request_parser::read_request(std::path::Path::new(file_path))?;
+    let request = environment::resolve_request(
+        &request,
+        &environment::load_variables(&workspace.root, None),
+    );
+
+    Ok(exporters::render(format, &request))
+}
+
 pub fn exit_app() {
+    _ = std::io::stdout().execute(DisableBracketedPaste);
     _ = terminal::disable_raw_mode();
     crate::core::helpers::clear_logger();
     ratatui::restore();
@@ -44,143 +198,502 @@ pub fn create_config_folder() {
     }
 }
 
-// collection contain lists of request data
-pub fn list_collections() -> Vec<String> {
-    fs::read_dir(std::env::current_dir().unwrap())
-        .unwrap()
+// collection contain lists of request data, they are the folders of the collections area
+// of the workspace, which does not exist until the first collection is created
+pub fn list_collections(workspace: &Workspace) -> Vec<String> {
+    let mut collections: Vec<String> = read_folder(&workspace.collections_path())
+        .into_iter()
+        .filter(|(_, is_folder)| *is_folder)
+        .map(|(name, _)| name)
+        .collect();
+    collections.sort();
+    collections
+}
+
+/// An entry of the collection tree, its path is relative to the collection like
+/// `users/admin/list-admins`.
+pub struct TreeEntry {
+    pub path: String,
+    pub is_folder: bool,
+}
+
+/// The path of `name` inside `folder`, both relative to the collection.
+pub fn child_path(folder: &str, name: &str) -> String {
+    if folder.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", folder, name)
+    }
+}
+
+// the folders and files of a collection folder, folders first and both sorted by name
+fn read_folder(folder_path: &Path) -> Vec<(String, bool)> {
+    let mut entries: Vec<(String, bool)> = fs::read_dir(folder_path)
+        .into_iter()
+        .flatten()
         .filter_map(|entry| {
-            let entry = entry.ok()?; // Handle errors with filter_map
-            let metadata = entry.metadata().ok()?;
-            if !metadata.is_file() {
-                Some(entry.path()) // Collect path if it's a file
-            } else {
-                None
+            let entry = entry.ok()?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            // hidden entries belong to other tools
+            if name.starts_with('.') {
+                return None;
             }
+            Some((name, entry.metadata().ok()?.is_dir()))
         })
-        .enumerate()
-        .map(|entry| {
-            //"\u{f024b} {}",
-            entry
-                .1
-                .display()
-                .to_string()
-                .strip_prefix(std::env::current_dir().unwrap().to_str().unwrap())
-                .unwrap()
-                .to_string()
-                .replace("/", "")
-        })
-        .collect()
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    entries
 }
 
-pub fn list_collection_children(collection_name: String) -> Vec<String> {
-    fs::read_dir(
-        std::env::current_dir()
-            .unwrap()
-            .join(collection_name.clone()),
-    )
-    .unwrap()
-    .filter_map(|entry| {
-        let entry = entry.ok()?; // Handle errors with filter_map
-        let metadata = entry.metadata().ok()?;
-        if metadata.is_file() {
-            Some(entry.path()) // Collect path if it's a file
+/// The folders and requests of a collection as shown in the tree, the children of a folder
+/// are only listed while it is expanded. A file holding several requests, like a `.http`
+/// file, is listed once per request.
+pub fn list_collection_tree(
+    workspace: &Workspace,
+    collection_name: &str,
+    expanded: &HashSet<String>,
+) -> Vec<TreeEntry> {
+    let collection_path = workspace.collections_path().join(collection_name);
+    let mut tree = vec![];
+    add_tree_entries(&collection_path, "", expanded, &mut tree);
+    tree
+}
+
+fn add_tree_entries(
+    collection_path: &Path,
+    folder: &str,
+    expanded: &HashSet<String>,
+    tree: &mut Vec<TreeEntry>,
+) {
+    for (name, is_folder) in read_folder(&collection_path.join(folder)) {
+        let path = child_path(folder, &name);
+        if is_folder {
+            let is_expanded = expanded.contains(&path);
+            tree.push(TreeEntry {
+                path: path.clone(),
+                is_folder,
+            });
+            if is_expanded {
+                add_tree_entries(collection_path, &path, expanded, tree);
+            }
         } else {
-            None
+            for request in request_parser::list_requests(&collection_path.join(&path)) {
+                tree.push(TreeEntry {
+                    path: child_path(folder, &request),
+                    is_folder,
+                });
+            }
         }
-    })
-    .enumerate()
-    .map(|entry| {
-        entry
-            .1
-            .display()
-            .to_string()
-            .strip_prefix(
-                std::env::current_dir()
-                    .unwrap()
-                    .join(collection_name.clone())
-                    .to_str()
-                    .unwrap(),
-            )
-            .unwrap()
-            .to_string()
-            .replace("/", "")
-    })
-    .collect()
+    }
+}
+
+// every request of the collection, including the ones in nested folders
+pub fn list_collection_children(workspace: &Workspace, collection_name: String) -> Vec<String> {
+    let collection_path = workspace.collections_path().join(&collection_name);
+    let mut folders = vec!["".to_string()];
+    let mut requests = vec![];
+
+    while let Some(folder) = folders.pop() {
+        for (name, is_folder) in read_folder(&collection_path.join(&folder)) {
+            let path = child_path(&folder, &name);
+            if is_folder {
+                folders.push(path);
+            } else {
+                for request in request_parser::list_requests(&collection_path.join(&path)) {
+                    requests.push(child_path(&folder, &request));
+                }
+            }
+        }
+    }
+
+    requests.sort();
+    requests
 }
 
 pub fn get_project_path() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
     Ok(config_dir().expect("dir does not exists").join("fetched"))
 }
 
-pub fn create_collection(collection_name: String) -> Result<(), Box<dyn std::error::Error>> {
-    _ = fs::create_dir(std::env::current_dir().unwrap().join(collection_name));
+pub fn create_folder(
+    workspace: &Workspace,
+    collection_name: &str,
+    folder: &str,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(
+        workspace
+            .collections_path()
+            .join(collection_name)
+            .join(folder),
+    )?;
+    Ok(())
+}
+
+pub fn create_collection(workspace: &Workspace, collection_name: &str) -> anyhow::Result<()> {
+    validate_name(collection_name)?;
+    fs::create_dir_all(workspace.collections_path().join(collection_name))?;
     Ok(())
 }
 
 pub fn create_request(
+    workspace: &Workspace,
     collection_name: &String,
     children: String,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let file = fs::File::create(
-        std::env::current_dir()
-            .unwrap()
-            .join(collection_name)
-            .join(children),
-    );
-    let file_content = br#"{
-  "method": "POST",
-  "url": "https://example.com/api/resource",
-  "headers": {
-    "Content-Type": "application/json",
-  },
-  "query_parameters": {
-    "search": "example",
-    "page": "2"
-  },
-  "body_type": "json",
-  "body": null,
-  "options":{
-    "validate_ssl": true,
-    "follow_redirect": true,
-    "attach_cookies": true,
-    "proxy" : "",
-    "timeout": 0.5
-  },
-  "metadata": {}
-}
-    "#;
-    _ = file.unwrap().write_all(file_content);
+    let children =
+        request_parser::with_request_extension(&children, workspace.config.request_format);
+    let mut file_path = workspace
+        .collections_path()
+        .join(collection_name)
+        .join(&children);
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // files holding several requests get a new request appended instead of being replaced
+    if file_path.exists() && formats::for_path(&file_path).is_multi_request() {
+        let count = request_parser::list_requests(&file_path).len();
+        file_path = file_path.with_file_name(format!("{}#{}", children, count + 1));
+    }
+
+    let request = RequestStructure {
+        method: "POST".to_string(),
+        url: "https://example.com/api/resource".to_string(),
+        headers: vec![Header::new("Content-Type", "application/json")],
+        query_parameters: HashMap::from([
+            ("search".to_string(), "example".to_string()),
+            ("page".to_string(), "2".to_string()),
+        ]),
+        body_type: BodyType::Json,
+        options: RequestOptions {
+            timeout_ms: 500,
+            ..importers::default_options()
+        },
+        ..Default::default()
+    };
+    request_parser::update_request(&file_path, request)?;
 
     Ok(())
 }
 
-pub fn delete_collection(collection_name: String) -> Result<(), Box<dyn std::error::Error>> {
-    _ = fs::remove_dir(std::env::current_dir().unwrap().join(collection_name)).unwrap();
+// characters which are not allowed in file names on at least one platform, `#` addresses
+// a request inside a file holding several requests
+const ILLEGAL_NAME_CHARACTERS: [char; 10] = ['/', '\\', '<', '>', ':', '"', '|', '?', '*', '#'];
+
+/// Checks a name typed for a collection, folder or request file.
+pub fn validate_name(name: &str) -> anyhow::Result<()> {
+    if name.trim().is_empty() || name == "." || name == ".." {
+        anyhow::bail!("The name can not be empty");
+    }
+    if let Some(character) = name
+        .chars()
+        .find(|character| ILLEGAL_NAME_CHARACTERS.contains(character) || character.is_control())
+    {
+        anyhow::bail!("The name can not contain {:?}", character);
+    }
+    if name.ends_with('.') || name.ends_with(' ') {
+        anyhow::bail!("The name can not end with a dot or a space");
+    }
     Ok(())
 }
 
-pub fn delete_collection_children(
-    collection_name: String,
-    children: String,
-) -> Result<(), Box<dyn std::error::Error>> {
-    _ = fs::remove_file(
-        std::env::current_dir()
-            .unwrap()
-            .join(collection_name)
-            .join(children),
-    )
-    .unwrap();
+// the file or folder an entry lives in, and the `#n` suffix of a request sharing its file
+fn entry_source(
+    workspace: &Workspace,
+    entry: &str,
+) -> anyhow::Result<(std::path::PathBuf, String)> {
+    let path = workspace.collections_path().join(entry);
+    let (source, _) = request_parser::split_request_path(&path);
+    let suffix = match source == path {
+        true => "".to_string(),
+        false => format!("#{}", entry.rsplit('#').next().unwrap_or_default()),
+    };
+    if !source.exists() {
+        anyhow::bail!("{} does not exist", entry);
+    }
+    Ok((source, suffix))
+}
+
+// the path of `target` relative to the workspace, with the request suffix of the entry
+fn entry_path(workspace: &Workspace, target: &Path, suffix: &str) -> anyhow::Result<String> {
+    let relative = target.strip_prefix(workspace.collections_path())?;
+    Ok(format!("{}{}", relative.to_string_lossy(), suffix))
+}
+
+// a renamed request keeps its file extension, and with it its format
+fn target_name(source: &Path, new_name: &str) -> String {
+    match source.extension() {
+        Some(extension) if source.is_file() && !formats::has_request_extension(new_name) => {
+            format!("{}.{}", new_name, extension.to_string_lossy())
+        }
+        _ => new_name.to_string(),
+    }
+}
+
+/// Renames a collection, folder or request, entries are paths relative to the workspace
+/// like `api/users/get-user`. Returns the new path of the entry.
+pub fn rename_entry(workspace: &Workspace, entry: &str, new_name: &str) -> anyhow::Result<String> {
+    validate_name(new_name)?;
+    let (source, suffix) = entry_source(workspace, entry)?;
+    let target = source.with_file_name(target_name(&source, new_name));
+
+    if target != source && target.exists() {
+        anyhow::bail!(
+            "{} already exists",
+            target.file_name().unwrap().to_string_lossy()
+        );
+    }
+    fs::rename(&source, &target)?;
+    entry_path(workspace, &target, &suffix)
+}
+
+/// Copies a collection, folder or request next to itself under a new name. A request
+/// sharing its file with other requests is copied alone into a new file.
+pub fn duplicate_entry(
+    workspace: &Workspace,
+    entry: &str,
+    new_name: &str,
+) -> anyhow::Result<String> {
+    validate_name(new_name)?;
+    let (source, suffix) = entry_source(workspace, entry)?;
+    let target = source.with_file_name(match suffix.is_empty() {
+        true => target_name(&source, new_name),
+        false => request_parser::with_request_extension(new_name, workspace.config.request_format),
+    });
+
+    if target.exists() {
+        anyhow::bail!(
+            "{} already exists",
+            target.file_name().unwrap().to_string_lossy()
+        );
+    }
+    if source.is_dir() {
+        copy_folder(&source, &target)?;
+    } else if suffix.is_empty() {
+        // copy the file as it is, keeping its formatting and comments
+        fs::copy(&source, &target)?;
+    } else {
+        let request = request_parser::read_request(&workspace.collections_path().join(entry))?;
+        request_parser::update_request(&target, request)?;
+    }
+    entry_path(workspace, &target, "")
+}
+
+fn copy_folder(source: &Path, target: &Path) -> anyhow::Result<()> {
+    fs::create_dir(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            copy_folder(&entry.path(), &target.join(entry.file_name()))?;
+        } else {
+            fs::copy(entry.path(), target.join(entry.file_name()))?;
+        }
+    }
     Ok(())
 }
 
-pub fn get_file_path(
-    collection: String,
-    children: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    Ok(std::env::current_dir()?
-        .join(collection)
-        .join(children)
-        .to_str()
-        .unwrap()
-        .to_string())
+/// Moves a collection, folder or request into another collection or folder, given as a
+/// path relative to the workspace like `api/users`. An empty destination turns a folder
+/// into a collection. A request sharing its file with other requests moves together with
+/// them. Returns the new path of the entry.
+pub fn move_entry(workspace: &Workspace, entry: &str, destination: &str) -> anyhow::Result<String> {
+    let (source, suffix) = entry_source(workspace, entry)?;
+    let destination_path = workspace.collections_path().join(destination);
+
+    if destination.is_empty() && source.is_file() {
+        anyhow::bail!("Requests have to be inside a collection");
+    }
+    if !destination_path.is_dir() {
+        anyhow::bail!("{} is not a collection or folder", destination);
+    }
+    if destination_path.starts_with(&source) {
+        anyhow::bail!("{} can not be moved into itself", entry);
+    }
+    let target = destination_path.join(source.file_name().unwrap_or_default());
+    if target.exists() {
+        anyhow::bail!(
+            "{} already exists in {}",
+            target.file_name().unwrap().to_string_lossy(),
+            destination
+        );
+    }
+    fs::rename(&source, &target)?;
+    entry_path(workspace, &target, &suffix)
+}
+
+// move the highlighted request or folder into another folder of the opened collection,
+// `/` is the root of the collection
+pub fn move_selected_entry(app: &mut App, destination: &str) {
+    if destination.is_empty() {
+        app.show_error_popup("Usage: move <collection>[/<folder>], or / for the top".to_string());
+        return;
+    }
+    let Some(entry) = app.selected_entry() else {
+        app.show_error_popup("Select a collection, folder or request to move".to_string());
+        return;
+    };
+
+    match move_entry(&app.workspace, &entry, destination.trim_matches('/')) {
+        Ok(moved) => app.follow_entry(&moved),
+        Err(err) => app.show_error_popup(format!("Failed to move {}: {}", entry, err)),
+    }
+}
+
+// requests are imported into the opened collection, or the highlighted one
+fn get_target_collection(app: &App) -> Option<String> {
+    if app.show_collection_children {
+        Some(app.selected_collection.clone())
+    } else {
+        app.collection_window_list_state
+            .selected()
+            .and_then(|index| app.collections.get(index))
+            .cloned()
+    }
+}
+
+// derive a file name like `get-users` from the request, without clobbering existing files
+fn get_request_name(
+    workspace: &Workspace,
+    collection_name: &String,
+    method: &str,
+    url: &str,
+) -> String {
+    importers::unique_path(
+        &workspace.collections_path().join(collection_name),
+        &request_parser::with_request_extension(
+            &importers::request_file_name(method, url),
+            workspace.config.request_format,
+        ),
+    )
+    .file_name()
+    .unwrap()
+    .to_string_lossy()
+    .to_string()
+}
+
+pub fn import_curl(app: &mut App, command: &str) {
+    let collection_name = match get_target_collection(app) {
+        Some(collection_name) => collection_name,
+        None => {
+            app.show_error_popup("Select a collection to import into".to_string());
+            return;
+        }
+    };
+
+    let import = match importers::curl::parse(command) {
+        Ok(import) => import,
+        Err(err) => {
+            app.show_error_popup(format!("Failed to import cURL command: {}", err));
+            return;
+        }
+    };
+
+    let request_name = get_request_name(
+        &app.workspace,
+        &collection_name,
+        &import.request.method,
+        &import.request.url,
+    );
+    let file_path = app
+        .workspace
+        .collections_path()
+        .join(&collection_name)
+        .join(&request_name);
+    if let Err(err) = request_parser::update_request(&file_path, import.request) {
+        app.show_error_popup(format!("Failed to save {}: {}", request_name, err));
+        return;
+    }
+
+    if app.show_collection_children {
+        app.reload_collections();
+        let index = app
+            .collections
+            .iter()
+            .position(|name| *name == request_name);
+        app.collection_window_list_state.select(index);
+    }
+
+    let mut msg = format!("Imported {} into {}", request_name, collection_name);
+    if !import.unsupported.is_empty() {
+        msg.push_str(&format!(
+            "\n\nUnsupported flags were not imported:\n{}",
+            import.unsupported.join("\n")
+        ));
+    }
+    app.show_popup(msg);
+}
+
+// import a file or folder exported by another client as new collections
+pub fn import_collection(app: &mut App, client: &str, path: &str) {
+    if path.is_empty() {
+        app.show_error_popup(format!("Usage: import {} <path>", client));
+        return;
+    }
+
+    let path = helpers::expand_path(path);
+    let result = match client {
+        "postman" => importers::postman::import(&path, &app.workspace),
+        "insomnia" => importers::insomnia::import(&path, &app.workspace),
+        "openapi" | "swagger" => importers::openapi::import(&path, &app.workspace),
+        _ => importers::bruno::import(&path, &app.workspace),
+    };
+
+    match result {
+        Ok((collection_name, report)) => {
+            if !app.show_collection_children {
+                app.reload_collections();
+            }
+            app.show_popup(report.to_message(&collection_name));
+        }
+        Err(err) => app.show_error_popup(format!("Failed to import {}: {}", path.display(), err)),
+    }
+}
+
+pub fn import_har(app: &mut App, file_path: &str, host_filter: Option<&str>) {
+    if file_path.is_empty() {
+        app.show_error_popup("Usage: import har <file> [host]".to_string());
+        return;
+    }
+    let collection_name = match get_target_collection(app) {
+        Some(collection_name) => collection_name,
+        None => {
+            app.show_error_popup("Select a collection to import into".to_string());
+            return;
+        }
+    };
+
+    match importers::har::import(
+        &helpers::expand_path(file_path),
+        &app.workspace.collections_path().join(&collection_name),
+        host_filter,
+        app.workspace.config.request_format,
+    ) {
+        Ok(report) => {
+            if app.show_collection_children {
+                app.reload_collections();
+            }
+            app.show_popup(report.to_message(&collection_name));
+        }
+        Err(err) => app.show_error_popup(format!("Failed to import {}: {}", file_path, err)),
+    }
+}
+
+// write sent requests with their answers into a HAR file
+pub fn export_har(app: &mut App, file_path: &str, entries: &[&HistoryEntry]) {
+    if file_path.is_empty() {
+        app.show_error_popup("Usage: export har <file>".to_string());
+        return;
+    }
+    if entries.is_empty() {
+        app.show_error_popup("No requests were sent yet".to_string());
+        return;
+    }
+
+    let path = helpers::expand_path(file_path);
+    match fs::write(&path, exporters::har::render(entries)) {
+        Ok(_) => app.show_popup(format!(
+            "Exported {} requests to {}",
+            entries.len(),
+            path.display()
+        )),
+        Err(err) => app.show_error_popup(format!("Failed to write {}: {}", path.display(), err)),
+    }
 }
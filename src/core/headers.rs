@@ -0,0 +1,81 @@
+// request headers offered while typing a header name
+const STANDARD_HEADERS: [&str; 32] = [
+    "Accept",
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Authorization",
+    "Cache-Control",
+    "Connection",
+    "Content-Disposition",
+    "Content-Encoding",
+    "Content-Length",
+    "Content-Type",
+    "Cookie",
+    "DNT",
+    "Date",
+    "Expect",
+    "Forwarded",
+    "From",
+    "Host",
+    "If-Match",
+    "If-Modified-Since",
+    "If-None-Match",
+    "If-Range",
+    "If-Unmodified-Since",
+    "Origin",
+    "Pragma",
+    "Range",
+    "Referer",
+    "TE",
+    "User-Agent",
+    "X-API-Key",
+    "X-Forwarded-For",
+    "X-Requested-With",
+];
+
+const MEDIA_TYPES: [&str; 8] = [
+    "application/json",
+    "application/xml",
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+    "text/html",
+    "application/octet-stream",
+    "*/*",
+];
+
+// values offered for the headers which mostly hold one of a few
+fn common_values(name: &str) -> &'static [&'static str] {
+    match name.to_lowercase().as_str() {
+        "accept" | "content-type" => &MEDIA_TYPES,
+        "accept-encoding" | "content-encoding" => &["gzip", "deflate", "br", "identity"],
+        "accept-language" => &["en-US,en;q=0.9", "en", "*"],
+        "authorization" => &["Bearer {{token}}", "Basic "],
+        "cache-control" => &["no-cache", "no-store", "max-age=0"],
+        "connection" => &["keep-alive", "close"],
+        "pragma" => &["no-cache"],
+        "x-requested-with" => &["XMLHttpRequest"],
+        _ => &[],
+    }
+}
+
+// case is ignored, what is typed tends to be lower case
+fn starting_with(candidates: &[&str], typed: &str) -> Vec<String> {
+    let typed = typed.to_lowercase();
+    candidates
+        .iter()
+        .filter(|candidate| candidate.to_lowercase().starts_with(&typed))
+        .map(|candidate| candidate.to_string())
+        .collect()
+}
+
+/// The standard header names starting with `typed`.
+pub fn complete_name(typed: &str) -> Vec<String> {
+    starting_with(&STANDARD_HEADERS, typed)
+}
+
+/// The common values of the header `name` starting with `typed`.
+pub fn complete_value(name: &str, typed: &str) -> Vec<String> {
+    starting_with(common_values(name), typed)
+}
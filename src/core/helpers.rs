@@ -1,5 +1,7 @@
 use crate::core::enums::ContainerPositions;
+use crate::core::environment;
 use ratatui::layout::Rect;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 pub fn logger<T: Debug>(msg: T) {
@@ -57,3 +59,108 @@ pub fn find_position(position: ContainerPositions, ratio: f32, area: Rect) -> Re
         _ => area,
     }
 }
+
+// percent encode everything except the unreserved characters of RFC 3986
+pub fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Whether `name` is a token of rfc 9110, what the names of methods and headers are.
+pub fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|character| {
+            character.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(character)
+        })
+}
+
+/// Checks that a request can be sent to `url` once its variables are replaced. Variables
+/// without a value are left as they are, a url starting with one is not checked further.
+pub fn validate_url(url: &str, variables: &HashMap<String, String>) -> anyhow::Result<()> {
+    if url.trim().is_empty() {
+        anyhow::bail!("The URL is empty");
+    }
+    if url.contains("{{") && url.matches("{{").count() != url.matches("}}").count() {
+        anyhow::bail!("A variable is not closed with }}}}");
+    }
+    let url = environment::interpolate(url, variables);
+    if url.starts_with("{{") {
+        return Ok(());
+    }
+    if url.chars().any(char::is_whitespace) {
+        anyhow::bail!("The URL holds a space, write it as %20");
+    }
+    let Some((scheme, rest)) = url.split_once("://") else {
+        anyhow::bail!("The URL needs to start with http:// or https://");
+    };
+    if !["http", "https"].contains(&scheme.to_lowercase().as_str()) {
+        anyhow::bail!("{}:// is not supported, use http:// or https://", scheme);
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() || host.starts_with(':') {
+        anyhow::bail!("The URL has no host");
+    }
+    Ok(())
+}
+
+// copy through the terminal with the OSC 52 escape sequence, this also works over ssh
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    use base64::Engine;
+    use std::io::Write;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
+
+// expand a leading `~` to the home directory
+pub fn expand_path(path: &str) -> std::path::PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => std::path::PathBuf::from(path),
+    }
+}
+
+// ISO 8601 in UTC like `2024-05-01T12:30:00.000Z`
+pub fn format_timestamp(time: std::time::SystemTime) -> String {
+    let duration = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let seconds = duration.as_secs();
+    let (days, seconds_of_day) = ((seconds / 86400) as i64, seconds % 86400);
+
+    // civil from days, http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        duration.subsec_millis()
+    )
+}
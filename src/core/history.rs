@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::components::structs::RequestStructure;
+use crate::constants::HISTORY_DIR_NAME;
+use crate::core::exporters;
+use crate::core::http::{Client, Response};
+
+// entries kept, the oldest are dropped first
+const HISTORY_LIMIT: usize = 500;
+// bytes of a body kept, so a few large downloads do not blow up the file
+const BODY_LIMIT: usize = 64 * 1024;
+
+/// A request as it was sent, along with what came back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // milliseconds since the epoch
+    pub sent_at: u64,
+    pub duration_ms: u64,
+    // the named environment the variables came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    // with its variables filled in
+    pub request: RequestStructure,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<Response>,
+    // why there is no response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// The method, url and status, which the filter of the history searches.
+    pub fn summary(&self) -> String {
+        let status = match &self.response {
+            Some(response) => response.status.to_string(),
+            None => "failed".to_string(),
+        };
+        format!(
+            "{} {} {}",
+            status,
+            self.request.method.to_uppercase(),
+            exporters::full_url(&self.request)
+        )
+    }
+
+    pub fn matches(&self, filter: &str) -> bool {
+        self.summary()
+            .to_lowercase()
+            .contains(&filter.to_lowercase())
+    }
+}
+
+// kept out of the workspace, since the requests are stored with their secrets filled in
+fn history_path(workspace_root: &Path) -> PathBuf {
+    // one file per workspace, named after where the workspace is
+    let name: String = workspace_root
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    dirs::data_dir()
+        .unwrap_or_default()
+        .join("fetched")
+        .join(HISTORY_DIR_NAME)
+        .join(format!("{}.jsonl", name.trim_matches('_')))
+}
+
+// cut at a character boundary, marking that something is missing
+fn truncate(text: &mut String) {
+    if text.len() <= BODY_LIMIT {
+        return;
+    }
+    let mut end = BODY_LIMIT;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str("\n[cut]");
+}
+
+// binary bodies are cut anywhere, they cannot be shown anyway
+fn truncate_body(body: &mut Vec<u8>) {
+    match String::from_utf8(std::mem::take(body)) {
+        Ok(mut text) => {
+            truncate(&mut text);
+            *body = text.into_bytes();
+        }
+        Err(err) => {
+            *body = err.into_bytes();
+            body.truncate(BODY_LIMIT);
+        }
+    }
+}
+
+/// Sends a request whose variables are filled in from `environment`, timing it. A failure is
+/// part of the entry.
+pub fn send(
+    client: &Client,
+    request: RequestStructure,
+    environment: Option<String>,
+) -> HistoryEntry {
+    let sent_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let started = Instant::now();
+    let answer = client.send(&request);
+    let (response, error) = match answer {
+        Ok(response) => (Some(response), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+    HistoryEntry {
+        sent_at: sent_at.as_millis() as u64,
+        duration_ms: started.elapsed().as_millis() as u64,
+        environment,
+        request,
+        response,
+        error,
+    }
+}
+
+/// Appends an entry to the history of the workspace, dropping the oldest ones past the limit.
+pub fn record(workspace_root: &Path, entry: &HistoryEntry) -> anyhow::Result<()> {
+    let mut entry = entry.clone();
+    truncate(&mut entry.request.body);
+    if let Some(response) = entry.response.as_mut() {
+        truncate_body(&mut response.body);
+    }
+    let path = history_path(workspace_root);
+    fs::create_dir_all(path.parent().unwrap_or(&path))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+    let raw_data = fs::read_to_string(&path)?;
+    let lines: Vec<&str> = raw_data.lines().collect();
+    if lines.len() > HISTORY_LIMIT {
+        let kept = &lines[lines.len() - HISTORY_LIMIT..];
+        fs::write(path, format!("{}\n", kept.join("\n")))?;
+    }
+    Ok(())
+}
+
+/// The sent requests, the most recent first. Lines which do not parse are skipped.
+pub fn list(workspace_root: &Path) -> Vec<HistoryEntry> {
+    let raw_data = fs::read_to_string(history_path(workspace_root)).unwrap_or_default();
+    let mut entries: Vec<HistoryEntry> = raw_data
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    entries.reverse();
+    entries
+}
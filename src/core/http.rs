@@ -0,0 +1,424 @@
+use cookie_store::CookieStore;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+use crate::components::structs::RequestStructure;
+use crate::core::enums::BodyType;
+use crate::core::exporters;
+
+// redirects followed before giving up, when the request follows them at all
+const REDIRECT_LIMIT: usize = 10;
+// how long a request without a timeout of its own may take
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+// bytes of a body read, the rest of a large or endless answer is dropped
+const BODY_LIMIT: u64 = 64 * 1024 * 1024;
+// headers which carry credentials, kept from hosts a request is redirected to
+const CREDENTIAL_HEADERS: [&str; 3] = ["authorization", "cookie", "proxy-authorization"];
+
+/// What the server answered to a request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    // the bytes as they came, a body need not be text
+    #[serde(with = "body")]
+    pub body: Vec<u8>,
+    // whether the body was cut at the limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl Response {
+    /// The value of the first header called `name`, whatever its case.
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The body when it is text.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.body).ok()
+    }
+
+    /// The body as it is shown, binary bodies are only described.
+    pub fn display_body(&self) -> String {
+        let body = match self.text() {
+            Some(text) => text.to_string(),
+            None => format!("[{} bytes of binary data]", self.body.len()),
+        };
+        match self.truncated {
+            true => format!("{}\n[cut after {} bytes]", body, self.body.len()),
+            false => body,
+        }
+    }
+}
+
+// bodies which are text are written as a string, the others as base64
+mod body {
+    use base64::prelude::BASE64_STANDARD;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Text(String),
+        Binary { base64: String },
+    }
+
+    pub fn serialize<S: Serializer>(body: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let stored = match std::str::from_utf8(body) {
+            Ok(text) => Stored::Text(text.to_string()),
+            Err(_) => Stored::Binary {
+                base64: BASE64_STANDARD.encode(body),
+            },
+        };
+        stored.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        match Stored::deserialize(deserializer)? {
+            Stored::Text(text) => Ok(text.into_bytes()),
+            Stored::Binary { base64 } => BASE64_STANDARD
+                .decode(base64)
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+// accepts any certificate, for requests which turn `validate_ssl` off
+#[derive(Debug)]
+struct AnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn insecure_tls_config() -> anyhow::Result<Arc<ClientConfig>> {
+    let provider = Arc::new(ring::default_provider());
+    let config = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AnyCertificate(provider)))
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+/// Sends requests, keeping the cookies the servers set for the requests which attach them.
+/// Clones share the cookies, so requests can be sent from another thread.
+#[derive(Clone, Default)]
+pub struct Client {
+    cookies: Arc<Mutex<CookieStore>>,
+}
+
+impl Client {
+    fn agent(&self, request: &RequestStructure) -> anyhow::Result<ureq::Agent> {
+        let timeout = match request.options.timeout_ms {
+            0 => DEFAULT_TIMEOUT,
+            timeout_ms => Duration::from_millis(timeout_ms),
+        };
+        // redirects are followed here, so cookies are kept along the way
+        let mut builder = ureq::AgentBuilder::new().timeout(timeout).redirects(0);
+        if !request.options.validate_ssl {
+            builder = builder.tls_config(insecure_tls_config()?);
+        }
+        match request.options.proxy.trim() {
+            "" => {}
+            proxy => builder = builder.proxy(ureq::Proxy::new(proxy)?),
+        }
+        Ok(builder.build())
+    }
+
+    fn send_once(
+        &self,
+        agent: &ureq::Agent,
+        request: &RequestStructure,
+        url: &Url,
+    ) -> anyhow::Result<Response> {
+        let mut call = agent.request_url(&request.method.to_uppercase(), url);
+        for header in request.enabled_headers() {
+            call = call.set(&header.name, &header.value);
+        }
+        if request.options.attach_cookies && request.header_value("cookie").is_none() {
+            let cookies = self.cookies.lock().unwrap();
+            let cookie = cookies
+                .get_request_values(url)
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; ");
+            if !cookie.is_empty() {
+                call = call.set("Cookie", &cookie);
+            }
+        }
+
+        let has_body = request.body_type != BodyType::None && !request.body.is_empty();
+        let answer = match has_body {
+            true => {
+                if request.header_value("content-type").is_none() {
+                    call = call.set("Content-Type", exporters::mime_type(&request.body_type));
+                }
+                call.send_bytes(request.body.as_bytes())
+            }
+            false => call.call(),
+        };
+        // an error status is still an answer
+        let answer = match answer {
+            Ok(answer) | Err(ureq::Error::Status(_, answer)) => answer,
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut headers = vec![];
+        for name in answer.headers_names() {
+            for value in answer.all(&name) {
+                headers.push((name.clone(), value.to_string()));
+            }
+        }
+        if request.options.attach_cookies {
+            let mut cookies = self.cookies.lock().unwrap();
+            for value in answer.all("set-cookie") {
+                // a cookie the store refuses is dropped, like a browser would
+                _ = cookies.parse(value, url);
+            }
+        }
+        let status = answer.status();
+        let reason = answer.status_text().to_string();
+        // one byte past the limit tells whether something was left
+        let mut body = vec![];
+        answer
+            .into_reader()
+            .take(BODY_LIMIT + 1)
+            .read_to_end(&mut body)?;
+        let truncated = body.len() as u64 > BODY_LIMIT;
+        if truncated {
+            body.truncate(BODY_LIMIT as usize);
+            // a character cut in half would make the whole body look binary
+            if let Err(err) = std::str::from_utf8(&body) {
+                if err.error_len().is_none() {
+                    body.truncate(err.valid_up_to());
+                }
+            }
+        }
+        Ok(Response {
+            status,
+            reason,
+            headers,
+            body,
+            truncated,
+        })
+    }
+
+    /// Sends a request with its variables filled in and waits for the answer, following
+    /// redirects when the request asks for it.
+    pub fn send(&self, request: &RequestStructure) -> anyhow::Result<Response> {
+        let agent = self.agent(request)?;
+        let mut request = request.clone();
+        let mut url = Url::parse(&exporters::full_url(&request))?;
+        for _ in 0..REDIRECT_LIMIT {
+            let response = self.send_once(&agent, &request, &url)?;
+            let location = response.header_value("location").filter(|_| {
+                request.options.follow_redirect && (300..400).contains(&response.status)
+            });
+            let Some(location) = location else {
+                return Ok(response);
+            };
+            // the location may be relative to the url which redirected
+            let origin = url.origin();
+            url = url.join(location)?;
+            // credentials are only meant for the host they were written for, like curl does
+            if url.origin() != origin {
+                request.headers.retain(|header| {
+                    !CREDENTIAL_HEADERS.contains(&header.name.to_lowercase().as_str())
+                });
+            }
+            // a see other is fetched with a get, without the body
+            if response.status == 303 {
+                request.method = "GET".to_string();
+                request.body_type = BodyType::None;
+            }
+        }
+        anyhow::bail!("gave up after {} redirects", REDIRECT_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::structs::Header;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // answers each connection with the next of `answers`, returning the request heads seen,
+    // the request line first
+    fn serve(answers: Vec<Vec<u8>>) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut heads = vec![];
+            for answer in answers {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = vec![];
+                let mut line = String::new();
+                // the head ends with an empty line
+                while line != "\r\n" {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    head.push(line.trim().to_string());
+                }
+                head.pop();
+                heads.push(head);
+                stream.write_all(&answer).unwrap();
+            }
+            heads
+        });
+        (address, server)
+    }
+
+    #[test]
+    fn follows_relative_redirects_and_keeps_binary_bodies() {
+        let mut binary = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n".to_vec();
+        binary.extend_from_slice(&[0xff, 0x00, 0xfe]);
+        binary.extend_from_slice(b"\r\n0\r\n\r\n");
+        let (address, server) = serve(vec![
+            b"HTTP/1.1 302 Found\r\nLocation: next\r\nContent-Length: 0\r\n\r\n".to_vec(),
+            binary,
+        ]);
+        let mut request = RequestStructure {
+            method: "GET".to_string(),
+            url: format!("{}/files/first", address),
+            ..RequestStructure::default()
+        };
+        request.options.follow_redirect = true;
+        request.options.validate_ssl = true;
+
+        let response = Client::default().send(&request).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, vec![0xff, 0x00, 0xfe]);
+        assert_eq!(response.text(), None);
+        let request_lines: Vec<_> = server
+            .join()
+            .unwrap()
+            .into_iter()
+            .map(|head| head[0].clone())
+            .collect();
+        assert_eq!(
+            request_lines,
+            ["GET /files/first HTTP/1.1", "GET /files/next HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn keeps_credentials_from_other_hosts_on_redirects() {
+        let ok = b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".to_vec();
+        let (elsewhere, other_server) = serve(vec![ok]);
+        let redirect = format!(
+            "HTTP/1.1 302 Found\r\nLocation: {}/landing\r\nContent-Length: 0\r\n\r\n",
+            elsewhere
+        );
+        let (address, server) = serve(vec![redirect.into_bytes()]);
+        let mut request = RequestStructure {
+            method: "GET".to_string(),
+            url: format!("{}/login", address),
+            headers: vec![
+                Header::new("Authorization", "Bearer secret"),
+                Header::new("Cookie", "session=1"),
+                Header::new("Accept", "text/plain"),
+            ],
+            ..RequestStructure::default()
+        };
+        request.options.follow_redirect = true;
+        request.options.validate_ssl = true;
+
+        assert_eq!(Client::default().send(&request).unwrap().status, 200);
+        let names = |head: &[String]| -> Vec<String> {
+            head[1..]
+                .iter()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, _)| name.to_lowercase())
+                .collect()
+        };
+        let sent = names(&server.join().unwrap()[0]);
+        assert!(sent.contains(&"authorization".to_string()));
+        assert!(sent.contains(&"cookie".to_string()));
+        let forwarded = names(&other_server.join().unwrap()[0]);
+        assert!(forwarded.contains(&"accept".to_string()));
+        assert!(!forwarded.contains(&"authorization".to_string()));
+        assert!(!forwarded.contains(&"cookie".to_string()));
+    }
+
+    #[test]
+    fn stores_binary_bodies_as_base64() {
+        let response = Response {
+            status: 200,
+            reason: "OK".to_string(),
+            headers: vec![],
+            body: vec![0xff, 0xfe],
+            truncated: false,
+        };
+        let stored = serde_json::to_value(&response).unwrap();
+        assert_eq!(stored["body"], serde_json::json!({ "base64": "//4=" }));
+        assert_eq!(
+            serde_json::from_value::<Response>(stored).unwrap(),
+            response
+        );
+
+        // history written before bodies were bytes has them as strings
+        let stored = serde_json::json!({
+            "status": 200, "reason": "OK", "headers": [], "body": "hello",
+        });
+        let response: Response = serde_json::from_value(stored).unwrap();
+        assert_eq!(response.text(), Some("hello"));
+    }
+}
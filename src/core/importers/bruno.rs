@@ -0,0 +1,632 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+use crate::core::environment;
+use crate::core::importers::{
+    basic_auth, default_options, path_variable, sanitize_file_name, set_header, split_query,
+    unique_path, ImportReport,
+};
+use crate::core::request_parser;
+use crate::core::workspace::Workspace;
+
+const HTTP_METHODS: [&str; 7] = ["get", "post", "put", "patch", "delete", "head", "options"];
+
+// a `name { ... }` or `name [ ... ]` section of a .bru file
+#[derive(Clone)]
+struct Block {
+    name: String,
+    lines: Vec<String>,
+}
+
+impl Block {
+    // the body of text blocks like `body:json`, without the indentation of the block
+    fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.strip_prefix("  ").unwrap_or(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // `key: value` entries of dictionary blocks, disabled entries start with `~`
+    fn entries(&self) -> Vec<(String, String, bool)> {
+        self.lines
+            .iter()
+            .filter_map(|line| {
+                let (key, value) = line.trim().split_once(':')?;
+                let (key, is_disabled) = match key.strip_prefix('~') {
+                    Some(key) => (key, true),
+                    None => (key, false),
+                };
+                Some((
+                    key.trim().to_string(),
+                    value.trim().to_string(),
+                    is_disabled,
+                ))
+            })
+            .collect()
+    }
+
+    fn get(&self, key: &str) -> String {
+        self.entries()
+            .into_iter()
+            .find(|(name, _, _)| name == key)
+            .map(|(_, value, _)| value)
+            .unwrap_or_default()
+    }
+}
+
+fn parse_bru(raw_data: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut current: Option<Block> = None;
+
+    for line in raw_data.lines() {
+        match current.as_mut() {
+            Some(block) => {
+                if line == "}" || line == "]" {
+                    blocks.push(current.take().unwrap());
+                } else {
+                    block.lines.push(line.to_string());
+                }
+            }
+            None => {
+                let line = line.trim_end();
+                if let Some(name) = line.strip_suffix('{').or(line.strip_suffix('[')) {
+                    current = Some(Block {
+                        name: name.trim().to_string(),
+                        lines: vec![],
+                    });
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+fn find<'a>(blocks: &'a [Block], name: &str) -> Option<&'a Block> {
+    blocks.iter().find(|block| block.name == name)
+}
+
+fn is_auth(block: &Block) -> bool {
+    block.name == "auth" || block.name.starts_with("auth:")
+}
+
+// the headers and auth a folder passes on to its requests, its headers win over the ones of
+// the folders above and its auth replaces theirs unless it inherits it
+fn inherit(parent: &[Block], folder: &[Block]) -> Vec<Block> {
+    let mut lines = vec![];
+    for blocks in [parent, folder] {
+        if let Some(headers) = find(blocks, "headers") {
+            lines.extend(headers.lines.iter().cloned());
+        }
+    }
+    let mut inherited = vec![Block {
+        name: "headers".to_string(),
+        lines,
+    }];
+    let folder_auth = find(folder, "auth")
+        .map(|auth| auth.get("mode"))
+        .filter(|mode| !mode.is_empty() && mode != "inherit");
+    let auth_blocks = match folder_auth {
+        Some(_) => folder,
+        None => parent,
+    };
+    inherited.extend(auth_blocks.iter().filter(|block| is_auth(block)).cloned());
+    inherited
+}
+
+/// Imports a Bruno collection folder as a collection of `workspace`.
+///
+/// Folders are copied as nested directories, `.bru` requests become request files and
+/// the files of the `environments` folder become named environments.
+pub fn import(collection_dir: &Path, workspace: &Workspace) -> Result<(String, ImportReport)> {
+    let mut report = ImportReport::default();
+
+    let bruno_file = collection_dir.join("bruno.json");
+    if !bruno_file.exists() {
+        bail!("{} is not a Bruno collection", collection_dir.display());
+    }
+    let bruno_config: serde_json::Value = serde_json::from_str(&fs::read_to_string(bruno_file)?)?;
+    let name = match bruno_config.get("name").and_then(|name| name.as_str()) {
+        Some(name) => name.to_string(),
+        None => collection_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    // collection level headers and auth are inherited by every request
+    let collection_blocks = match fs::read_to_string(collection_dir.join("collection.bru")) {
+        Ok(raw_data) => parse_bru(&raw_data),
+        Err(_) => vec![],
+    };
+
+    let collection_path = unique_path(&workspace.collections_path(), &sanitize_file_name(&name));
+    fs::create_dir_all(&collection_path)?;
+    import_dir(
+        collection_dir,
+        &collection_path,
+        &collection_blocks,
+        &name,
+        workspace,
+        &mut report,
+    )?;
+
+    // the values path parameters came with are shared by every environment
+    for conflict in environment::merge_variables(&workspace.root, None, &report.variables)? {
+        report.warn(format!(
+            "variable {} already exists in the environment with another value",
+            conflict
+        ));
+    }
+
+    let environments_dir = collection_dir.join("environments");
+    if environments_dir.is_dir() {
+        for entry in fs::read_dir(environments_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "bru") {
+                import_environment(&path, workspace, &mut report)?;
+            }
+        }
+    }
+
+    let collection_name = collection_path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    Ok((collection_name, report))
+}
+
+fn import_dir(
+    dir: &Path,
+    target: &Path,
+    inherited_blocks: &[Block],
+    location: &str,
+    workspace: &Workspace,
+    report: &mut ImportReport,
+) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|entry| entry.ok()).collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    let folder_blocks = match fs::read_to_string(dir.join("folder.bru")) {
+        Ok(raw_data) => parse_bru(&raw_data),
+        Err(_) => vec![],
+    };
+    let folder_inherited = inherit(inherited_blocks, &folder_blocks);
+
+    for entry in entries {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let location = format!("{}/{}", location, file_name);
+
+        if path.is_dir() {
+            if file_name == "environments" || file_name.starts_with('.') {
+                continue;
+            }
+            let folder_path = unique_path(target, &sanitize_file_name(&file_name));
+            fs::create_dir_all(&folder_path)?;
+            import_dir(
+                &path,
+                &folder_path,
+                &folder_inherited,
+                &location,
+                workspace,
+                report,
+            )?;
+        } else if file_name == "collection.bru" {
+            // headers and auth were already applied to every request
+            for block in inherited_blocks.iter().filter(|block| {
+                !["meta", "headers", "auth"].contains(&block.name.as_str())
+                    && !block.name.starts_with("auth:")
+            }) {
+                report.warn(format!("{}: {}", location, block.name));
+            }
+        } else if file_name == "folder.bru" {
+            // headers and auth are applied to the requests of the folder
+            for block in folder_blocks
+                .iter()
+                .filter(|block| !["meta", "headers"].contains(&block.name.as_str()))
+                .filter(|block| !is_auth(block))
+            {
+                report.warn(format!("{}: {}", location, block.name));
+            }
+        } else if path.extension().is_some_and(|extension| extension == "bru") {
+            let blocks = parse_bru(&fs::read_to_string(&path)?);
+            let request_name = find(&blocks, "meta")
+                .map(|meta| meta.get("name"))
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| file_name.trim_end_matches(".bru").to_string());
+
+            match convert_request(&blocks, &folder_inherited, &location, report) {
+                Some(request) => {
+                    request_parser::update_request(
+                        &unique_path(
+                            target,
+                            &request_parser::with_request_extension(
+                                &sanitize_file_name(&request_name),
+                                workspace.config.request_format,
+                            ),
+                        ),
+                        request,
+                    )?;
+                    report.created += 1;
+                }
+                None => report.warn(format!("{}: not an http request", location)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn import_environment(path: &Path, workspace: &Workspace, report: &mut ImportReport) -> Result<()> {
+    let environment_name = path.file_stem().unwrap().to_string_lossy().to_string();
+    let blocks = parse_bru(&fs::read_to_string(path)?);
+
+    let mut variables = vec![];
+    for block in &blocks {
+        match block.name.as_str() {
+            "vars" => {
+                for (name, value, is_disabled) in block.entries() {
+                    if is_disabled {
+                        report.warn(format!(
+                            "environment {}: disabled variable {}",
+                            environment_name, name
+                        ));
+                    } else {
+                        variables.push((name, value));
+                    }
+                }
+            }
+            // secrets are not part of the export, keep the names so they can be filled in
+            "vars:secret" => {
+                for line in &block.lines {
+                    let name = line.trim().trim_end_matches(',').trim_start_matches('~');
+                    if !name.is_empty() {
+                        variables.push((name.to_string(), "".to_string()));
+                    }
+                }
+            }
+            name => report.warn(format!("environment {}: {}", environment_name, name)),
+        }
+    }
+
+    for conflict in
+        environment::merge_variables(&workspace.root, Some(&environment_name), &variables)?
+    {
+        report.warn(format!(
+            "variable {} already exists in environment {} with another value",
+            conflict, environment_name
+        ));
+    }
+    Ok(())
+}
+
+// a request header replaces a collection header of the same name, disabled headers
+// starting with `~` are kept turned off
+fn add_header(headers: &mut Vec<Header>, (name, value, is_disabled): (String, String, bool)) {
+    headers.retain(|header| !header.name.eq_ignore_ascii_case(&name));
+    headers.push(Header {
+        enabled: !is_disabled,
+        ..Header::new(name, value)
+    });
+}
+
+fn convert_request(
+    blocks: &[Block],
+    inherited_blocks: &[Block],
+    location: &str,
+    report: &mut ImportReport,
+) -> Option<RequestStructure> {
+    let method_block = blocks
+        .iter()
+        .find(|block| HTTP_METHODS.contains(&block.name.as_str()))?;
+
+    let (mut url, mut query_parameters) = split_query(&method_block.get("url"));
+    let mut headers: Vec<Header> = vec![];
+    let mut body_type = BodyType::None;
+    let mut body = "".to_string();
+    let mut graphql_variables = None;
+    let mut is_graphql = false;
+
+    for entry in find(inherited_blocks, "headers")
+        .map(Block::entries)
+        .unwrap_or_default()
+    {
+        add_header(&mut headers, entry);
+    }
+
+    for block in blocks {
+        match block.name.as_str() {
+            "meta" => {}
+            name if HTTP_METHODS.contains(&name) => {}
+            "params:query" | "query" => {
+                // the url already contains the enabled parameters, only drop the disabled ones
+                query_parameters.clear();
+                for (name, value, is_disabled) in block.entries() {
+                    if is_disabled {
+                        report.warn(format!("{}: disabled query parameter {}", location, name));
+                    } else {
+                        query_parameters.insert(name, value);
+                    }
+                }
+            }
+            // `:id` becomes the `{{id}}` variable, the value goes into the environment
+            "params:path" => {
+                for (name, value, _) in block.entries() {
+                    url = path_variable(&url, &name);
+                    if !value.is_empty() {
+                        report.variables.push((name, value));
+                    }
+                }
+            }
+            "headers" => {
+                for entry in block.entries() {
+                    add_header(&mut headers, entry);
+                }
+            }
+            "body:json" => (body_type, body) = (BodyType::Json, block.text()),
+            "body:text" => (body_type, body) = (BodyType::Text, block.text()),
+            "body:xml" => (body_type, body) = (BodyType::Xml, block.text()),
+            "body:graphql" => {
+                (body_type, body) = (BodyType::Json, block.text());
+                is_graphql = true;
+            }
+            "body:graphql:vars" => graphql_variables = Some(block.text()),
+            "body:form-urlencoded" => {
+                let pairs: Vec<String> = block
+                    .entries()
+                    .into_iter()
+                    .filter(|(_, _, is_disabled)| !is_disabled)
+                    .map(|(name, value, _)| format!("{}={}", name, value))
+                    .collect();
+                (body_type, body) = (BodyType::Form, pairs.join("&"));
+            }
+            "body:multipart-form" => {
+                let mut fields = vec![];
+                for (name, value, is_disabled) in block.entries() {
+                    if value.starts_with("@file(") {
+                        report.warn(format!("{}: form file field {}", location, name));
+                    } else if !is_disabled {
+                        fields.push(format!("{}={}", name, value));
+                    }
+                }
+                (body_type, body) = (BodyType::Multipart, fields.join("\n"));
+            }
+            name if name.starts_with("auth:") => {}
+            name => report.warn(format!("{}: {}", location, name)),
+        }
+    }
+
+    // graphql is sent as json, the same way bruno does
+    if is_graphql {
+        let variables = graphql_variables
+            .and_then(|variables| serde_json::from_str(&variables).ok())
+            .unwrap_or(serde_json::Value::Null);
+        body = serde_json::to_string_pretty(&serde_json::json!({
+            "query": body,
+            "variables": variables,
+        }))
+        .unwrap();
+    }
+
+    let auth_mode = method_block.get("auth");
+    let (auth_mode, auth_blocks) = if auth_mode == "inherit" {
+        let mode = find(inherited_blocks, "auth")
+            .map(|auth| auth.get("mode"))
+            .unwrap_or_default();
+        (mode, inherited_blocks)
+    } else {
+        (auth_mode, blocks)
+    };
+    let auth = find(auth_blocks, &format!("auth:{}", auth_mode));
+    match (auth_mode.as_str(), auth) {
+        ("" | "none", _) => {}
+        ("bearer", Some(auth)) => {
+            set_header(
+                &mut headers,
+                "Authorization",
+                format!("Bearer {}", auth.get("token")),
+            );
+        }
+        ("basic", Some(auth)) => {
+            set_header(
+                &mut headers,
+                "Authorization",
+                basic_auth(&auth.get("username"), &auth.get("password")),
+            );
+        }
+        ("apikey", Some(auth)) => {
+            if auth.get("placement") == "queryparams" {
+                query_parameters.insert(auth.get("key"), auth.get("value"));
+            } else {
+                set_header(&mut headers, &auth.get("key"), auth.get("value"));
+            }
+        }
+        (mode, _) => report.warn(format!("{}: {} auth", location, mode)),
+    }
+
+    Some(RequestStructure {
+        version: REQUEST_FILE_VERSION,
+        method: method_block.name.to_uppercase(),
+        url,
+        headers,
+        query_parameters,
+        body_type,
+        body,
+        options: default_options(),
+        metadata: HashMap::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::importers::collection_requests;
+
+    fn convert(raw_data: &str, collection: &str) -> RequestStructure {
+        let mut report = ImportReport::default();
+        convert_request(
+            &parse_bru(raw_data),
+            &parse_bru(collection),
+            "api/users",
+            &mut report,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_a_bru_request_with_disabled_headers() {
+        let request = convert(
+            "\
+meta {
+  name: Create user
+  seq: 1
+}
+
+post {
+  url: {{baseUrl}}/users
+  body: json
+  auth: none
+}
+
+headers {
+  Accept: application/json
+  ~X-Debug: on
+}
+
+body:json {
+  {
+    \"name\": \"ada\"
+  }
+}
+",
+            "",
+        );
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "{{baseUrl}}/users");
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(request.body, "{\n  \"name\": \"ada\"\n}");
+        assert_eq!(
+            request.headers[0],
+            Header::new("Accept", "application/json")
+        );
+        assert_eq!(request.headers[1].name, "X-Debug");
+        assert!(!request.headers[1].enabled);
+    }
+
+    #[test]
+    fn inherits_collection_headers_and_auth() {
+        let request = convert(
+            "\
+get {
+  url: {{baseUrl}}/users
+  auth: inherit
+}
+
+headers {
+  accept: text/plain
+}
+",
+            "\
+headers {
+  Accept: application/json
+  X-Team: core
+}
+
+auth {
+  mode: bearer
+}
+
+auth:bearer {
+  token: {{token}}
+}
+",
+        );
+        assert_eq!(
+            request.headers,
+            [
+                Header::new("X-Team", "core"),
+                Header::new("accept", "text/plain"),
+                Header::new("Authorization", "Bearer {{token}}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_folder_headers_auth_and_path_parameters() {
+        let dir = std::env::temp_dir().join(format!("fetched-bruno-{}", std::process::id()));
+        let bruno_dir = dir.join("bruno");
+        fs::create_dir_all(bruno_dir.join("users")).unwrap();
+        fs::write(bruno_dir.join("bruno.json"), r#"{"name": "api"}"#).unwrap();
+        fs::write(
+            bruno_dir.join("collection.bru"),
+            "headers {\n  X-Team: core\n  Accept: */*\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            bruno_dir.join("users").join("folder.bru"),
+            "\
+meta {
+  name: users
+}
+
+headers {
+  Accept: application/json
+}
+
+auth {
+  mode: bearer
+}
+
+auth:bearer {
+  token: {{token}}
+}
+",
+        )
+        .unwrap();
+        fs::write(
+            bruno_dir.join("users").join("get.bru"),
+            "\
+get {
+  url: {{baseUrl}}/users/:id/:idx
+  auth: inherit
+}
+
+params:path {
+  id: 7
+}
+",
+        )
+        .unwrap();
+        let workspace_root = dir.join("workspace");
+        fs::create_dir_all(&workspace_root).unwrap();
+        let workspace = Workspace::load(workspace_root.clone());
+
+        let (collection_name, report) = import(&bruno_dir, &workspace).unwrap();
+        let requests = collection_requests(&workspace.collections_path().join(collection_name));
+        let variables = environment::load_variables(&workspace_root, None);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(report.created, 1);
+        assert_eq!(report.warnings, Vec::<String>::new());
+        let (_, request) = &requests[0];
+        assert_eq!(request.url, "{{baseUrl}}/users/{{id}}/:idx");
+        assert_eq!(
+            request.headers,
+            [
+                Header::new("X-Team", "core"),
+                Header::new("Accept", "application/json"),
+                Header::new("Authorization", "Bearer {{token}}"),
+            ]
+        );
+        assert_eq!(variables["id"], "7");
+    }
+}
@@ -0,0 +1,413 @@
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+
+use crate::components::structs::{Header, RequestOptions, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+use crate::core::helpers;
+use crate::core::importers::{basic_auth, split_query};
+
+// flags that consume the next argument but have no place in the request model,
+// they are skipped together with their value and reported back to the user
+const UNSUPPORTED_FLAGS_WITH_VALUE: [&str; 30] = [
+    "-o",
+    "--output",
+    "-A",
+    "--user-agent",
+    "-b",
+    "--cookie",
+    "-c",
+    "--cookie-jar",
+    "-e",
+    "--referer",
+    "-T",
+    "--upload-file",
+    "-w",
+    "--write-out",
+    "-K",
+    "--config",
+    "-E",
+    "--cert",
+    "--key",
+    "--cacert",
+    "--connect-timeout",
+    "--retry",
+    "--resolve",
+    "-r",
+    "--range",
+    "--limit-rate",
+    "--interface",
+    "--url-query",
+    "-z",
+    "--time-cond",
+];
+
+pub struct CurlImport {
+    pub request: RequestStructure,
+    // flags found in the command which could not be mapped onto the request
+    pub unsupported: Vec<String>,
+}
+
+#[derive(PartialEq)]
+enum BodyKind {
+    None,
+    Data,
+    UrlEncoded,
+    Form,
+}
+
+pub fn parse(command: &str) -> Result<CurlImport> {
+    let args = tokenize(command)?;
+    let mut args = args.into_iter().peekable();
+
+    match args.peek() {
+        Some(first) if first == "curl" => {
+            args.next();
+        }
+        Some(_) => {}
+        None => bail!("Empty cURL command"),
+    }
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    // in the order of the command, a name may repeat like several `-H 'Cookie: …'`
+    let mut headers: Vec<Header> = vec![];
+    let mut data: Vec<String> = vec![];
+    let mut body_kind = BodyKind::None;
+    let mut options = RequestOptions {
+        validate_ssl: true,
+        follow_redirect: false,
+        attach_cookies: false,
+        proxy: "".to_string(),
+        timeout_ms: 0,
+    };
+    let mut unsupported: Vec<String> = vec![];
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') || arg == "-" {
+            // the first one is the url, the others are likely values of flags we do not know
+            match url {
+                Some(_) => unsupported.push(arg),
+                None => url = Some(arg),
+            }
+            continue;
+        }
+
+        // split short flags with attached values (-XPOST) and bundled switches (-kL)
+        let (flag, attached) = match arg.char_indices().nth(2) {
+            Some((index, _)) if !arg.starts_with("--") => {
+                let (flag, rest) = arg.split_at(index);
+                if takes_value(flag) {
+                    (flag.to_string(), Some(rest.to_string()))
+                } else {
+                    for switch in arg.chars().skip(1) {
+                        apply_switch(&format!("-{}", switch), &mut options, &mut unsupported);
+                    }
+                    continue;
+                }
+            }
+            _ => (arg, None),
+        };
+
+        if !takes_value(&flag) {
+            apply_switch(&flag, &mut options, &mut unsupported);
+            continue;
+        }
+
+        let value = match attached.or_else(|| args.next()) {
+            Some(value) => value,
+            None => bail!("Missing value for {}", flag),
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value.to_uppercase()),
+            "--url" => url = Some(value),
+            "-H" | "--header" => match value.split_once(':') {
+                Some((name, header_value)) => {
+                    headers.push(Header::new(name.trim(), header_value.trim()))
+                }
+                None => unsupported.push(format!("{} {}", flag, value)),
+            },
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
+                if value.starts_with('@') && flag != "--data-raw" {
+                    unsupported.push(format!("{} {}", flag, value));
+                    continue;
+                }
+                if body_kind == BodyKind::None {
+                    body_kind = BodyKind::Data;
+                }
+                data.push(value);
+            }
+            "--data-urlencode" => {
+                let encoded = match value.split_once('=') {
+                    Some(("", content)) => helpers::url_encode(content),
+                    Some((name, content)) => format!("{}={}", name, helpers::url_encode(content)),
+                    None if value.contains('@') => {
+                        unsupported.push(format!("{} {}", flag, value));
+                        continue;
+                    }
+                    None => helpers::url_encode(&value),
+                };
+                body_kind = BodyKind::UrlEncoded;
+                data.push(encoded);
+            }
+            "-F" | "--form" => {
+                body_kind = BodyKind::Form;
+                data.push(value);
+            }
+            "-u" | "--user" => {
+                let (username, password) = value.split_once(':').unwrap_or((&value, ""));
+                headers.push(Header::new("Authorization", basic_auth(username, password)));
+            }
+            "-x" | "--proxy" => options.proxy = value,
+            "-m" | "--max-time" => {
+                let seconds: f64 = value
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid value for {}: {}", flag, value))?;
+                options.timeout_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+            }
+            _ => unsupported.push(format!("{} {}", flag, value)),
+        }
+    }
+
+    let url = url.ok_or_else(|| anyhow!("No URL found in the cURL command"))?;
+    let (url, query_parameters) = split_query(&url);
+
+    let method = method.unwrap_or_else(|| {
+        if body_kind == BodyKind::None {
+            "GET".to_string()
+        } else {
+            "POST".to_string()
+        }
+    });
+
+    let is_json = headers.iter().any(|header| {
+        header.name.eq_ignore_ascii_case("content-type") && header.value.contains("json")
+    });
+    let (body_type, body) = match body_kind {
+        BodyKind::None => (BodyType::None, "".to_string()),
+        BodyKind::Form => (BodyType::Multipart, data.join("\n")),
+        BodyKind::UrlEncoded => (BodyType::Form, data.join("&")),
+        BodyKind::Data if is_json => (BodyType::Json, data.join("&")),
+        BodyKind::Data => {
+            let body = data.join("&");
+            if serde_json::from_str::<serde_json::Value>(&body).is_ok() {
+                (BodyType::Json, body)
+            } else {
+                (BodyType::Form, body)
+            }
+        }
+    };
+
+    Ok(CurlImport {
+        request: RequestStructure {
+            version: REQUEST_FILE_VERSION,
+            method,
+            url,
+            headers,
+            query_parameters,
+            body_type,
+            body,
+            options,
+            metadata: HashMap::new(),
+        },
+        unsupported,
+    })
+}
+
+fn takes_value(flag: &str) -> bool {
+    matches!(
+        flag,
+        "-X" | "--request"
+            | "--url"
+            | "-H"
+            | "--header"
+            | "-d"
+            | "--data"
+            | "--data-raw"
+            | "--data-binary"
+            | "--data-ascii"
+            | "--data-urlencode"
+            | "-F"
+            | "--form"
+            | "-u"
+            | "--user"
+            | "-x"
+            | "--proxy"
+            | "-m"
+            | "--max-time"
+    ) || UNSUPPORTED_FLAGS_WITH_VALUE.contains(&flag)
+}
+
+fn apply_switch(flag: &str, options: &mut RequestOptions, unsupported: &mut Vec<String>) {
+    match flag {
+        "-k" | "--insecure" => options.validate_ssl = false,
+        "-L" | "--location" => options.follow_redirect = true,
+        // only change what curl prints, nothing to keep in the request
+        "-s" | "--silent" | "-S" | "--show-error" | "-v" | "--verbose" | "-i" | "--include" => {}
+        _ => unsupported.push(flag.to_string()),
+    }
+}
+
+/// Splits a command line into arguments the way a POSIX shell would.
+///
+/// Handles single and double quotes, ANSI-C quoting (`$'...'`) used by browser devtools
+/// and backslash line continuations of multi-line commands.
+fn tokenize(command: &str) -> Result<Vec<String>> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => bail!("Unterminated single quote"),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => current.push('\n'),
+                            Some('t') => current.push('\t'),
+                            Some('r') => current.push('\r'),
+                            Some(c) => current.push(c),
+                            None => bail!("Unterminated quote"),
+                        },
+                        Some(c) => current.push(c),
+                        None => bail!("Unterminated quote"),
+                    }
+                }
+            }
+            '"' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => {}
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => bail!("Unterminated double quote"),
+                        },
+                        Some(c) => current.push(c),
+                        None => bail!("Unterminated double quote"),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // line continuation
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => {
+                    in_arg = true;
+                    current.push(c);
+                }
+                None => {}
+            },
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_quotes_and_line_continuations() {
+        let args = tokenize("curl 'a b' \"c \\\"d\\\"\" $'e\\nf' g\\ h \\\n  -k").unwrap();
+        assert_eq!(args, ["curl", "a b", "c \"d\"", "e\nf", "g h", "-k"]);
+        assert!(tokenize("curl 'open").is_err());
+    }
+
+    #[test]
+    fn keeps_repeated_headers_in_order() {
+        let import = parse(
+            "curl https://example.com/api?page=2 -H 'Cookie: a=1' -H 'Accept: */*' -H 'Cookie: b=2'",
+        )
+        .unwrap();
+        let request = import.request;
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.url, "https://example.com/api");
+        assert_eq!(request.query_parameters["page"], "2");
+        assert_eq!(
+            request.headers,
+            [
+                Header::new("Cookie", "a=1"),
+                Header::new("Accept", "*/*"),
+                Header::new("Cookie", "b=2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_raw_data_as_the_body() {
+        let import =
+            parse(r#"curl -XPUT https://example.com --data-raw '{"name": "@ada"}'"#).unwrap();
+        assert_eq!(import.request.method, "PUT");
+        assert_eq!(import.request.body_type, BodyType::Json);
+        assert_eq!(import.request.body, r#"{"name": "@ada"}"#);
+
+        // only --data-raw takes a leading `@` literally
+        let import = parse("curl https://example.com -d @body.json").unwrap();
+        assert_eq!(import.request.method, "GET");
+        assert_eq!(import.unsupported, ["-d @body.json"]);
+    }
+
+    #[test]
+    fn turns_user_into_basic_auth() {
+        let import = parse("curl -u ada:secret -kL https://example.com").unwrap();
+        assert_eq!(
+            import.request.headers,
+            [Header::new("Authorization", "Basic YWRhOnNlY3JldA==")]
+        );
+        assert!(!import.request.options.validate_ssl);
+        assert!(import.request.options.follow_redirect);
+    }
+
+    #[test]
+    fn reports_flags_it_does_not_know() {
+        let import = parse("curl -é https://example.com -Xé").unwrap();
+        assert_eq!(import.unsupported, ["-é"]);
+        assert_eq!(import.request.method, "É");
+
+        // the value of an unknown flag does not replace the url
+        let import =
+            parse("curl https://example.com --retry 3 -w '%{http_code}' --unknown 5").unwrap();
+        assert_eq!(import.request.url, "https://example.com");
+        assert_eq!(
+            import.unsupported,
+            ["--retry 3", "-w %{http_code}", "--unknown", "5"]
+        );
+    }
+}
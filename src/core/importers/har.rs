@@ -0,0 +1,322 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::{BodyType, RequestFileFormat};
+use crate::core::importers::{
+    collection_requests, default_options, request_file_name, split_query, unique_path, ImportReport,
+};
+use crate::core::request_parser;
+
+// headers the client sets on its own, keeping them would only go stale
+const SKIPPED_HEADERS: [&str; 3] = ["content-length", "host", "connection"];
+
+#[derive(Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Deserialize)]
+struct Log {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    request: Request,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<Pair>,
+    #[serde(rename = "queryString", default)]
+    query_string: Vec<Pair>,
+    #[serde(rename = "postData")]
+    post_data: Option<PostData>,
+}
+
+#[derive(Deserialize)]
+struct PostData {
+    #[serde(rename = "mimeType", default)]
+    mime_type: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    params: Vec<Param>,
+}
+
+#[derive(Deserialize)]
+struct Pair {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct Param {
+    name: String,
+    #[serde(default)]
+    value: String,
+    #[serde(rename = "fileName")]
+    file_name: Option<String>,
+}
+
+/// Imports the entries of a HAR 1.2 file as requests of an existing collection.
+///
+/// Only entries whose host contains `host_filter` are imported, and entries which are
+/// identical to an earlier entry or to a request already in the collection are skipped.
+pub fn import(
+    file_path: &Path,
+    collection_path: &Path,
+    host_filter: Option<&str>,
+    request_format: RequestFileFormat,
+) -> Result<ImportReport> {
+    let har: Har = serde_json::from_str(&fs::read_to_string(file_path)?)?;
+    let mut report = ImportReport::default();
+
+    // requests in folders and in files with others count as well
+    let mut seen: HashSet<String> = collection_requests(collection_path)
+        .iter()
+        .map(|(_, request)| identity(request))
+        .collect();
+    let mut duplicates = 0;
+    let mut filtered = 0;
+
+    for (index, entry) in har.log.entries.iter().enumerate() {
+        let location = format!("entry {}", index + 1);
+        if let Some(host_filter) = host_filter {
+            if !host(&entry.request.url).contains(host_filter) {
+                filtered += 1;
+                continue;
+            }
+        }
+
+        let request = convert_request(&entry.request, &location, &mut report);
+        if !seen.insert(identity(&request)) {
+            duplicates += 1;
+            continue;
+        }
+
+        request_parser::update_request(
+            &unique_path(
+                collection_path,
+                &request_parser::with_request_extension(
+                    &request_file_name(&request.method, &request.url),
+                    request_format,
+                ),
+            ),
+            request,
+        )?;
+        report.created += 1;
+    }
+
+    if duplicates > 0 {
+        report.warn(format!("{} duplicate entries were skipped", duplicates));
+    }
+    if filtered > 0 {
+        report.warn(format!("{} entries of other hosts were skipped", filtered));
+    }
+    Ok(report)
+}
+
+fn host(url: &str) -> &str {
+    let rest = url.split("://").last().unwrap_or(url);
+    rest.split(['/', '?', '#']).next().unwrap_or(rest)
+}
+
+// requests are the same when they would send the same thing
+fn identity(request: &RequestStructure) -> String {
+    let mut query: Vec<_> = request.query_parameters.iter().collect();
+    query.sort();
+    format!(
+        "{} {} {:?} {}",
+        request.method, request.url, query, request.body
+    )
+}
+
+fn convert_request(
+    request: &Request,
+    location: &str,
+    report: &mut ImportReport,
+) -> RequestStructure {
+    let (url, mut query_parameters) = split_query(&request.url);
+    // the structured query string is already decoded, prefer it over the raw url
+    if !request.query_string.is_empty() {
+        query_parameters = request
+            .query_string
+            .iter()
+            .map(|pair| (pair.name.clone(), pair.value.clone()))
+            .collect();
+    }
+
+    // http/2 pseudo headers like `:authority` are not real headers
+    let headers: Vec<Header> = request
+        .headers
+        .iter()
+        .filter(|header| !header.name.starts_with(':'))
+        .filter(|header| !SKIPPED_HEADERS.contains(&header.name.to_lowercase().as_str()))
+        .map(|header| Header::new(&header.name, &header.value))
+        .collect();
+
+    let (body_type, body) = match &request.post_data {
+        None => (BodyType::None, "".to_string()),
+        Some(post_data) if post_data.mime_type.contains("json") => {
+            (BodyType::Json, post_data.text.clone())
+        }
+        Some(post_data) if post_data.mime_type.starts_with("multipart/form-data") => {
+            let mut fields = vec![];
+            for param in &post_data.params {
+                if param.file_name.is_some() {
+                    report.warn(format!("{}: form file field {}", location, param.name));
+                } else {
+                    fields.push(format!("{}={}", param.name, param.value));
+                }
+            }
+            (BodyType::Multipart, fields.join("\n"))
+        }
+        Some(post_data)
+            if post_data
+                .mime_type
+                .starts_with("application/x-www-form-urlencoded") =>
+        {
+            let body = if post_data.text.is_empty() {
+                post_data
+                    .params
+                    .iter()
+                    .map(|param| format!("{}={}", param.name, param.value))
+                    .collect::<Vec<_>>()
+                    .join("&")
+            } else {
+                post_data.text.clone()
+            };
+            (BodyType::Form, body)
+        }
+        Some(post_data) => (BodyType::Text, post_data.text.clone()),
+    };
+
+    RequestStructure {
+        version: REQUEST_FILE_VERSION,
+        method: request.method.to_uppercase(),
+        url,
+        headers,
+        query_parameters,
+        body_type,
+        body,
+        options: default_options(),
+        metadata: HashMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const HAR: &str = r#"{"log": {"entries": [
+        {"request": {
+            "method": "get",
+            "url": "https://api.example.com/users?page=2",
+            "headers": [
+                {"name": ":authority", "value": "api.example.com"},
+                {"name": "Accept", "value": "application/json"},
+                {"name": "Host", "value": "api.example.com"}
+            ],
+            "queryString": [{"name": "page", "value": "2"}]
+        }},
+        {"request": {
+            "method": "POST",
+            "url": "https://api.example.com/users",
+            "postData": {"mimeType": "application/json", "text": "{\"name\": \"ada\"}"}
+        }},
+        {"request": {"method": "GET", "url": "https://cdn.example.org/logo.png"}},
+        {"request": {"method": "GET", "url": "https://api.example.com/users?page=2"}}
+    ]}}"#;
+
+    // a collection to import into, next to the HAR file
+    fn collection_with_har(name: &str) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("fetched-har-{}-{}", name, std::process::id()));
+        let collection_path = dir.join("api");
+        fs::create_dir_all(&collection_path).unwrap();
+        fs::write(dir.join("requests.har"), HAR).unwrap();
+        (dir, collection_path)
+    }
+
+    fn import_har(dir: &Path, collection_path: &Path) -> ImportReport {
+        import(
+            &dir.join("requests.har"),
+            collection_path,
+            Some("api.example.com"),
+            RequestFileFormat::Json,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn imports_the_entries_of_a_host_once() {
+        let (dir, collection_path) = collection_with_har("import");
+        let report = import_har(&dir, &collection_path);
+        let mut requests: Vec<_> = collection_requests(&collection_path)
+            .into_iter()
+            .map(|(_, request)| request)
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(report.created, 2);
+        assert_eq!(
+            report.warnings,
+            [
+                "1 duplicate entries were skipped",
+                "1 entries of other hosts were skipped"
+            ]
+        );
+        requests.sort_by_key(|request| request.method.clone());
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "https://api.example.com/users");
+        assert_eq!(requests[0].query_parameters["page"], "2");
+        assert_eq!(
+            requests[0].headers,
+            [Header::new("Accept", "application/json")]
+        );
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(requests[1].body_type, BodyType::Json);
+        assert_eq!(requests[1].body, r#"{"name": "ada"}"#);
+    }
+
+    #[test]
+    fn skips_requests_moved_into_folders_or_shared_files() {
+        let (dir, collection_path) = collection_with_har("reimport");
+        import_har(&dir, &collection_path);
+        let folder = collection_path.join("users");
+        fs::create_dir_all(&folder).unwrap();
+        for (path, request) in collection_requests(&collection_path) {
+            fs::remove_file(&path).unwrap();
+            match request.method.as_str() {
+                "GET" => {
+                    request_parser::update_request(&folder.join("list.json"), request).unwrap()
+                }
+                _ => fs::write(
+                    collection_path.join("shared.http"),
+                    "GET https://api.example.com/health\n\n###\n\
+                     POST https://api.example.com/users\n\
+                     Content-Type: application/json\n\n{\"name\": \"ada\"}\n",
+                )
+                .unwrap(),
+            }
+        }
+
+        let report = import_har(&dir, &collection_path);
+        let count = collection_requests(&collection_path).len();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(report.created, 0);
+        assert_eq!(report.warnings[0], "3 duplicate entries were skipped");
+        assert_eq!(count, 3);
+    }
+}
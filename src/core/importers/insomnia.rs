@@ -0,0 +1,420 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+use crate::core::environment;
+use crate::core::importers::{
+    basic_auth, default_options, sanitize_file_name, set_header, split_query, unique_path,
+    ImportReport,
+};
+use crate::core::request_parser;
+use crate::core::workspace::Workspace;
+
+#[derive(Deserialize)]
+struct Export {
+    #[serde(rename = "__export_format")]
+    export_format: u8,
+    #[serde(default)]
+    resources: Vec<Resource>,
+}
+
+// every workspace, folder, request and environment is a flat resource pointing to its parent
+#[derive(Deserialize)]
+struct Resource {
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(rename = "_type")]
+    kind: String,
+    #[serde(rename = "parentId")]
+    parent_id: Option<String>,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    method: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    headers: Vec<Pair>,
+    #[serde(default)]
+    parameters: Vec<Pair>,
+    body: Option<Body>,
+    authentication: Option<Value>,
+    data: Option<Value>,
+    #[serde(rename = "settingFollowRedirects")]
+    follow_redirects: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Body {
+    #[serde(rename = "mimeType")]
+    mime_type: Option<String>,
+    text: Option<String>,
+    #[serde(default)]
+    params: Vec<Pair>,
+}
+
+#[derive(Deserialize)]
+struct Pair {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+/// Imports an Insomnia v4 export, every workspace in it becomes a collection of `workspace`.
+///
+/// The base environment is merged into the shared variables and sub environments into
+/// named environments of the environment file.
+pub fn import(file_path: &Path, workspace: &Workspace) -> Result<(String, ImportReport)> {
+    let export: Export = serde_json::from_str(&fs::read_to_string(file_path)?)?;
+    let mut report = ImportReport::default();
+
+    if export.export_format != 4 {
+        bail!(
+            "Unsupported Insomnia export format: {}",
+            export.export_format
+        );
+    }
+
+    let mut collection_names = vec![];
+    for insomnia_workspace in export.resources.iter().filter(|r| r.kind == "workspace") {
+        let collection_path = unique_path(
+            &workspace.collections_path(),
+            &sanitize_file_name(&insomnia_workspace.name),
+        );
+        fs::create_dir_all(&collection_path)?;
+        import_children(
+            &export.resources,
+            insomnia_workspace,
+            &collection_path,
+            &insomnia_workspace.name,
+            workspace,
+            &mut report,
+        )?;
+        collection_names.push(
+            collection_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+        );
+    }
+
+    if collection_names.is_empty() {
+        bail!("No workspace found in the Insomnia export");
+    }
+
+    Ok((collection_names.join(", "), report))
+}
+
+fn import_children(
+    resources: &[Resource],
+    parent: &Resource,
+    dir: &Path,
+    location: &str,
+    workspace: &Workspace,
+    report: &mut ImportReport,
+) -> Result<()> {
+    let children = resources
+        .iter()
+        .filter(|resource| resource.parent_id.as_deref() == Some(&parent.id));
+
+    for child in children {
+        let location = format!("{}/{}", location, child.name);
+        match child.kind.as_str() {
+            "request_group" => {
+                let folder_path = unique_path(dir, &sanitize_file_name(&child.name));
+                fs::create_dir_all(&folder_path)?;
+                if child.data.as_ref().is_some_and(|data| data != &Value::Null) {
+                    report.warn(format!("{}: folder environment", location));
+                }
+                import_children(resources, child, &folder_path, &location, workspace, report)?;
+            }
+            "request" => {
+                let request = convert_request(child, &location, report);
+                request_parser::update_request(
+                    &unique_path(
+                        dir,
+                        &request_parser::with_request_extension(
+                            &sanitize_file_name(&child.name),
+                            workspace.config.request_format,
+                        ),
+                    ),
+                    request,
+                )?;
+                report.created += 1;
+            }
+            "environment" => {
+                // the base environment is a child of the workspace, the others are its children
+                let environment_name = if parent.kind == "environment" {
+                    Some(child.name.as_str())
+                } else {
+                    None
+                };
+                let variables = convert_variables(child.data.as_ref(), &location, report);
+                for conflict in
+                    environment::merge_variables(&workspace.root, environment_name, &variables)?
+                {
+                    report.warn(format!(
+                        "variable {} already exists in the environment with another value",
+                        conflict
+                    ));
+                }
+                import_children(resources, child, dir, &location, workspace, report)?;
+            }
+            "cookie_jar" | "api_spec" => {}
+            kind => report.warn(format!("{}: {}", location, kind)),
+        }
+    }
+
+    Ok(())
+}
+
+// insomnia references variables as `{{ _.name }}`, fetched as `{{name}}`
+fn convert_template(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        match rest[start..].find("}}") {
+            Some(end) => {
+                let name = rest[start + 2..start + end].trim();
+                result.push_str(&format!("{{{{{}}}}}", name.trim_start_matches("_.")));
+                rest = &rest[start + end + 2..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+fn convert_variables(
+    data: Option<&Value>,
+    location: &str,
+    report: &mut ImportReport,
+) -> Vec<(String, String)> {
+    let mut variables = vec![];
+    if let Some(Value::Object(data)) = data {
+        for (name, value) in data {
+            match value {
+                Value::String(value) => variables.push((name.clone(), convert_template(value))),
+                Value::Object(_) | Value::Array(_) => {
+                    report.warn(format!("{}: nested variable {}", location, name))
+                }
+                value => variables.push((name.clone(), value.to_string())),
+            }
+        }
+    }
+    variables
+}
+
+fn convert_pairs(
+    pairs: &[Pair],
+    kind: &str,
+    location: &str,
+    report: &mut ImportReport,
+) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .filter(|pair| {
+            if pair.disabled {
+                report.warn(format!("{}: disabled {} {}", location, kind, pair.name));
+            }
+            !pair.disabled
+        })
+        .map(|pair| (convert_template(&pair.name), convert_template(&pair.value)))
+        .collect()
+}
+
+fn convert_request(
+    resource: &Resource,
+    location: &str,
+    report: &mut ImportReport,
+) -> RequestStructure {
+    let (url, mut query_parameters) = split_query(&convert_template(&resource.url));
+    query_parameters.extend(convert_pairs(
+        &resource.parameters,
+        "query parameter",
+        location,
+        report,
+    ));
+    // disabled headers are kept turned off
+    let mut headers: Vec<Header> = resource
+        .headers
+        .iter()
+        .map(|header| Header {
+            enabled: !header.disabled,
+            ..Header::new(
+                convert_template(&header.name),
+                convert_template(&header.value),
+            )
+        })
+        .collect();
+
+    let (body_type, body) = match &resource.body {
+        Some(body) => convert_body(body, location, report),
+        None => (BodyType::None, "".to_string()),
+    };
+
+    if let Some(auth) = &resource.authentication {
+        convert_auth(auth, &mut headers, &mut query_parameters, location, report);
+    }
+
+    let mut options = default_options();
+    if resource.follow_redirects.as_deref() == Some("off") {
+        options.follow_redirect = false;
+    }
+
+    RequestStructure {
+        version: REQUEST_FILE_VERSION,
+        method: resource.method.to_uppercase(),
+        url,
+        headers,
+        query_parameters,
+        body_type,
+        body,
+        options,
+        metadata: HashMap::new(),
+    }
+}
+
+fn convert_body(body: &Body, location: &str, report: &mut ImportReport) -> (BodyType, String) {
+    let text = convert_template(body.text.as_deref().unwrap_or_default());
+    match body.mime_type.as_deref() {
+        None => (BodyType::None, "".to_string()),
+        Some("application/json") | Some("application/graphql") => (BodyType::Json, text),
+        Some("application/xml") | Some("text/xml") => (BodyType::Xml, text),
+        Some("text/plain") => (BodyType::Text, text),
+        Some("application/x-www-form-urlencoded") => {
+            let pairs: Vec<String> = convert_pairs(&body.params, "form field", location, report)
+                .into_iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            (BodyType::Form, pairs.join("&"))
+        }
+        Some("multipart/form-data") => {
+            let mut fields = vec![];
+            for field in body.params.iter().filter(|field| !field.disabled) {
+                if field.kind.as_deref() == Some("file") {
+                    report.warn(format!("{}: form file field {}", location, field.name));
+                } else {
+                    fields.push(format!("{}={}", field.name, convert_template(&field.value)));
+                }
+            }
+            (BodyType::Multipart, fields.join("\n"))
+        }
+        Some(mime_type) => {
+            report.warn(format!("{}: {} body", location, mime_type));
+            (BodyType::None, "".to_string())
+        }
+    }
+}
+
+fn convert_auth(
+    auth: &Value,
+    headers: &mut Vec<Header>,
+    query_parameters: &mut HashMap<String, String>,
+    location: &str,
+    report: &mut ImportReport,
+) {
+    let attribute =
+        |name: &str| convert_template(auth.get(name).and_then(Value::as_str).unwrap_or_default());
+    if auth.get("disabled").and_then(Value::as_bool) == Some(true) {
+        return;
+    }
+
+    match auth.get("type").and_then(Value::as_str) {
+        None | Some("none") => {}
+        Some("bearer") => {
+            let prefix = match attribute("prefix") {
+                prefix if prefix.is_empty() => "Bearer".to_string(),
+                prefix => prefix,
+            };
+            set_header(
+                headers,
+                "Authorization",
+                format!("{} {}", prefix, attribute("token")),
+            );
+        }
+        Some("basic") => {
+            set_header(
+                headers,
+                "Authorization",
+                basic_auth(&attribute("username"), &attribute("password")),
+            );
+        }
+        Some("apikey") => {
+            if attribute("addTo") == "queryParams" {
+                query_parameters.insert(attribute("key"), attribute("value"));
+            } else {
+                set_header(headers, &attribute("key"), attribute("value"));
+            }
+        }
+        Some(kind) => report.warn(format!("{}: {} auth", location, kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_insomnia_templates() {
+        assert_eq!(
+            convert_template("{{ _.baseUrl }}/users/{{id}}"),
+            "{{baseUrl}}/users/{{id}}"
+        );
+        assert_eq!(convert_template("{{ open"), "{{ open");
+    }
+
+    #[test]
+    fn converts_a_request_resource() {
+        let resource: Resource = serde_json::from_value(json!({
+            "_id": "req_1",
+            "_type": "request",
+            "parentId": "wrk_1",
+            "name": "Create user",
+            "method": "post",
+            "url": "{{ _.baseUrl }}/users",
+            "headers": [
+                { "name": "Accept", "value": "application/json" },
+                { "name": "X-Debug", "value": "on", "disabled": true },
+            ],
+            "parameters": [{ "name": "notify", "value": "true" }],
+            "body": { "mimeType": "application/json", "text": "{\"name\": \"{{ _.name }}\"}" },
+            "authentication": { "type": "bearer", "token": "{{ _.token }}" },
+            "settingFollowRedirects": "off",
+        }))
+        .unwrap();
+        let request = convert_request(&resource, "api/Create user", &mut ImportReport::default());
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "{{baseUrl}}/users");
+        assert_eq!(request.query_parameters["notify"], "true");
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(request.body, "{\"name\": \"{{name}}\"}");
+        assert!(!request.options.follow_redirect);
+        assert_eq!(request.headers.len(), 3);
+        assert!(!request.headers[1].enabled);
+        assert_eq!(
+            request.headers[2],
+            Header::new("Authorization", "Bearer {{token}}")
+        );
+    }
+}
@@ -0,0 +1,233 @@
+pub mod bruno;
+pub mod curl;
+pub mod har;
+pub mod insomnia;
+pub mod openapi;
+pub mod postman;
+
+use base64::Engine;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::components::structs::{Header, RequestOptions, RequestStructure};
+use crate::core::{formats, request_parser};
+
+// summary of an import, shown to the user once it is done
+#[derive(Default)]
+pub struct ImportReport {
+    pub created: usize,
+    // requests which already existed and were brought up to date
+    pub updated: usize,
+    // everything that could not be mapped onto fetched
+    pub warnings: Vec<String>,
+    // values of the path variables of the requests, for the environment
+    pub variables: Vec<(String, String)>,
+}
+
+impl ImportReport {
+    pub fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    pub fn to_message(&self, target: &str) -> String {
+        let mut msg = format!("Imported {} requests into {}", self.created, target);
+        if self.updated > 0 {
+            msg.push_str(&format!(", updated {}", self.updated));
+        }
+        if !self.warnings.is_empty() {
+            msg.push_str(&format!("\n\nWarnings:\n{}", self.warnings.join("\n")));
+        }
+        msg
+    }
+}
+
+// a `:name` segment of the path becomes the `{{name}}` variable, `:names` or a port are
+// left alone
+pub fn path_variable(url: &str, name: &str) -> String {
+    let segment = format!(":{}", name);
+    url.split('/')
+        .map(|part| match part == segment {
+            true => format!("{{{{{}}}}}", name),
+            false => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// move the query string of the url into the query parameters
+pub fn split_query(url: &str) -> (String, HashMap<String, String>) {
+    let url = url.split('#').next().unwrap_or(url);
+    let mut query_parameters = HashMap::new();
+
+    match url.split_once('?') {
+        Some((base, query)) => {
+            for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                query_parameters.insert(name.to_string(), value.to_string());
+            }
+            (base.to_string(), query_parameters)
+        }
+        None => (url.to_string(), query_parameters),
+    }
+}
+
+// options for requests coming from other clients, which all follow redirects by default
+pub fn default_options() -> RequestOptions {
+    RequestOptions {
+        validate_ssl: true,
+        follow_redirect: true,
+        attach_cookies: true,
+        proxy: "".to_string(),
+        timeout_ms: 0,
+    }
+}
+
+// auth and the body decide some headers, which replace a header of the same name
+pub fn set_header(headers: &mut Vec<Header>, name: &str, value: impl Into<String>) {
+    match headers
+        .iter_mut()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+    {
+        Some(header) => {
+            header.value = value.into();
+            header.enabled = true;
+        }
+        None => headers.push(Header::new(name, value)),
+    }
+}
+
+// a header the body needs, unless the request sets it itself
+pub fn default_header(headers: &mut Vec<Header>, name: &str, value: &str) {
+    if !headers
+        .iter()
+        .any(|header| header.name.eq_ignore_ascii_case(name))
+    {
+        headers.push(Header::new(name, value));
+    }
+}
+
+// credentials holding variables are kept as `Basic user:password`, they are encoded once
+// the variables are filled in
+pub fn basic_auth(username: &str, password: &str) -> String {
+    let credentials = format!("{}:{}", username, password);
+    match credentials.contains("{{") {
+        true => format!("Basic {}", credentials),
+        false => format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        ),
+    }
+}
+
+// replace the characters which are not allowed in file names
+pub fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let name = name.trim().trim_matches('.');
+
+    if name.is_empty() {
+        "request".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+// a file name like `get-users` for requests which have no name of their own
+pub fn request_file_name(method: &str, url: &str) -> String {
+    let path = url.split("://").last().unwrap_or(url);
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let segment = path
+        .split('/')
+        .skip(1)
+        .filter(|segment| !segment.is_empty())
+        .last()
+        .unwrap_or("request");
+
+    sanitize_file_name(
+        &format!("{}-{}", method, segment)
+            .to_lowercase()
+            .replace(['{', '}'], ""),
+    )
+}
+
+// a path inside dir which does not exist yet, numbered when the name is taken
+pub fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    // `users.yaml` becomes `users-1.yaml`, the extension decides how the file is read
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if formats::has_request_extension(name) => {
+            (stem, format!(".{}", extension))
+        }
+        _ => (name, "".to_string()),
+    };
+
+    let mut path = dir.join(name);
+    let mut suffix = 1;
+    while path.exists() {
+        path = dir.join(format!("{}-{}{}", stem, suffix, extension));
+        suffix += 1;
+    }
+    path
+}
+
+/// Every request of a collection with its path, the ones in folders and the ones sharing a
+/// file with others included. Files which do not load are left out.
+pub fn collection_requests(collection_path: &Path) -> Vec<(PathBuf, RequestStructure)> {
+    let mut requests = vec![];
+    let mut folders = vec![collection_path.to_path_buf()];
+    while let Some(folder) = folders.pop() {
+        for path in fs::read_dir(&folder)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            // hidden entries belong to other tools
+            if path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            {
+                continue;
+            }
+            if path.is_dir() {
+                folders.push(path);
+                continue;
+            }
+            for name in request_parser::list_requests(&path) {
+                let request_path = path.with_file_name(name);
+                if let Ok(request) = request_parser::read_request(&request_path) {
+                    requests.push((request_path, request));
+                }
+            }
+        }
+    }
+    requests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_auth_keeps_variables_readable() {
+        assert_eq!(basic_auth("ada", "secret"), "Basic YWRhOnNlY3JldA==");
+        assert_eq!(
+            basic_auth("{{user}}", "{{pass}}"),
+            "Basic {{user}}:{{pass}}"
+        );
+    }
+
+    #[test]
+    fn path_variables_replace_whole_segments() {
+        assert_eq!(
+            path_variable("http://localhost:8080/users/:id/:idx/:id_type/:id", "id"),
+            "http://localhost:8080/users/{{id}}/:idx/:id_type/{{id}}"
+        );
+    }
+}
@@ -0,0 +1,1020 @@
+use anyhow::{bail, Result};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+use crate::core::environment;
+use crate::core::formats::migration;
+use crate::core::importers::{
+    collection_requests, default_options, sanitize_file_name, set_header, unique_path, ImportReport,
+};
+use crate::core::request_parser;
+use crate::core::workspace::Workspace;
+
+const HTTP_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// the key under the request metadata which marks a request as generated
+const METADATA_KEY: &str = "openapi";
+
+// schemas can reference themselves, stop generating examples this deep
+const MAX_EXAMPLE_DEPTH: usize = 8;
+
+struct Spec {
+    document: Value,
+    is_swagger: bool,
+}
+
+/// Generates one collection of `workspace` per tag from an OpenAPI 3.x or Swagger 2.0 file.
+///
+/// Every generated request remembers the operation it came from and how it looked when it
+/// was generated, so importing the spec again only updates what the user did not edit.
+pub fn import(file_path: &Path, workspace: &Workspace) -> Result<(String, ImportReport)> {
+    let raw_data = fs::read_to_string(file_path)?;
+    let document: Value = if file_path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&raw_data)?
+    } else {
+        serde_yaml::from_str(&raw_data)?
+    };
+
+    let is_swagger = document.get("swagger").and_then(Value::as_str) == Some("2.0");
+    let is_openapi = document
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with('3'));
+    if !is_swagger && !is_openapi {
+        bail!("Only OpenAPI 3.x and Swagger 2.0 specifications are supported");
+    }
+    let spec = Spec {
+        document,
+        is_swagger,
+    };
+    let mut report = ImportReport::default();
+
+    let title = spec
+        .document
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("API")
+        .to_string();
+
+    let mut variables = vec![("baseUrl".to_string(), spec.base_url())];
+    let mut collections: Vec<String> = vec![];
+    let mut generated_requests: HashMap<PathBuf, HashMap<String, (PathBuf, RequestStructure)>> =
+        HashMap::new();
+
+    let paths = spec
+        .document
+        .get("paths")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    for (path, path_item) in &paths {
+        for method in HTTP_METHODS {
+            let operation = match path_item.get(method) {
+                Some(operation) => operation,
+                None => continue,
+            };
+            let operation_key = format!("{} {}", method.to_uppercase(), path);
+            let location = format!("{}/{}", title, operation_key);
+
+            let collection_name = sanitize_file_name(
+                operation
+                    .pointer("/tags/0")
+                    .and_then(Value::as_str)
+                    .unwrap_or(&title),
+            );
+            let collection_path = workspace.collections_path().join(&collection_name);
+            fs::create_dir_all(&collection_path)?;
+            if !collections.contains(&collection_name) {
+                collections.push(collection_name);
+            }
+
+            let (request, security_variables) =
+                spec.convert_operation(method, path, path_item, operation, &location, &mut report);
+            variables.extend(security_variables);
+
+            let existing = generated_requests
+                .entry(collection_path.clone())
+                .or_insert_with(|| find_generated_requests(&collection_path));
+            match existing.get(&operation_key) {
+                Some((request_path, current)) => {
+                    let mut merged = merge(current, &request, &location, &mut report);
+                    set_snapshot(&mut merged, &request, &operation_key);
+                    if merged != *current {
+                        request_parser::update_request(request_path, merged)?;
+                        report.updated += 1;
+                    }
+                }
+                None => {
+                    let request_name = operation
+                        .get("summary")
+                        .or(operation.get("operationId"))
+                        .and_then(Value::as_str)
+                        .unwrap_or(&operation_key)
+                        .to_string();
+                    let mut created = request.clone();
+                    set_snapshot(&mut created, &request, &operation_key);
+                    request_parser::update_request(
+                        &unique_path(
+                            &collection_path,
+                            &request_parser::with_request_extension(
+                                &sanitize_file_name(&request_name),
+                                workspace.config.request_format,
+                            ),
+                        ),
+                        created,
+                    )?;
+                    report.created += 1;
+                }
+            }
+        }
+    }
+
+    // security variables are added empty, so it is obvious what has to be filled in
+    for conflict in environment::merge_variables(&workspace.root, None, &variables)? {
+        if conflict == "baseUrl" {
+            continue;
+        }
+        report.warn(format!(
+            "variable {} already exists in the environment with another value",
+            conflict
+        ));
+    }
+
+    Ok((collections.join(", "), report))
+}
+
+impl Spec {
+    fn base_url(&self) -> String {
+        if self.is_swagger {
+            let scheme = self
+                .document
+                .pointer("/schemes/0")
+                .and_then(Value::as_str)
+                .unwrap_or("https");
+            let host = self
+                .document
+                .get("host")
+                .and_then(Value::as_str)
+                .unwrap_or("localhost");
+            let base_path = self
+                .document
+                .get("basePath")
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            format!("{}://{}{}", scheme, host, base_path.trim_end_matches('/'))
+        } else {
+            let server = self.document.pointer("/servers/0");
+            let mut url = server
+                .and_then(|server| server.get("url"))
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string();
+            // server variables are replaced by their defaults
+            if let Some(Value::Object(server_variables)) =
+                server.and_then(|server| server.get("variables"))
+            {
+                for (name, variable) in server_variables {
+                    let default = variable
+                        .get("default")
+                        .and_then(Value::as_str)
+                        .unwrap_or("");
+                    url = url.replace(&format!("{{{}}}", name), default);
+                }
+            }
+            url.trim_end_matches('/').to_string()
+        }
+    }
+
+    // follow a local `$ref` like `#/components/schemas/Pet`
+    fn resolve<'a>(&'a self, value: &'a Value) -> &'a Value {
+        let mut value = value;
+        for _ in 0..MAX_EXAMPLE_DEPTH {
+            match value.get("$ref").and_then(Value::as_str) {
+                Some(reference) => match reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| self.document.pointer(pointer))
+                {
+                    Some(resolved) => value = resolved,
+                    None => return &Value::Null,
+                },
+                None => return value,
+            }
+        }
+        value
+    }
+
+    fn convert_operation(
+        &self,
+        method: &str,
+        path: &str,
+        path_item: &Value,
+        operation: &Value,
+        location: &str,
+        report: &mut ImportReport,
+    ) -> (RequestStructure, Vec<(String, String)>) {
+        let mut url = format!("{{{{baseUrl}}}}{}", path);
+        let mut headers: Vec<Header> = vec![];
+        let mut query_parameters = HashMap::new();
+        let mut body_type = BodyType::None;
+        let mut body = "".to_string();
+        let mut form_fields: Vec<(String, String)> = vec![];
+
+        // operation parameters override the ones shared by the whole path
+        let mut parameters: Vec<&Value> = vec![];
+        for parameter in path_item
+            .get("parameters")
+            .into_iter()
+            .chain(operation.get("parameters"))
+            .filter_map(Value::as_array)
+            .flatten()
+        {
+            let parameter = self.resolve(parameter);
+            parameters.retain(|existing| {
+                existing.get("name") != parameter.get("name")
+                    || existing.get("in") != parameter.get("in")
+            });
+            parameters.push(parameter);
+        }
+
+        for parameter in parameters {
+            let name = parameter
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let variable = format!("{{{{{}}}}}", name);
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("path") => url = url.replace(&format!("{{{}}}", name), &variable),
+                Some("query") => {
+                    query_parameters.insert(name.to_string(), variable);
+                }
+                Some("header") => {
+                    set_header(&mut headers, name, variable);
+                }
+                // swagger 2.0 describes the body as a parameter
+                Some("body") => {
+                    let example = self.example(parameter.get("schema").unwrap_or(&Value::Null), 0);
+                    body_type = BodyType::Json;
+                    body = serde_json::to_string_pretty(&example).unwrap();
+                }
+                Some("formData") => {
+                    form_fields.push((name.to_string(), variable));
+                }
+                Some(kind) => report.warn(format!("{}: {} parameter {}", location, kind, name)),
+                None => {}
+            }
+        }
+
+        if self.is_swagger {
+            if !form_fields.is_empty() {
+                let consumes = operation
+                    .get("consumes")
+                    .or(self.document.get("consumes"))
+                    .map(Value::to_string)
+                    .unwrap_or_default();
+                if consumes.contains("multipart/form-data") {
+                    (body_type, body) = multipart(&form_fields);
+                } else {
+                    (body_type, body) = urlencoded(&form_fields);
+                }
+            }
+            let content_type = match body_type {
+                BodyType::Json => Some("application/json"),
+                BodyType::Form => Some("application/x-www-form-urlencoded"),
+                BodyType::Multipart => Some("multipart/form-data"),
+                _ => None,
+            };
+            if let Some(content_type) = content_type {
+                set_header(&mut headers, "Content-Type", content_type);
+            }
+        } else if let Some(request_body) = operation.get("requestBody") {
+            let request_body = self.resolve(request_body);
+            if let Some((content_type, converted)) =
+                self.convert_request_body(request_body, location, report)
+            {
+                set_header(&mut headers, "Content-Type", content_type);
+                (body_type, body) = converted;
+            }
+        }
+
+        let security_variables = self.convert_security(
+            operation,
+            &mut headers,
+            &mut query_parameters,
+            location,
+            report,
+        );
+
+        let request = RequestStructure {
+            version: REQUEST_FILE_VERSION,
+            method: method.to_uppercase(),
+            url,
+            headers,
+            query_parameters,
+            body_type,
+            body,
+            options: default_options(),
+            metadata: HashMap::new(),
+        };
+        (request, security_variables)
+    }
+
+    fn convert_request_body(
+        &self,
+        request_body: &Value,
+        location: &str,
+        report: &mut ImportReport,
+    ) -> Option<(String, (BodyType, String))> {
+        let content = request_body.get("content").and_then(Value::as_object)?;
+        let preferred = [
+            "application/json",
+            "application/x-www-form-urlencoded",
+            "multipart/form-data",
+        ];
+        let (content_type, media_type) = preferred
+            .iter()
+            .find_map(|content_type| content.get_key_value(*content_type))
+            .or_else(|| content.iter().next())?;
+
+        // an explicit example wins over one generated from the schema
+        let example = media_type
+            .get("example")
+            .cloned()
+            .or_else(|| {
+                media_type
+                    .get("examples")
+                    .and_then(Value::as_object)
+                    .and_then(|examples| examples.values().next())
+                    .map(|example| self.resolve(example))
+                    .and_then(|example| example.get("value").cloned())
+            })
+            .unwrap_or_else(|| self.example(media_type.get("schema").unwrap_or(&Value::Null), 0));
+
+        let fields = || -> Vec<(String, String)> {
+            example
+                .as_object()
+                .map(|object| {
+                    object
+                        .iter()
+                        .map(|(name, value)| (name.clone(), plain_value(value)))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let converted = match content_type.as_str() {
+            content_type if content_type.contains("json") => (
+                BodyType::Json,
+                serde_json::to_string_pretty(&example).unwrap(),
+            ),
+            "application/x-www-form-urlencoded" => urlencoded(&fields()),
+            "multipart/form-data" => multipart(&fields()),
+            content_type if content_type.starts_with("text/") || content_type.contains("xml") => {
+                (BodyType::Text, plain_value(&example))
+            }
+            content_type => {
+                report.warn(format!("{}: {} body", location, content_type));
+                return None;
+            }
+        };
+        Some((content_type.clone(), converted))
+    }
+
+    fn convert_security(
+        &self,
+        operation: &Value,
+        headers: &mut Vec<Header>,
+        query_parameters: &mut HashMap<String, String>,
+        location: &str,
+        report: &mut ImportReport,
+    ) -> Vec<(String, String)> {
+        // `security: []` on an operation turns off the global requirements
+        let requirement = operation
+            .get("security")
+            .or(self.document.get("security"))
+            .and_then(|security| security.pointer("/0"))
+            .and_then(Value::as_object)
+            .and_then(|requirement| requirement.keys().next().cloned());
+        let scheme_name = match requirement {
+            Some(scheme_name) => scheme_name,
+            None => return vec![],
+        };
+
+        let schemes_pointer = if self.is_swagger {
+            "/securityDefinitions"
+        } else {
+            "/components/securitySchemes"
+        };
+        let scheme = self
+            .document
+            .pointer(schemes_pointer)
+            .and_then(|schemes| schemes.get(&scheme_name))
+            .map(|scheme| self.resolve(scheme))
+            .unwrap_or(&Value::Null);
+        let attribute = |name: &str| {
+            scheme
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_lowercase()
+        };
+
+        let variable = match (attribute("type").as_str(), attribute("scheme").as_str()) {
+            ("http", "bearer") | ("oauth2", _) | ("openidconnect", _) => {
+                set_header(headers, "Authorization", "Bearer {{token}}");
+                "token".to_string()
+            }
+            ("http", "basic") | ("basic", _) => {
+                set_header(headers, "Authorization", "Basic {{basicCredentials}}");
+                "basicCredentials".to_string()
+            }
+            ("apikey", _) => {
+                let name = scheme
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or(&scheme_name)
+                    .to_string();
+                let variable = sanitize_variable(&scheme_name);
+                match attribute("in").as_str() {
+                    "query" => {
+                        query_parameters.insert(name, format!("{{{{{}}}}}", variable));
+                    }
+                    "header" => {
+                        set_header(headers, &name, format!("{{{{{}}}}}", variable));
+                    }
+                    kind => {
+                        report.warn(format!("{}: api key in {}", location, kind));
+                        return vec![];
+                    }
+                }
+                variable
+            }
+            (kind, _) => {
+                report.warn(format!(
+                    "{}: {} security scheme {}",
+                    location, kind, scheme_name
+                ));
+                return vec![];
+            }
+        };
+
+        vec![(variable, "".to_string())]
+    }
+
+    fn example(&self, schema: &Value, depth: usize) -> Value {
+        if depth > MAX_EXAMPLE_DEPTH {
+            return Value::Null;
+        }
+        let schema = self.resolve(schema);
+
+        for key in ["example", "default"] {
+            if let Some(example) = schema.get(key) {
+                return example.clone();
+            }
+        }
+        if let Some(first) = schema.pointer("/enum/0") {
+            return first.clone();
+        }
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            let mut merged = Map::new();
+            for schema in schemas {
+                if let Value::Object(object) = self.example(schema, depth + 1) {
+                    merged.extend(object);
+                }
+            }
+            return Value::Object(merged);
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(first) = schema.get(key).and_then(|schemas| schemas.get(0)) {
+                return self.example(first, depth + 1);
+            }
+        }
+
+        let kind = match schema.get("type") {
+            Some(Value::String(kind)) => kind.as_str(),
+            // openapi 3.1 allows a list of types like `["string", "null"]`
+            Some(Value::Array(kinds)) => kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|kind| *kind != "null")
+                .unwrap_or("null"),
+            _ if schema.get("properties").is_some() => "object",
+            _ => "",
+        };
+        match kind {
+            "object" => {
+                let mut object = Map::new();
+                if let Some(Value::Object(properties)) = schema.get("properties") {
+                    for (name, property) in properties {
+                        object.insert(name.clone(), self.example(property, depth + 1));
+                    }
+                }
+                Value::Object(object)
+            }
+            "array" => match schema.get("items") {
+                Some(items) => json!([self.example(items, depth + 1)]),
+                None => json!([]),
+            },
+            "string" => match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => json!("1970-01-01T00:00:00Z"),
+                Some("date") => json!("1970-01-01"),
+                Some("email") => json!("user@example.com"),
+                Some("uuid") => json!("00000000-0000-0000-0000-000000000000"),
+                Some("uri") | Some("url") => json!("https://example.com"),
+                _ => json!("string"),
+            },
+            "integer" => json!(0),
+            "number" => json!(0.0),
+            "boolean" => json!(true),
+            _ => Value::Null,
+        }
+    }
+}
+
+fn plain_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => "".to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn sanitize_variable(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn urlencoded(fields: &[(String, String)]) -> (BodyType, String) {
+    let pairs: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    (BodyType::Form, pairs.join("&"))
+}
+
+fn multipart(fields: &[(String, String)]) -> (BodyType, String) {
+    let pairs: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    (BodyType::Multipart, pairs.join("\n"))
+}
+
+// store the generated request next to it, it is the base of the merge on the next import
+fn set_snapshot(request: &mut RequestStructure, generated: &RequestStructure, operation_key: &str) {
+    let mut snapshot = generated.clone();
+    snapshot.metadata.clear();
+    request.metadata.insert(
+        METADATA_KEY.to_string(),
+        json!({
+            "operation": operation_key,
+            "generated": snapshot,
+        }),
+    );
+}
+
+// requests created by an earlier import of the spec, by operation. requests moved into
+// folders, or into a file with others, are still found
+fn find_generated_requests(collection_path: &Path) -> HashMap<String, (PathBuf, RequestStructure)> {
+    collection_requests(collection_path)
+        .into_iter()
+        .filter_map(|(request_path, request)| {
+            let operation_key = request
+                .metadata
+                .get(METADATA_KEY)?
+                .get("operation")?
+                .as_str()?
+                .to_string();
+            Some((operation_key, (request_path, request)))
+        })
+        .collect()
+}
+
+// three way merge between the request on disk, the one generated by the last import and
+// the newly generated one, fields the user edited since the last import are kept
+fn merge(
+    current: &RequestStructure,
+    generated: &RequestStructure,
+    location: &str,
+    report: &mut ImportReport,
+) -> RequestStructure {
+    let previous: RequestStructure = current
+        .metadata
+        .get(METADATA_KEY)
+        .and_then(|metadata| metadata.get("generated"))
+        .and_then(|snapshot| migration::load(snapshot.clone()).ok())
+        .unwrap_or_else(|| current.clone());
+
+    let mut merged = current.clone();
+    let mut kept = vec![];
+
+    merged.method = merge_field(
+        "method",
+        &current.method,
+        &previous.method,
+        &generated.method,
+        &mut kept,
+    );
+    merged.url = merge_field(
+        "url",
+        &current.url,
+        &previous.url,
+        &generated.url,
+        &mut kept,
+    );
+    merged.body_type = merge_field(
+        "body type",
+        &current.body_type,
+        &previous.body_type,
+        &generated.body_type,
+        &mut kept,
+    );
+    merged.body = merge_field(
+        "body",
+        &current.body,
+        &previous.body,
+        &generated.body,
+        &mut kept,
+    );
+    merged.headers = merge_headers(
+        &current.headers,
+        &previous.headers,
+        &generated.headers,
+        &mut kept,
+    );
+    merged.query_parameters = merge_map(
+        &current.query_parameters,
+        &previous.query_parameters,
+        &generated.query_parameters,
+        "query parameter",
+        &mut kept,
+    );
+
+    for field in kept {
+        report.warn(format!("{}: kept your changes to {}", location, field));
+    }
+
+    merged
+}
+
+fn merge_field<T: PartialEq + Clone>(
+    name: &str,
+    current: &T,
+    previous: &T,
+    generated: &T,
+    kept: &mut Vec<String>,
+) -> T {
+    if current == previous {
+        generated.clone()
+    } else {
+        if previous != generated {
+            kept.push(name.to_string());
+        }
+        current.clone()
+    }
+}
+
+fn merge_map(
+    current: &HashMap<String, String>,
+    previous: &HashMap<String, String>,
+    generated: &HashMap<String, String>,
+    kind: &str,
+    kept: &mut Vec<String>,
+) -> HashMap<String, String> {
+    let mut merged = current.clone();
+
+    for (name, value) in generated {
+        match (current.get(name), previous.get(name)) {
+            (current_value, previous_value) if current_value == previous_value => {
+                merged.insert(name.clone(), value.clone());
+            }
+            (_, previous_value) => {
+                if previous_value != Some(value) {
+                    kept.push(format!("{} {}", kind, name));
+                }
+            }
+        }
+    }
+    // entries removed from the spec go away unless the user changed them
+    for (name, value) in previous {
+        if !generated.contains_key(name) && current.get(name) == Some(value) {
+            merged.remove(name);
+        }
+    }
+
+    merged
+}
+
+// a name which repeats is told apart by how often it came before, like `Accept (2)`
+fn keyed_headers(headers: &[Header]) -> Vec<(String, &Header)> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    headers
+        .iter()
+        .map(|header| {
+            let count = seen.entry(&header.name).or_default();
+            *count += 1;
+            let key = match *count {
+                1 => header.name.clone(),
+                count => format!("{} ({})", header.name, count),
+            };
+            (key, header)
+        })
+        .collect()
+}
+
+// headers are merged one by one like a map, the headers on disk keep their order, toggles
+// and descriptions
+fn merge_headers(
+    current: &[Header],
+    previous: &[Header],
+    generated: &[Header],
+    kept: &mut Vec<String>,
+) -> Vec<Header> {
+    let as_map = |headers: &[Header]| -> HashMap<String, String> {
+        keyed_headers(headers)
+            .into_iter()
+            .map(|(key, header)| (key, header.value.clone()))
+            .collect()
+    };
+    let values = merge_map(
+        &as_map(current),
+        &as_map(previous),
+        &as_map(generated),
+        "header",
+        kept,
+    );
+
+    let mut merged: Vec<(String, Header)> = keyed_headers(current)
+        .into_iter()
+        .filter_map(|(key, header)| {
+            let value = values.get(&key)?.clone();
+            Some((
+                key,
+                Header {
+                    value,
+                    ..header.clone()
+                },
+            ))
+        })
+        .collect();
+    // new headers of the spec come after, in the order of the spec
+    for (key, header) in keyed_headers(generated) {
+        if let Some(value) = values.get(&key) {
+            if !merged.iter().any(|(merged_key, _)| *merged_key == key) {
+                merged.push((key, Header::new(&header.name, value)));
+            }
+        }
+    }
+    merged.into_iter().map(|(_, header)| header).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPERATION: &str = "GET /users";
+
+    fn generated(url: &str, headers: &[(&str, &str)]) -> RequestStructure {
+        RequestStructure {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| Header::new(*name, *value))
+                .collect(),
+            options: default_options(),
+            ..RequestStructure::default()
+        }
+    }
+
+    // the request as the last import left it on disk
+    fn imported(request: &RequestStructure) -> RequestStructure {
+        let mut current = request.clone();
+        set_snapshot(&mut current, request, OPERATION);
+        current
+    }
+
+    fn merge_into(current: &RequestStructure, generated: &RequestStructure) -> RequestStructure {
+        merge(current, generated, OPERATION, &mut ImportReport::default())
+    }
+
+    #[test]
+    fn keeps_unchanged_requests_as_they_are() {
+        let request = generated("{{baseUrl}}/users", &[("Accept", "application/json")]);
+        let current = imported(&request);
+        assert_eq!(merge_into(&current, &request), current);
+    }
+
+    #[test]
+    fn keeps_local_edits_when_the_spec_did_not_change_them() {
+        let request = generated("{{baseUrl}}/users", &[("Accept", "application/json")]);
+        let mut current = imported(&request);
+        current.url = "{{baseUrl}}/people".to_string();
+        current.headers[0].value = "text/plain".to_string();
+
+        let merged = merge_into(&current, &request);
+        assert_eq!(merged.url, "{{baseUrl}}/people");
+        assert_eq!(merged.headers, [Header::new("Accept", "text/plain")]);
+    }
+
+    #[test]
+    fn takes_upstream_changes_of_untouched_fields() {
+        let request = generated("{{baseUrl}}/users", &[("Accept", "application/json")]);
+        let mut current = imported(&request);
+        current.headers[0].value = "text/plain".to_string();
+        let upstream = generated(
+            "{{baseUrl}}/v2/users",
+            &[("Accept", "application/xml"), ("X-Version", "2")],
+        );
+
+        let mut report = ImportReport::default();
+        let merged = merge(&current, &upstream, OPERATION, &mut report);
+        assert_eq!(merged.url, "{{baseUrl}}/v2/users");
+        // both changed the header, the local value wins and the report says so
+        assert_eq!(
+            merged.headers,
+            [
+                Header::new("Accept", "text/plain"),
+                Header::new("X-Version", "2")
+            ]
+        );
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn drops_what_was_removed_upstream_unless_it_was_edited() {
+        let request = generated(
+            "{{baseUrl}}/users",
+            &[("X-Old", "1"), ("X-Edited", "1"), ("Accept", "*/*")],
+        );
+        let mut current = imported(&request);
+        current.headers[1].value = "2".to_string();
+        let upstream = generated("{{baseUrl}}/users", &[("Accept", "*/*")]);
+
+        let merged = merge_into(&current, &upstream);
+        assert_eq!(
+            merged.headers,
+            [Header::new("X-Edited", "2"), Header::new("Accept", "*/*")]
+        );
+    }
+
+    #[test]
+    fn keeps_each_of_repeated_headers() {
+        let request = generated(
+            "{{baseUrl}}/users",
+            &[("Accept", "application/json"), ("Accept", "text/csv")],
+        );
+        let mut current = imported(&request);
+        current.headers[1].value = "text/plain".to_string();
+
+        let merged = merge_into(&current, &request);
+        assert_eq!(
+            merged.headers,
+            [
+                Header::new("Accept", "application/json"),
+                Header::new("Accept", "text/plain")
+            ]
+        );
+
+        let upstream = generated(
+            "{{baseUrl}}/users",
+            &[("Accept", "application/xml"), ("Accept", "text/csv")],
+        );
+        let merged = merge_into(&current, &upstream);
+        assert_eq!(
+            merged.headers,
+            [
+                Header::new("Accept", "application/xml"),
+                Header::new("Accept", "text/plain")
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_generated_requests_in_folders() {
+        let collection_path =
+            std::env::temp_dir().join(format!("fetched-openapi-{}", std::process::id()));
+        let folder = collection_path.join("users");
+        fs::create_dir_all(&folder).unwrap();
+        let request = imported(&generated("{{baseUrl}}/users", &[]));
+        request_parser::update_request(&folder.join("list.json"), request.clone()).unwrap();
+
+        let found = find_generated_requests(&collection_path);
+        fs::remove_dir_all(&collection_path).unwrap();
+        assert_eq!(found[OPERATION], (folder.join("list.json"), request));
+    }
+
+    fn convert(
+        document: Value,
+        path: &str,
+        method: &str,
+    ) -> (RequestStructure, Vec<(String, String)>) {
+        let spec = Spec {
+            is_swagger: document.get("swagger").is_some(),
+            document,
+        };
+        let path_item = &spec.document["paths"][path];
+        spec.convert_operation(
+            method,
+            path,
+            path_item,
+            &path_item[method],
+            OPERATION,
+            &mut ImportReport::default(),
+        )
+    }
+
+    #[test]
+    fn converts_an_openapi_operation() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [{ "name": "id", "in": "path" }],
+                    "put": {
+                        "parameters": [
+                            { "name": "notify", "in": "query" },
+                            { "name": "X-Request-Id", "in": "header" },
+                        ],
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/User" },
+                                },
+                            },
+                        },
+                        "security": [{ "key": [] }],
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string", "example": "ada" } },
+                    },
+                },
+                "securitySchemes": {
+                    "key": { "type": "apiKey", "in": "header", "name": "X-Api-Key" },
+                },
+            },
+        });
+        let (request, variables) = convert(document, "/users/{id}", "put");
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.url, "{{baseUrl}}/users/{{id}}");
+        assert_eq!(request.query_parameters["notify"], "{{notify}}");
+        assert_eq!(
+            request.headers,
+            [
+                Header::new("X-Request-Id", "{{X-Request-Id}}"),
+                Header::new("Content-Type", "application/json"),
+                Header::new("X-Api-Key", "{{key}}"),
+            ]
+        );
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(
+            serde_json::from_str::<Value>(&request.body).unwrap(),
+            json!({ "name": "ada" })
+        );
+        assert_eq!(variables, [("key".to_string(), "".to_string())]);
+    }
+
+    #[test]
+    fn converts_a_swagger_body_parameter() {
+        let document = json!({
+            "swagger": "2.0",
+            "host": "api.example.com",
+            "basePath": "/v1/",
+            "paths": {
+                "/users": {
+                    "post": {
+                        "parameters": [{
+                            "name": "user",
+                            "in": "body",
+                            "schema": { "type": "object", "properties": { "age": { "type": "integer" } } },
+                        }],
+                    },
+                },
+            },
+        });
+        let spec = Spec {
+            document: document.clone(),
+            is_swagger: true,
+        };
+        assert_eq!(spec.base_url(), "https://api.example.com/v1");
+
+        let (request, _) = convert(document, "/users", "post");
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(
+            request.headers,
+            [Header::new("Content-Type", "application/json")]
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::components::structs::{Header, RequestStructure};
+use crate::constants::REQUEST_FILE_VERSION;
+use crate::core::enums::BodyType;
+use crate::core::environment;
+use crate::core::importers::ImportReport;
+use crate::core::importers::{
+    basic_auth, default_header, default_options, path_variable, sanitize_file_name, set_header,
+    split_query, unique_path,
+};
+use crate::core::request_parser;
+use crate::core::workspace::Workspace;
+
+// only the parts of the v2.1 schema fetched knows about, everything else is reported
+#[derive(Deserialize)]
+struct Collection {
+    info: Info,
+    #[serde(default)]
+    item: Vec<Item>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+    #[serde(default)]
+    event: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct Info {
+    name: String,
+    schema: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    #[serde(default)]
+    name: String,
+    // folders have children, requests have a request
+    item: Option<Vec<Item>>,
+    request: Option<Value>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    event: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default = "default_method")]
+    method: String,
+    #[serde(default)]
+    header: Vec<KeyValue>,
+    url: Option<Value>,
+    body: Option<Body>,
+    auth: Option<Auth>,
+}
+
+#[derive(Deserialize)]
+struct Body {
+    #[serde(default)]
+    mode: String,
+    #[serde(default)]
+    raw: String,
+    #[serde(default)]
+    urlencoded: Vec<KeyValue>,
+    #[serde(default)]
+    formdata: Vec<KeyValue>,
+    graphql: Option<Value>,
+    options: Option<Value>,
+    #[serde(default)]
+    disabled: bool,
+}
+
+#[derive(Deserialize)]
+struct KeyValue {
+    #[serde(default)]
+    key: String,
+    value: Option<Value>,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+struct Auth {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(flatten)]
+    attributes: HashMap<String, Value>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+impl KeyValue {
+    fn value(&self) -> String {
+        match &self.value {
+            Some(Value::String(value)) => value.clone(),
+            Some(Value::Null) | None => "".to_string(),
+            Some(value) => value.to_string(),
+        }
+    }
+}
+
+impl Auth {
+    // auth attributes are stored as a list of `{ key, value }` under the auth type
+    fn attribute(&self, name: &str) -> String {
+        self.attributes
+            .get(&self.kind)
+            .and_then(|attributes| serde_json::from_value::<Vec<KeyValue>>(attributes.clone()).ok())
+            .and_then(|attributes| {
+                attributes
+                    .into_iter()
+                    .find(|attribute| attribute.key == name)
+            })
+            .map(|attribute| attribute.value())
+            .unwrap_or_default()
+    }
+}
+
+/// Imports a Postman Collection v2.1 file as a collection directory of `workspace`.
+///
+/// Folders become nested directories, collection variables are merged into the
+/// environment file and everything without a counterpart in fetched, like scripts,
+/// ends up in the returned report.
+pub fn import(file_path: &Path, workspace: &Workspace) -> Result<(String, ImportReport)> {
+    let collection: Collection = serde_json::from_str(&fs::read_to_string(file_path)?)?;
+    let mut report = ImportReport::default();
+
+    if let Some(schema) = &collection.info.schema {
+        if !schema.contains("v2.1") && !schema.contains("v2.0") {
+            bail!("Unsupported Postman schema: {}", schema);
+        }
+    }
+
+    let collection_path = unique_path(
+        &workspace.collections_path(),
+        &sanitize_file_name(&collection.info.name),
+    );
+    fs::create_dir_all(&collection_path)?;
+
+    if !collection.event.is_empty() {
+        report.warn(format!("{}: collection scripts", collection.info.name));
+    }
+
+    import_items(
+        &collection.item,
+        &collection_path,
+        collection.auth.as_ref(),
+        &collection.info.name,
+        workspace,
+        &mut report,
+    )?;
+
+    let mut variables: Vec<(String, String)> = collection
+        .variable
+        .iter()
+        .filter(|variable| !variable.key.is_empty())
+        .map(|variable| (variable.key.clone(), variable.value()))
+        .collect();
+    variables.append(&mut report.variables);
+    for conflict in environment::merge_variables(&workspace.root, None, &variables)? {
+        report.warn(format!(
+            "variable {} already exists in the environment with another value",
+            conflict
+        ));
+    }
+
+    let collection_name = collection_path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    Ok((collection_name, report))
+}
+
+fn import_items(
+    items: &[Item],
+    dir: &Path,
+    inherited_auth: Option<&Auth>,
+    location: &str,
+    workspace: &Workspace,
+    report: &mut ImportReport,
+) -> Result<()> {
+    for item in items {
+        let location = format!("{}/{}", location, item.name);
+        let auth = item.auth.as_ref().or(inherited_auth);
+
+        if !item.event.is_empty() {
+            report.warn(format!("{}: scripts", location));
+        }
+
+        if let Some(children) = &item.item {
+            let folder_path = unique_path(dir, &sanitize_file_name(&item.name));
+            fs::create_dir_all(&folder_path)?;
+            import_items(children, &folder_path, auth, &location, workspace, report)?;
+        } else if let Some(request) = &item.request {
+            let request = convert_request(request, auth, &location, report)?;
+            let request_path = unique_path(
+                dir,
+                &request_parser::with_request_extension(
+                    &sanitize_file_name(&item.name),
+                    workspace.config.request_format,
+                ),
+            );
+            request_parser::update_request(&request_path, request)?;
+            report.created += 1;
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_request(
+    request: &Value,
+    inherited_auth: Option<&Auth>,
+    location: &str,
+    report: &mut ImportReport,
+) -> Result<RequestStructure> {
+    // a request can be written as just its url
+    let request: Request = match request {
+        Value::String(url) => Request {
+            method: default_method(),
+            header: vec![],
+            url: Some(Value::String(url.clone())),
+            body: None,
+            auth: None,
+        },
+        request => serde_json::from_value(request.clone())?,
+    };
+
+    // disabled headers are kept turned off
+    let mut headers: Vec<Header> = request
+        .header
+        .iter()
+        .map(|header| Header {
+            enabled: !header.disabled,
+            ..Header::new(header.key.clone(), header.value())
+        })
+        .collect();
+
+    let (url, mut query_parameters) = convert_url(request.url.as_ref(), location, report);
+
+    let (body_type, body) = match &request.body {
+        Some(body) if !body.disabled => convert_body(body, &mut headers, location, report),
+        _ => (BodyType::None, "".to_string()),
+    };
+
+    if let Some(auth) = request.auth.as_ref().or(inherited_auth) {
+        convert_auth(auth, &mut headers, &mut query_parameters, location, report);
+    }
+
+    Ok(RequestStructure {
+        version: REQUEST_FILE_VERSION,
+        method: request.method.to_uppercase(),
+        url,
+        headers,
+        query_parameters,
+        body_type,
+        body,
+        options: default_options(),
+        metadata: HashMap::new(),
+    })
+}
+
+fn convert_url(
+    url: Option<&Value>,
+    location: &str,
+    report: &mut ImportReport,
+) -> (String, HashMap<String, String>) {
+    match url {
+        Some(Value::String(raw)) => split_query(raw),
+        Some(Value::Object(url)) => {
+            let raw = url.get("raw").and_then(Value::as_str).unwrap_or_default();
+            let (base, mut query_parameters) = split_query(raw);
+
+            // the structured query list knows about disabled parameters, prefer it
+            if let Some(query) = url.get("query") {
+                query_parameters.clear();
+                for parameter in
+                    serde_json::from_value::<Vec<KeyValue>>(query.clone()).unwrap_or_default()
+                {
+                    if parameter.disabled {
+                        report.warn(format!(
+                            "{}: disabled query parameter {}",
+                            location, parameter.key
+                        ));
+                    } else {
+                        query_parameters.insert(parameter.key.clone(), parameter.value());
+                    }
+                }
+            }
+
+            // path variables like `:id` become regular variables
+            let mut base = base;
+            if let Some(variables) = url.get("variable") {
+                for variable in
+                    serde_json::from_value::<Vec<KeyValue>>(variables.clone()).unwrap_or_default()
+                {
+                    base = path_variable(&base, &variable.key);
+                    // the value it had goes into the environment
+                    let value = variable.value();
+                    if !value.is_empty() {
+                        report.variables.push((variable.key.clone(), value));
+                    }
+                }
+            }
+
+            (base, query_parameters)
+        }
+        _ => {
+            report.warn(format!("{}: missing url", location));
+            ("".to_string(), HashMap::new())
+        }
+    }
+}
+
+fn convert_body(
+    body: &Body,
+    headers: &mut Vec<Header>,
+    location: &str,
+    report: &mut ImportReport,
+) -> (BodyType, String) {
+    match body.mode.as_str() {
+        "raw" => {
+            let language = body
+                .options
+                .as_ref()
+                .and_then(|options| options.pointer("/raw/language"))
+                .and_then(Value::as_str)
+                .unwrap_or("text");
+            (BodyType::from_name(language), body.raw.clone())
+        }
+        "urlencoded" => {
+            let pairs: Vec<String> = body
+                .urlencoded
+                .iter()
+                .filter(|pair| !pair.disabled)
+                .map(|pair| format!("{}={}", pair.key, pair.value()))
+                .collect();
+            (BodyType::Form, pairs.join("&"))
+        }
+        "formdata" => {
+            let mut fields = vec![];
+            for field in body.formdata.iter().filter(|field| !field.disabled) {
+                if field.kind.as_deref() == Some("file") {
+                    report.warn(format!("{}: form file field {}", location, field.key));
+                } else {
+                    fields.push(format!("{}={}", field.key, field.value()));
+                }
+            }
+            (BodyType::Multipart, fields.join("\n"))
+        }
+        "graphql" => {
+            let graphql = body.graphql.clone().unwrap_or_default();
+            let variables = match graphql.get("variables") {
+                Some(Value::String(variables)) if !variables.trim().is_empty() => {
+                    serde_json::from_str(variables).unwrap_or(Value::Null)
+                }
+                _ => Value::Null,
+            };
+            let payload = serde_json::json!({
+                "query": graphql.get("query").cloned().unwrap_or_default(),
+                "variables": variables,
+            });
+            default_header(headers, "Content-Type", "application/json");
+            (
+                BodyType::Json,
+                serde_json::to_string_pretty(&payload).unwrap(),
+            )
+        }
+        mode => {
+            report.warn(format!("{}: {} body", location, mode));
+            (BodyType::None, "".to_string())
+        }
+    }
+}
+
+fn convert_auth(
+    auth: &Auth,
+    headers: &mut Vec<Header>,
+    query_parameters: &mut HashMap<String, String>,
+    location: &str,
+    report: &mut ImportReport,
+) {
+    match auth.kind.as_str() {
+        "noauth" => {}
+        "bearer" => {
+            set_header(
+                headers,
+                "Authorization",
+                format!("Bearer {}", auth.attribute("token")),
+            );
+        }
+        "basic" => {
+            set_header(
+                headers,
+                "Authorization",
+                basic_auth(&auth.attribute("username"), &auth.attribute("password")),
+            );
+        }
+        "apikey" => {
+            let (name, value) = (auth.attribute("key"), auth.attribute("value"));
+            if auth.attribute("in") == "query" {
+                query_parameters.insert(name, value);
+            } else {
+                set_header(headers, &name, value);
+            }
+        }
+        kind => report.warn(format!("{}: {} auth", location, kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn convert(request: Value, inherited_auth: Option<&Auth>) -> (RequestStructure, ImportReport) {
+        let mut report = ImportReport::default();
+        let request = convert_request(&request, inherited_auth, "api/users", &mut report).unwrap();
+        (request, report)
+    }
+
+    #[test]
+    fn converts_a_request_with_disabled_headers() {
+        let (request, report) = convert(
+            json!({
+                "method": "post",
+                "header": [
+                    { "key": "Accept", "value": "application/json" },
+                    { "key": "X-Debug", "value": "on", "disabled": true },
+                ],
+                "url": {
+                    "raw": "{{baseUrl}}/users/:id?page=2&draft=1",
+                    "query": [
+                        { "key": "page", "value": "2" },
+                        { "key": "draft", "value": "1", "disabled": true },
+                    ],
+                    "variable": [{ "key": "id", "value": "7" }],
+                },
+                "body": {
+                    "mode": "raw",
+                    "raw": "{\"name\": \"ada\"}",
+                    "options": { "raw": { "language": "json" } },
+                },
+            }),
+            None,
+        );
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "{{baseUrl}}/users/{{id}}");
+        assert_eq!(
+            request.query_parameters,
+            HashMap::from([("page".to_string(), "2".to_string())])
+        );
+        assert_eq!(
+            request.headers[0],
+            Header::new("Accept", "application/json")
+        );
+        assert!(!request.headers[1].enabled);
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(
+            report.warnings,
+            ["api/users: disabled query parameter draft"]
+        );
+        assert_eq!(report.variables, [("id".to_string(), "7".to_string())]);
+    }
+
+    #[test]
+    fn keeps_variables_of_inherited_basic_auth_readable() {
+        let auth: Auth = serde_json::from_value(json!({
+            "type": "basic",
+            "basic": [
+                { "key": "username", "value": "{{user}}" },
+                { "key": "password", "value": "{{pass}}" },
+            ],
+        }))
+        .unwrap();
+        let (request, _) = convert(json!("https://example.com"), Some(&auth));
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.headers,
+            [Header::new("Authorization", "Basic {{user}}:{{pass}}")]
+        );
+    }
+
+    #[test]
+    fn sends_graphql_as_json() {
+        let (request, _) = convert(
+            json!({
+                "url": "https://example.com/graphql",
+                "body": { "mode": "graphql", "graphql": { "query": "{ me { id } }" } },
+            }),
+            None,
+        );
+        assert_eq!(request.body_type, BodyType::Json);
+        assert_eq!(
+            serde_json::from_str::<Value>(&request.body).unwrap(),
+            json!({ "query": "{ me { id } }", "variables": null })
+        );
+        assert_eq!(
+            request.headers,
+            [Header::new("Content-Type", "application/json")]
+        );
+    }
+}
@@ -0,0 +1,666 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+use crate::constants::{CONFIG_PATH, KEYMAP_FILE};
+use crate::core::enums::{Action, FocusedWindow, InputMode, Popup};
+use crate::core::helpers;
+
+// the bindings used unless the keymap of the user changes them
+const DEFAULT_KEYMAP: &str = include_str!("../../keymap.toml");
+
+// binding a key to this frees it
+const UNBOUND: &str = "none";
+
+// the popups are a mode of their own, their tables are named after the popups
+const MODES: [&str; 4] = ["normal", "insert", "control", "popup"];
+const POPUP_MODE: &str = "popup";
+const WINDOWS: [&str; 4] = ["collections", "request", "response", "editor"];
+
+// keys written by name in the keymap file, besides single characters
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("esc", KeyCode::Esc),
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+/// A key along with the modifiers held while pressing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        let mut modifiers = key.modifiers;
+        // the case of a letter already tells whether shift was held
+        if let KeyCode::Char(_) = key.code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        KeyChord {
+            code: key.code,
+            modifiers,
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(character) => write!(f, "{}", character),
+            KeyCode::F(number) => write!(f, "f{}", number),
+            code => match NAMED_KEYS.iter().find(|(_, named)| *named == code) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{:?}", code),
+            },
+        }
+    }
+}
+
+/// Writes a sequence of keys the way the keymap file does, like `g g`.
+pub fn format_keys(keys: &[KeyChord]) -> String {
+    keys.iter()
+        .map(KeyChord::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// a single key like `a`, `G`, `ctrl+c`, `shift+tab` or `f5`
+fn parse_key(token: &str) -> Result<KeyChord, String> {
+    let (prefix, name) = match token.strip_suffix("++") {
+        Some(prefix) => (prefix, "+"),
+        None if token == "+" => ("", "+"),
+        None => token.rsplit_once('+').unwrap_or(("", token)),
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for modifier in prefix.split('+').filter(|modifier| !modifier.is_empty()) {
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(format!("unknown modifier {:?}", modifier)),
+        };
+    }
+
+    let mut characters = name.chars();
+    let code = match (characters.next(), characters.next()) {
+        (Some(character), None) => KeyCode::Char(character),
+        _ if name.eq_ignore_ascii_case("space") => KeyCode::Char(' '),
+        _ => match NAMED_KEYS
+            .iter()
+            .find(|(named, _)| named.eq_ignore_ascii_case(name))
+        {
+            Some((_, code)) => *code,
+            None => match name.to_lowercase().strip_prefix('f').map(str::parse) {
+                Some(Ok(number)) => KeyCode::F(number),
+                _ => return Err(format!("unknown key {:?}", name)),
+            },
+        },
+    };
+
+    // shifted letters are written in upper case, shift+tab is its own key
+    let code = match code {
+        KeyCode::Char(character) if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::Char(character.to_ascii_uppercase())
+        }
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::BackTab
+        }
+        code => code,
+    };
+    Ok(KeyChord { code, modifiers })
+}
+
+/// Parses the keys of a binding, separated by spaces like `g g`. A word of letters which
+/// is not the name of a key, like `gg`, is read as one key per letter.
+pub fn parse_keys(keys: &str) -> Result<Vec<KeyChord>, String> {
+    let mut sequence = vec![];
+    for token in keys.split_whitespace() {
+        match parse_key(token) {
+            Ok(chord) => sequence.push(chord),
+            Err(_) if token.chars().all(char::is_alphanumeric) => {
+                sequence.extend(token.chars().map(|character| KeyChord {
+                    code: KeyCode::Char(character),
+                    modifiers: KeyModifiers::NONE,
+                }))
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    if sequence.is_empty() {
+        return Err("no keys given".to_string());
+    }
+    Ok(sequence)
+}
+
+/// What the keys pressed so far do.
+#[derive(Debug, PartialEq)]
+pub enum Resolution {
+    Action(Action),
+    // the keys start a longer binding, wait for the next one
+    Pending,
+    Unbound,
+}
+
+/// Key bindings per input mode, and per focused window within a mode. The bindings of
+/// the window win over the ones of the whole mode. Popups are bound the same way, under
+/// the `popup` mode.
+#[derive(Default)]
+pub struct Keymap {
+    scopes: HashMap<String, HashMap<Vec<KeyChord>, Action>>,
+}
+
+// like `normal.collections`, then `normal`
+fn scope_names(mode: &InputMode, window: &FocusedWindow) -> [String; 2] {
+    let mode = mode.to_string().to_lowercase();
+    [
+        format!("{}.{}", mode, window.to_string().to_lowercase()),
+        mode,
+    ]
+}
+
+// like `popup.trash`, then `popup`
+fn popup_scope_names(popup: Popup) -> [String; 2] {
+    [format!("{}.{}", POPUP_MODE, popup), POPUP_MODE.to_string()]
+}
+
+// the tables a mode may have besides its own bindings
+fn is_window_of(mode: &str, name: &str) -> bool {
+    match mode {
+        POPUP_MODE => Popup::iter().any(|popup| popup.to_string() == name),
+        _ => WINDOWS.contains(&name),
+    }
+}
+
+fn action_index(action: Action) -> usize {
+    Action::iter()
+        .position(|other| other == action)
+        .unwrap_or_default()
+}
+
+impl Keymap {
+    pub fn resolve(
+        &self,
+        mode: &InputMode,
+        window: &FocusedWindow,
+        keys: &[KeyChord],
+    ) -> Resolution {
+        self.resolve_in(scope_names(mode, window), keys)
+    }
+
+    /// What the keys pressed so far do while `popup` is shown.
+    pub fn resolve_popup(&self, popup: Popup, keys: &[KeyChord]) -> Resolution {
+        self.resolve_in(popup_scope_names(popup), keys)
+    }
+
+    fn resolve_in(&self, scopes: [String; 2], keys: &[KeyChord]) -> Resolution {
+        for scope in scopes {
+            let Some(bindings) = self.scopes.get(&scope) else {
+                continue;
+            };
+            if let Some(action) = bindings.get(keys) {
+                return Resolution::Action(*action);
+            }
+            if bindings
+                .keys()
+                .any(|binding| binding.len() > keys.len() && binding.starts_with(keys))
+            {
+                return Resolution::Pending;
+            }
+        }
+        Resolution::Unbound
+    }
+
+    /// The bindings usable in `mode` while `window` is focused, in the order the actions
+    /// are declared. A key bound for the window wins over the same key bound for the mode.
+    pub fn active_bindings(
+        &self,
+        mode: &InputMode,
+        window: &FocusedWindow,
+    ) -> Vec<(Vec<KeyChord>, Action)> {
+        self.active_in(scope_names(mode, window))
+    }
+
+    /// The bindings usable while `popup` is shown, like [`Keymap::active_bindings`].
+    pub fn popup_bindings(&self, popup: Popup) -> Vec<(Vec<KeyChord>, Action)> {
+        self.active_in(popup_scope_names(popup))
+    }
+
+    /// A hint like `[y] copy  [q] close` for the border of a popup, with the shortest key
+    /// bound to each of the actions. Labels whose actions have no key are left out.
+    pub fn popup_hint(&self, popup: Popup, hints: &[(&[Action], &str)]) -> String {
+        let bindings = self.popup_bindings(popup);
+        hints
+            .iter()
+            .filter_map(|(actions, label)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .filter_map(|action| {
+                        bindings
+                            .iter()
+                            .filter(|(_, bound)| bound == action)
+                            .map(|(keys, _)| format_keys(keys))
+                            .min_by_key(|keys| (keys.len(), keys.clone()))
+                    })
+                    .collect();
+                match keys.is_empty() {
+                    true => None,
+                    false => Some(format!("[{}] {}", keys.join("/"), label)),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    fn active_in(&self, scopes: [String; 2]) -> Vec<(Vec<KeyChord>, Action)> {
+        let mut active: Vec<(Vec<KeyChord>, Action)> = vec![];
+        for scope in scopes {
+            for (keys, action) in self.scopes.get(&scope).into_iter().flatten() {
+                if !active.iter().any(|(bound, _)| bound == keys) {
+                    active.push((keys.clone(), *action));
+                }
+            }
+        }
+        active.sort_by_key(|(keys, action)| (action_index(*action), format_keys(keys)));
+        active
+    }
+
+    /// Every binding of an action, with the scope it applies to like `normal.collections`.
+    pub fn bindings_of(&self, action: Action) -> Vec<(String, Vec<KeyChord>)> {
+        let mut bindings: Vec<_> = self
+            .scopes
+            .iter()
+            .flat_map(|(scope, bindings)| {
+                bindings
+                    .iter()
+                    .filter(|(_, bound)| **bound == action)
+                    .map(|(keys, _)| (scope.clone(), keys.clone()))
+            })
+            .collect();
+        bindings.sort_by_key(|(scope, keys)| (scope.clone(), format_keys(keys)));
+        bindings
+    }
+
+    // the bindings of a keymap file, `source` names the file in errors
+    fn add_layer(&mut self, layer: toml::Table, source: &str, errors: &mut Vec<String>) {
+        // `gg` and `g g` are the same keys, so a file can bind them twice
+        let mut bound = HashMap::new();
+        for (mode, value) in layer {
+            let Some(table) = value.as_table().filter(|_| MODES.contains(&mode.as_str())) else {
+                errors.push(format!("{}: unknown mode [{}]", source, mode));
+                continue;
+            };
+
+            for (keys, value) in table {
+                match value {
+                    toml::Value::Table(window_table) if is_window_of(&mode, keys) => {
+                        let scope = format!("{}.{}", mode, keys);
+                        for (keys, value) in window_table {
+                            self.bind(&scope, keys, value, source, &mut bound, errors);
+                        }
+                    }
+                    toml::Value::Table(_) => {
+                        errors.push(format!("{}: unknown window [{}.{}]", source, mode, keys))
+                    }
+                    value => self.bind(&mode, keys, value, source, &mut bound, errors),
+                }
+            }
+        }
+    }
+
+    fn bind(
+        &mut self,
+        scope: &str,
+        keys: &str,
+        value: &toml::Value,
+        source: &str,
+        bound: &mut HashMap<(String, Vec<KeyChord>), String>,
+        errors: &mut Vec<String>,
+    ) {
+        let sequence = match parse_keys(keys) {
+            Ok(sequence) => sequence,
+            Err(err) => {
+                errors.push(format!("{}: [{}] {:?}: {}", source, scope, keys, err));
+                return;
+            }
+        };
+        if let Some(other) = bound.insert((scope.to_string(), sequence.clone()), keys.to_string()) {
+            errors.push(format!(
+                "{}: [{}] {:?} and {:?} are the same keys",
+                source, scope, other, keys
+            ));
+        }
+        let bindings = self.scopes.entry(scope.to_string()).or_default();
+        match value.as_str() {
+            Some(UNBOUND) => {
+                bindings.remove(&sequence);
+            }
+            Some(name) => match Action::from_str(name) {
+                Ok(action) => {
+                    bindings.insert(sequence, action);
+                }
+                Err(_) => errors.push(format!(
+                    "{}: [{}] {:?}: unknown action {:?}",
+                    source, scope, keys, name
+                )),
+            },
+            None => errors.push(format!(
+                "{}: [{}] {:?}: the action has to be a string",
+                source, scope, keys
+            )),
+        }
+    }
+
+    // a binding which starts another one of the same mode keeps it from ever being used
+    fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = vec![];
+        for (scope, bindings) in &self.scopes {
+            let mode = scope.split('.').next().unwrap_or(scope);
+            let mut others: Vec<_> = bindings.iter().collect();
+            if let Some(mode_bindings) = self.scopes.get(mode).filter(|_| mode != scope) {
+                others.extend(mode_bindings);
+            }
+
+            for (keys, action) in bindings {
+                for (other_keys, other_action) in &others {
+                    if other_keys.len() > keys.len() && other_keys.starts_with(keys) {
+                        conflicts.push(format!(
+                            "[{}] {:?} ({}) hides {:?} ({})",
+                            scope,
+                            format_keys(keys),
+                            action,
+                            format_keys(other_keys),
+                            other_action
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts.sort();
+        conflicts
+    }
+}
+
+/// Loads the default bindings and the changes of the user to them. Bindings which can not
+/// be read and bindings hiding each other are returned as errors.
+pub fn load_keymap() -> (Keymap, Vec<String>) {
+    let mut keymap = Keymap::default();
+    let mut errors = vec![];
+    if let Ok(layer) = toml::from_str(DEFAULT_KEYMAP) {
+        keymap.add_layer(layer, "default keymap", &mut errors);
+    }
+
+    let file = helpers::expand_path(CONFIG_PATH).join(KEYMAP_FILE);
+    if let Ok(raw_data) = fs::read_to_string(&file) {
+        match toml::from_str(&raw_data) {
+            Ok(layer) => keymap.add_layer(layer, &file.display().to_string(), &mut errors),
+            Err(err) => errors.push(format!("{}: {}", file.display(), err.message())),
+        }
+    }
+
+    errors.extend(keymap.conflicts());
+    (keymap, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord { code, modifiers }
+    }
+
+    fn keymap(layers: &[&str]) -> (Keymap, Vec<String>) {
+        let mut keymap = Keymap::default();
+        let mut errors = vec![];
+        for layer in layers {
+            keymap.add_layer(toml::from_str(layer).unwrap(), "test", &mut errors);
+        }
+        (keymap, errors)
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            parse_keys("ctrl+c").unwrap(),
+            [chord(KeyCode::Char('c'), KeyModifiers::CONTROL)]
+        );
+        assert_eq!(
+            parse_keys("shift+a").unwrap(),
+            [chord(KeyCode::Char('A'), KeyModifiers::NONE)]
+        );
+        assert_eq!(
+            parse_keys("shift+tab").unwrap(),
+            [chord(KeyCode::BackTab, KeyModifiers::NONE)]
+        );
+        assert_eq!(
+            parse_keys("ctrl++ f5 space").unwrap(),
+            [
+                chord(KeyCode::Char('+'), KeyModifiers::CONTROL),
+                chord(KeyCode::F(5), KeyModifiers::NONE),
+                chord(KeyCode::Char(' '), KeyModifiers::NONE),
+            ]
+        );
+        // a word which is no key name is one key per letter
+        assert_eq!(parse_keys("gg").unwrap(), parse_keys("g g").unwrap());
+        assert_eq!(format_keys(&parse_keys("g T").unwrap()), "g T");
+
+        assert!(parse_keys("").is_err());
+        assert!(parse_keys("hyper+a").is_err());
+        assert!(parse_keys("ctrl+nokey").is_err());
+    }
+
+    #[test]
+    fn window_bindings_win_over_mode_bindings() {
+        let (keymap, errors) = keymap(&[r#"
+            [normal]
+            "d" = "delete"
+            "g g" = "move-first"
+
+            [normal.response]
+            "d" = "none"
+            "y" = "export"
+            "#]);
+        assert_eq!(errors, Vec::<String>::new());
+
+        let d = parse_keys("d").unwrap();
+        let g = parse_keys("g").unwrap();
+        let y = parse_keys("y").unwrap();
+        let collections = FocusedWindow::Collections;
+        assert_eq!(
+            keymap.resolve(&InputMode::Normal, &collections, &d),
+            Resolution::Action(Action::Delete)
+        );
+        assert_eq!(
+            keymap.resolve(&InputMode::Normal, &collections, &g),
+            Resolution::Pending
+        );
+        assert_eq!(
+            keymap.resolve(&InputMode::Normal, &collections, &y),
+            Resolution::Unbound
+        );
+        // unbinding in the window only frees the key of the window
+        assert_eq!(
+            keymap.resolve(&InputMode::Normal, &FocusedWindow::Response, &d),
+            Resolution::Action(Action::Delete)
+        );
+        assert_eq!(
+            keymap.resolve(&InputMode::Normal, &FocusedWindow::Response, &y),
+            Resolution::Action(Action::Export)
+        );
+    }
+
+    #[test]
+    fn the_user_keymap_changes_single_bindings() {
+        let (keymap, errors) = keymap(&[
+            r#"
+            [normal]
+            "d" = "delete"
+            "u" = "undo"
+            "#,
+            r#"
+            [normal]
+            "x" = "delete"
+            "u" = "none"
+            "#,
+        ]);
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(
+            keymap.bindings_of(Action::Delete),
+            [
+                ("normal".to_string(), parse_keys("d").unwrap()),
+                ("normal".to_string(), parse_keys("x").unwrap()),
+            ]
+        );
+        assert_eq!(keymap.bindings_of(Action::Undo), []);
+    }
+
+    #[test]
+    fn reports_bad_bindings() {
+        let (_, errors) = keymap(&[r#"
+            "x" = "quit"
+
+            [visual]
+            "v" = "quit"
+
+            [normal]
+            "gg" = "move-first"
+            "g g" = "move-last"
+            "q" = "fly"
+            "w" = 1
+            "hyper+e" = "quit"
+
+            [normal.sidebar]
+            "s" = "send"
+            "#]);
+        let mut errors = errors;
+        errors.sort();
+        assert_eq!(
+            errors,
+            [
+                r#"test: [normal] "g g" and "gg" are the same keys"#,
+                r#"test: [normal] "hyper+e": unknown modifier "hyper""#,
+                r#"test: [normal] "q": unknown action "fly""#,
+                r#"test: [normal] "w": the action has to be a string"#,
+                "test: unknown mode [visual]",
+                "test: unknown mode [x]",
+                "test: unknown window [normal.sidebar]",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_bindings_hiding_others() {
+        let (keymap, _) = keymap(&[r#"
+            [normal]
+            "g g" = "move-first"
+
+            [normal.request]
+            "g" = "send"
+            "#]);
+        assert_eq!(
+            keymap.conflicts(),
+            [r#"[normal.request] "g" (send) hides "g g" (move-first)"#]
+        );
+    }
+
+    #[test]
+    fn the_default_keymap_has_no_errors() {
+        let (_, errors) = keymap(&[DEFAULT_KEYMAP]);
+        assert_eq!(errors, Vec::<String>::new());
+        let (keymap, _) = keymap(&[DEFAULT_KEYMAP]);
+        assert_eq!(keymap.conflicts(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn popups_have_bindings_of_their_own() {
+        let (keymap, errors) = keymap(&[
+            DEFAULT_KEYMAP,
+            r#"
+            [popup]
+            "x" = "cancel"
+
+            [popup.trash]
+            "r" = "none"
+            "u" = "restore"
+            "#,
+        ]);
+        assert_eq!(errors, Vec::<String>::new());
+
+        let resolve = |popup, keys| keymap.resolve_popup(popup, &parse_keys(keys).unwrap());
+        assert_eq!(
+            resolve(Popup::Trash, "u"),
+            Resolution::Action(Action::Restore)
+        );
+        assert_eq!(resolve(Popup::Trash, "r"), Resolution::Unbound);
+        assert_eq!(
+            resolve(Popup::Trash, "enter"),
+            Resolution::Action(Action::Restore)
+        );
+        // the bindings of every popup apply unless the popup binds the key itself
+        assert_eq!(
+            resolve(Popup::Method, "x"),
+            Resolution::Action(Action::Cancel)
+        );
+        assert_eq!(
+            resolve(Popup::Method, "enter"),
+            Resolution::Action(Action::Submit)
+        );
+        assert_eq!(
+            resolve(Popup::Export, "enter"),
+            Resolution::Action(Action::Copy)
+        );
+        // letters are left for the search of the history
+        assert_eq!(resolve(Popup::History, "q"), Resolution::Unbound);
+        // and the popups are not bound in the other modes
+        assert_eq!(
+            keymap.resolve(
+                &InputMode::Normal,
+                &FocusedWindow::Collections,
+                &parse_keys("u").unwrap()
+            ),
+            Resolution::Action(Action::Undo)
+        );
+
+        assert_eq!(
+            keymap.popup_hint(
+                Popup::Trash,
+                &[
+                    (&[Action::Restore], "restore"),
+                    (&[Action::PurgeAll], "purge all"),
+                    (&[Action::Copy], "copy"),
+                ]
+            ),
+            "[u] restore  [D] purge all"
+        );
+    }
+}
@@ -1,7 +1,20 @@
 pub mod app;
+pub mod commands;
+pub mod config;
 pub mod enums;
+pub mod environment;
+pub mod exporters;
+pub mod external_editor;
+pub mod formats;
 pub mod handler;
+pub mod headers;
 pub mod helpers;
+pub mod history;
+pub mod http;
+pub mod importers;
+pub mod keymap;
 pub mod request_parser;
+pub mod syntax;
 pub mod theme;
-
+pub mod trash;
+pub mod workspace;
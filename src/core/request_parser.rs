@@ -1,21 +1,199 @@
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::components::structs::RequestStructure;
+use crate::core::enums::RequestFileFormat;
+use crate::core::formats;
 
-pub fn read_json_file(file_path: &PathBuf) -> serde_json::Result<RequestStructure> {
-    let raw_json_data = fs::read_to_string(file_path).unwrap();
+// `api.http#2` addresses the second request of a file which holds several requests
+pub fn split_request_path(path: &Path) -> (PathBuf, usize) {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
 
-    let json_data = serde_json::from_str(&raw_json_data)?;
+    if let Some((name, number)) = file_name.rsplit_once('#') {
+        let file_path = path.with_file_name(name);
+        if let Ok(number) = number.parse::<usize>() {
+            if number > 0 && formats::for_path(&file_path).is_multi_request() {
+                return (file_path, number - 1);
+            }
+        }
+    }
+    (path.to_path_buf(), 0)
+}
+
+/// The names listed for a request file, one per request it holds.
+pub fn list_requests(file_path: &Path) -> Vec<String> {
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let format = formats::for_path(file_path);
+    if !format.is_multi_request() {
+        return vec![file_name];
+    }
+
+    let count = fs::read_to_string(file_path)
+        .ok()
+        .and_then(|raw_data| format.parse(&raw_data).ok())
+        .map(|requests| requests.len())
+        .unwrap_or_default();
+    if count <= 1 {
+        return vec![file_name];
+    }
+    (1..=count)
+        .map(|number| format!("{}#{}", file_name, number))
+        .collect()
+}
+
+/// Why a request could not be loaded, with the position of the problem when the format
+/// parser knows it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl LoadError {
+    /// Describes the error with the path of the file relative to `folder`, or to the working
+    /// directory for files outside of it.
+    pub fn describe(&self, folder: &Path) -> String {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let file = self
+            .file
+            .strip_prefix(folder)
+            .or_else(|_| self.file.strip_prefix(&cwd))
+            .unwrap_or(&self.file);
+        let mut description = file.display().to_string();
+        if let Some(line) = self.line {
+            description.push_str(&format!(":{}", line));
+            if let Some(column) = self.column {
+                description.push_str(&format!(":{}", column));
+            }
+        }
+        format!("{}: {}", description, self.reason)
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        write!(f, "{}", self.describe(&cwd))
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+pub fn load_request(path: &Path) -> Result<RequestStructure, LoadError> {
+    let (file_path, index) = split_request_path(path);
+    let error = |line, column, reason: String| LoadError {
+        file: file_path.clone(),
+        line,
+        column,
+        reason,
+    };
 
-    Ok(json_data)
+    let raw_data =
+        fs::read_to_string(&file_path).map_err(|err| error(None, None, err.to_string()))?;
+    let requests = formats::for_path(&file_path)
+        .parse(&raw_data)
+        .map_err(|err| {
+            let (line, column, reason) = describe_parse_error(&err, &raw_data);
+            error(line, column, reason)
+        })?;
+
+    requests
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| error(None, None, format!("No request {} in the file", index + 1)))
+}
+
+pub fn read_request(path: &Path) -> anyhow::Result<RequestStructure> {
+    Ok(load_request(path)?)
+}
+
+// the 1 based line and column of a parse error and its message without the position
+fn describe_parse_error(
+    err: &anyhow::Error,
+    raw_data: &str,
+) -> (Option<usize>, Option<usize>, String) {
+    // json and yaml append ` at line 3 column 5` to their messages
+    let strip_position = |message: String| match message.split_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message,
+    };
+
+    if let Some(err) = err.downcast_ref::<serde_json::Error>() {
+        // errors found after parsing, like a missing field, have no position
+        let position = (err.line() > 0).then_some((err.line(), err.column()));
+        (
+            position.map(|(line, _)| line),
+            position.map(|(_, column)| column),
+            strip_position(err.to_string()),
+        )
+    } else if let Some(err) = err.downcast_ref::<serde_yaml::Error>() {
+        (
+            err.location().map(|location| location.line()),
+            err.location().map(|location| location.column()),
+            strip_position(err.to_string()),
+        )
+    } else if let Some(err) = err.downcast_ref::<toml::de::Error>() {
+        let position = err.span().map(|span| {
+            let before = &raw_data[..span.start.min(raw_data.len())];
+            let line = before.matches('\n').count() + 1;
+            let column = before.len() - before.rfind('\n').map(|index| index + 1).unwrap_or(0) + 1;
+            (line, column)
+        });
+        (
+            position.map(|(line, _)| line),
+            position.map(|(_, column)| column),
+            err.message().to_string(),
+        )
+    } else {
+        (None, None, err.to_string())
+    }
 }
 
-pub fn update_json_file(file_path: &PathBuf, data: RequestStructure) -> anyhow::Result<()> {
-    let string_data = serde_json::to_string_pretty(&data)?;
+pub fn update_request(path: &Path, data: RequestStructure) -> anyhow::Result<()> {
+    let (file_path, index) = split_request_path(path);
+    let raw_data = fs::read_to_string(&file_path).unwrap_or_default();
 
-    //let mut file = fs::File::open(file_path)?;
-    fs::write(file_path, string_data)?;
-    crate::core::helpers::logger(data);
+    fs::write(
+        &file_path,
+        formats::for_path(&file_path).update(&raw_data, index, &data)?,
+    )?;
     Ok(())
 }
+
+// remove the request, and the file with it once it holds nothing else
+pub fn delete_request(path: &Path) -> anyhow::Result<()> {
+    let (file_path, index) = split_request_path(path);
+    let format = formats::for_path(&file_path);
+
+    if format.is_multi_request() {
+        let raw_data = format.remove(&fs::read_to_string(&file_path)?, index)?;
+        if !raw_data.trim().is_empty() {
+            fs::write(&file_path, raw_data)?;
+            return Ok(());
+        }
+    }
+    fs::remove_file(file_path)?;
+    Ok(())
+}
+
+/// The file name of a new request, stored in `format` unless the name already has a request
+/// file extension.
+pub fn with_request_extension(name: &str, format: RequestFileFormat) -> String {
+    if formats::has_request_extension(name) {
+        return name.to_string();
+    }
+    match format {
+        RequestFileFormat::Json => name.to_string(),
+        RequestFileFormat::Yaml => format!("{}.yaml", name),
+        RequestFileFormat::Toml => format!("{}.toml", name),
+    }
+}
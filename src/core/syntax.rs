@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use crate::core::theme::SyntaxColors;
+
+// the style of a JSON string, keys are the strings followed by a colon
+fn string_style(rest: &str, colors: &SyntaxColors) -> Style {
+    let color = match rest.trim_start().starts_with(':') {
+        true => colors.key,
+        false => colors.string,
+    };
+    Style::new().fg(Color::from(color))
+}
+
+// a string token starting at `start`, up to its closing quote or the end of the line
+fn string_end(line: &str, start: usize) -> usize {
+    let mut escaped = false;
+    for (index, character) in line[start + 1..].char_indices() {
+        match character {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return start + 1 + index + 1,
+            _ => escaped = false,
+        }
+    }
+    line.len()
+}
+
+fn token_end(line: &str, start: usize, is_part: impl Fn(char) -> bool) -> usize {
+    line[start..]
+        .char_indices()
+        .find(|(_, character)| !is_part(*character))
+        .map(|(index, _)| start + index)
+        .unwrap_or(line.len())
+}
+
+/// Colors the keys, values and punctuation of a JSON text line by line. Text which is not
+/// valid JSON is still shown, only the tokens which can be recognized are colored.
+pub fn highlight_json<'a>(text: &'a str, colors: &SyntaxColors) -> Vec<Line<'a>> {
+    text.lines()
+        .map(|line| {
+            let mut spans = vec![];
+            let mut start = 0;
+            while let Some(character) = line[start..].chars().next() {
+                let (end, style) = match character {
+                    '"' => {
+                        let end = string_end(line, start);
+                        (end, string_style(&line[end..], colors))
+                    }
+                    '-' | '0'..='9' => (
+                        token_end(line, start, |character| {
+                            character.is_ascii_digit() || "+-.eE".contains(character)
+                        }),
+                        Style::new().fg(Color::from(colors.number)),
+                    ),
+                    '{' | '}' | '[' | ']' | ',' | ':' => {
+                        (start + 1, Style::new().fg(Color::from(colors.punctuation)))
+                    }
+                    _ if character.is_alphabetic() => {
+                        let end = token_end(line, start, char::is_alphanumeric);
+                        let style = match &line[start..end] {
+                            "true" | "false" => Style::new().fg(Color::from(colors.boolean)),
+                            "null" => Style::new().fg(Color::from(colors.null)),
+                            _ => Style::new(),
+                        };
+                        (end, style)
+                    }
+                    _ => (start + character.len_utf8(), Style::new()),
+                };
+                spans.push(Span::styled(&line[start..end], style));
+                start = end;
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// The `{{name}}` variables of a text with their names, as ranges of characters.
+pub fn find_variables(text: &str) -> Vec<(Range<usize>, String)> {
+    let characters: Vec<char> = text.chars().collect();
+    let mut variables = vec![];
+    let mut start = 0;
+    while start + 1 < characters.len() {
+        if characters[start..start + 2] != ['{', '{'] {
+            start += 1;
+            continue;
+        }
+        let Some(length) = characters[start + 2..]
+            .windows(2)
+            .position(|pair| pair == ['}', '}'])
+        else {
+            break;
+        };
+        let end = start + 2 + length + 2;
+        let name: String = characters[start + 2..end - 2].iter().collect();
+        variables.push((start..end, name.trim().to_string()));
+        start = end;
+    }
+    variables
+}
+
+/// Colors the variables of a text, the ones without a value in `unknown`.
+pub fn highlight_variables<'a>(
+    text: &str,
+    variables: &HashMap<String, String>,
+    known: Style,
+    unknown: Style,
+) -> Line<'a> {
+    let characters: Vec<char> = text.chars().collect();
+    let mut spans = vec![];
+    let mut start = 0;
+    for (range, name) in find_variables(text) {
+        spans.push(Span::raw(
+            characters[start..range.start].iter().collect::<String>(),
+        ));
+        let style = match variables.contains_key(&name) {
+            true => known,
+            false => unknown,
+        };
+        spans.push(Span::styled(
+            characters[range.clone()].iter().collect::<String>(),
+            style,
+        ));
+        start = range.end;
+    }
+    spans.push(Span::raw(characters[start..].iter().collect::<String>()));
+    Line::from(spans)
+}
@@ -2,29 +2,285 @@ use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, BorderType};
 use serde::Deserialize;
 use std::fs;
-use toml::de::Error;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
 
+use crate::constants::{CONFIG_PATH, THEME_FILE};
 use crate::core::enums::{ThemeState, WidgetType};
+use crate::core::helpers;
+
+// the themes shipped with fetched, the first one is used unless a theme file picks another
+const BUILTIN_THEMES: [(&str, &str); 4] = [
+    ("dark", include_str!("../../themes/dark.toml")),
+    ("light", include_str!("../../themes/light.toml")),
+    ("gruvbox", include_str!("../../themes/gruvbox.toml")),
+    (
+        "solarized-light",
+        include_str!("../../themes/solarized-light.toml"),
+    ),
+];
+
+// user themes picked by name live next to the theme file, like `themes/nord.toml`
+const THEMES_DIR: &str = "themes";
 
 #[derive(Default, Debug, Clone, Deserialize)]
 pub struct Config {
     pub focus: Colors,
     pub normal: Colors,
+    pub selection: SelectionColors,
+    pub popup: PopupColors,
+    pub error: PopupColors,
+    pub status: StatusColors,
+    pub syntax: SyntaxColors,
+    pub methods: MethodColors,
 }
 
 #[derive(Default, Debug, Deserialize, Clone)]
 pub struct Colors {
-    pub foreground: u32,
-    pub background: u32,
-    pub highlight: u32,
-    pub border: u32,
+    pub foreground: ThemeColor,
+    pub background: ThemeColor,
+    pub highlight: ThemeColor,
+    pub border: ThemeColor,
+}
+
+#[derive(Default, Debug, Deserialize, Clone)]
+pub struct SelectionColors {
+    pub foreground: ThemeColor,
+    pub background: ThemeColor,
 }
 
-pub fn get_theme() -> Result<Config, Error> {
-    let config: Config = toml::from_str(&fs::read_to_string("theme.toml").expect(""))
-        .expect("Failed to parse theme");
+#[derive(Default, Debug, Deserialize, Clone)]
+pub struct PopupColors {
+    pub foreground: ThemeColor,
+    pub border: ThemeColor,
+}
 
-    Ok(config)
+// response status codes by their class
+#[derive(Default, Debug, Deserialize, Clone)]
+pub struct StatusColors {
+    pub informational: ThemeColor,
+    pub success: ThemeColor,
+    pub redirect: ThemeColor,
+    pub client_error: ThemeColor,
+    pub server_error: ThemeColor,
+}
+
+// tokens of JSON bodies
+#[derive(Default, Debug, Deserialize, Clone)]
+pub struct SyntaxColors {
+    pub key: ThemeColor,
+    pub string: ThemeColor,
+    pub number: ThemeColor,
+    pub boolean: ThemeColor,
+    pub null: ThemeColor,
+    pub punctuation: ThemeColor,
+}
+
+// the badges of the request methods
+#[derive(Default, Debug, Deserialize, Clone)]
+pub struct MethodColors {
+    pub get: ThemeColor,
+    pub post: ThemeColor,
+    pub put: ThemeColor,
+    pub patch: ThemeColor,
+    pub delete: ThemeColor,
+    pub other: ThemeColor,
+}
+
+/// A color of a theme file, written as `#RRGGBB`, a name like `lightblue` or a number
+/// like `0x00FF9C73`.
+#[derive(Default, Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "RawColor")]
+pub struct ThemeColor(Color);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawColor {
+    Number(u32),
+    Name(String),
+}
+
+impl TryFrom<RawColor> for ThemeColor {
+    type Error = String;
+
+    fn try_from(raw: RawColor) -> Result<Self, Self::Error> {
+        match raw {
+            RawColor::Number(number) => Ok(ThemeColor(Color::from_u32(number))),
+            RawColor::Name(name) => Color::from_str(&name)
+                .map(ThemeColor)
+                .map_err(|_| format!("unknown color {:?}, use #RRGGBB or a color name", name)),
+        }
+    }
+}
+
+impl From<ThemeColor> for Color {
+    fn from(color: ThemeColor) -> Self {
+        color.0
+    }
+}
+
+impl Config {
+    pub fn method_color(&self, method: &str) -> Color {
+        match method.to_uppercase().as_str() {
+            "GET" => self.methods.get,
+            "POST" => self.methods.post,
+            "PUT" => self.methods.put,
+            "PATCH" => self.methods.patch,
+            "DELETE" => self.methods.delete,
+            _ => self.methods.other,
+        }
+        .into()
+    }
+
+    pub fn status_color(&self, status: u16) -> Color {
+        match status {
+            100..=199 => self.status.informational,
+            200..=299 => self.status.success,
+            300..=399 => self.status.redirect,
+            400..=499 => self.status.client_error,
+            _ => self.status.server_error,
+        }
+        .into()
+    }
+}
+
+/// The names `:theme` accepts, the built-in themes and the ones of the user.
+pub fn theme_names() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_THEMES
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+    for entry in fs::read_dir(user_themes_path())
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        let path = entry.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+fn user_themes_path() -> PathBuf {
+    helpers::expand_path(CONFIG_PATH).join(THEMES_DIR)
+}
+
+/// The files a theme is read from, the ones of the user and the workspace overriding single
+/// colors of the configured theme, or the file of a theme picked by name.
+pub fn theme_files(name: Option<&str>, workspace_root: &Path) -> Vec<PathBuf> {
+    match name {
+        Some(name) => vec![user_themes_path().join(format!("{}.toml", name))],
+        None => vec![
+            helpers::expand_path(CONFIG_PATH).join(THEME_FILE),
+            workspace_root.join(THEME_FILE),
+        ],
+    }
+}
+
+/// When the theme files were last changed, to reload the theme once one of them changes.
+pub fn modified_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| {
+            fs::metadata(file)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+// tables are merged key by key, so a theme file only has to set the colors it changes
+fn merge(base: &mut toml::Table, layer: toml::Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(layer)) => merge(base, layer),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn read_layer(file: &Path, errors: &mut Vec<String>) -> Option<toml::Table> {
+    let raw_data = fs::read_to_string(file).ok()?;
+    match toml::from_str(&raw_data) {
+        Ok(layer) => Some(layer),
+        Err(err) => {
+            let line = err
+                .span()
+                .map(|span| {
+                    raw_data[..span.start.min(raw_data.len())]
+                        .matches('\n')
+                        .count()
+                        + 1
+                })
+                .unwrap_or(1);
+            errors.push(format!("{}:{}: {}", file.display(), line, err.message()));
+            None
+        }
+    }
+}
+
+fn builtin_theme(name: &str) -> Option<toml::Table> {
+    BUILTIN_THEMES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .and_then(|(_, raw_data)| toml::from_str(raw_data).ok())
+}
+
+/// Loads a theme picked by name, or the configured one when there is no name. The theme
+/// files of the user and the workspace are layered over the built-in theme they pick with
+/// `theme = "<name>"`, the dark one by default. Files which can not be parsed are skipped
+/// and reported in the returned errors.
+pub fn load_theme(name: Option<&str>, workspace_root: &Path) -> (Config, Vec<String>) {
+    let mut errors = vec![];
+    let layers: Vec<toml::Table> = theme_files(name, workspace_root)
+        .iter()
+        .filter_map(|file| read_layer(file, &mut errors))
+        .collect();
+
+    let base_name = match name {
+        Some(name) => name.to_string(),
+        None => layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.get("theme")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| BUILTIN_THEMES[0].0.to_string()),
+    };
+    // every theme starts from the default one, so missing colors still have one
+    let mut table = builtin_theme(BUILTIN_THEMES[0].0).unwrap_or_default();
+    match builtin_theme(&base_name) {
+        Some(base) => merge(&mut table, base),
+        None if name.is_none() || layers.is_empty() => {
+            errors.push(format!("Unknown theme {}", base_name))
+        }
+        None => {}
+    }
+    for layer in layers {
+        merge(&mut table, layer);
+    }
+
+    match table.try_into() {
+        Ok(config) => (config, errors),
+        Err(err) => {
+            errors.push(format!("Invalid theme: {}", err.message()));
+            let default = builtin_theme(BUILTIN_THEMES[0].0).unwrap_or_default();
+            (default.try_into().unwrap_or_default(), errors)
+        }
+    }
 }
 
 pub fn set_border_style<'a>(
@@ -35,13 +291,13 @@ pub fn set_border_style<'a>(
         Ok(Block::bordered()
             .border_type(BorderType::Rounded)
             .title_alignment(ratatui::layout::Alignment::Left)
-            .style(Style::new().fg(Color::from_u32(theme.focus.highlight))))
+            .style(Style::new().fg(Color::from(theme.focus.highlight))))
     } else {
         Ok(Block::bordered()
             .border_type(BorderType::Rounded)
             .title_alignment(ratatui::layout::Alignment::Left)
             .style(
-                Style::new(), //.bg(Color::from_u32(theme.normal.background))
+                Style::new(), //.bg(Color::from(theme.normal.background))
             ))
     }
 }
@@ -53,53 +309,59 @@ pub fn match_color_theme_for_widgets(
 ) -> Result<Style, Box<dyn std::error::Error>> {
     match widget {
         WidgetType::Paragraph => match theme_state {
-            ThemeState::Focus => Ok(Style::new().fg(Color::from_u32(theme.focus.foreground))),
-            ThemeState::Normal => todo!(),
+            ThemeState::Focus => Ok(Style::new().fg(Color::from(theme.focus.foreground))),
+            ThemeState::Normal => Ok(Style::new().fg(Color::from(theme.normal.foreground))),
         },
         WidgetType::Tab => match theme_state {
-            ThemeState::Focus => Ok(Style::new().fg(Color::from_u32(theme.focus.foreground))),
-            ThemeState::Normal => Ok(Style::new().fg(Color::from_u32(theme.normal.foreground))),
+            ThemeState::Focus => Ok(Style::new().fg(Color::from(theme.focus.foreground))),
+            ThemeState::Normal => Ok(Style::new().fg(Color::from(theme.normal.foreground))),
         },
         WidgetType::List => match theme_state {
             ThemeState::Focus => Ok(Style::new()
-                .fg(Color::from_u32(theme.focus.foreground))
-                .bg(Color::from_u32(theme.focus.highlight))),
-            ThemeState::Normal => Ok(Style::new().fg(Color::from_u32(theme.normal.foreground))),
+                .fg(Color::from(theme.selection.foreground))
+                .bg(Color::from(theme.selection.background))),
+            ThemeState::Normal => Ok(Style::new().fg(Color::from(theme.normal.foreground))),
         },
     }
 }
 
 // set the border when input elements are focused
-pub fn set_input_block<'a>(is_focused: bool) -> Block<'a> {
-    let theme = get_theme().unwrap();
+pub fn set_input_block<'a>(theme: &Config, is_focused: bool) -> Block<'a> {
     let block = Block::bordered()
         .border_type(BorderType::Rounded)
         .border_style(if is_focused {
-            Color::from_u32(theme.focus.border)
+            Color::from(theme.focus.border)
         } else {
-            Color::from_u32(theme.normal.border)
+            Color::from(theme.normal.border)
         });
     block
 }
 
-pub fn set_button_block<'a>(is_focused: bool) -> Block<'a> {
-    let theme = get_theme().unwrap();
+pub fn set_button_block<'a>(theme: &Config, is_focused: bool) -> Block<'a> {
     let block = Block::bordered()
         .border_type(BorderType::Rounded)
         .border_style(if is_focused {
-            Color::from_u32(theme.focus.border)
+            Color::from(theme.focus.border)
         } else {
-            Color::from_u32(theme.normal.border)
+            Color::from(theme.normal.border)
         });
     block
 }
 
-pub fn set_button_style(is_focused: bool) -> Style {
-    let theme = get_theme().unwrap();
+pub fn set_button_style(theme: &Config, is_focused: bool) -> Style {
     let style = Style::new().fg(if is_focused {
-        Color::from_u32(theme.focus.foreground)
+        Color::from(theme.focus.foreground)
     } else {
-        Color::from_u32(theme.normal.foreground)
+        Color::from(theme.normal.foreground)
     });
     style
 }
+
+// popups use the error colors when they report a failure
+pub fn popup_block<'a>(theme: &Config, is_error: bool) -> Block<'a> {
+    let colors = if is_error { &theme.error } else { &theme.popup };
+    Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(Color::from(colors.border))
+        .style(Style::new().fg(Color::from(colors.foreground)))
+}
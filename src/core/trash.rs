@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::components::structs::RequestStructure;
+use crate::constants::TRASH_DIR_NAME;
+use crate::core::request_parser;
+use crate::core::workspace::Workspace;
+
+const ENTRY_FILE_NAME: &str = "entry.json";
+
+// a deleted collection, folder or request, kept in `.trash/<id>/` next to `entry.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    #[serde(skip)]
+    pub id: String,
+    // where the entry was, relative to the workspace like `api/users/get-user`
+    pub path: String,
+    pub deleted_at: u64,
+    // a request which shared its file with other requests is kept on its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<RequestStructure>,
+}
+
+fn trash_path(workspace: &Workspace) -> PathBuf {
+    workspace.root.join(TRASH_DIR_NAME)
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Moves a collection, folder or request, given relative to the workspace, into the trash.
+/// Returns the id to restore it with.
+pub fn move_to_trash(workspace: &Workspace, entry: &str) -> anyhow::Result<String> {
+    let path = workspace.collections_path().join(entry);
+    let (source, _) = request_parser::split_request_path(&path);
+    if !source.exists() {
+        anyhow::bail!("{} does not exist", entry);
+    }
+
+    let deleted_at = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    // deletes made within the same millisecond still get their own folder
+    let mut id = deleted_at.as_millis().to_string();
+    while trash_path(workspace).join(&id).exists() {
+        id.push('0');
+    }
+    let item_path = trash_path(workspace).join(&id);
+    fs::create_dir_all(&item_path)?;
+
+    let mut trashed = TrashEntry {
+        id: id.clone(),
+        path: entry.to_string(),
+        deleted_at: deleted_at.as_secs(),
+        request: None,
+    };
+    if source == path || request_parser::list_requests(&source).len() <= 1 {
+        trashed.path = entry.split('#').next().unwrap_or(entry).to_string();
+        fs::rename(&source, item_path.join(file_name(&trashed.path)))?;
+    } else {
+        trashed.request = Some(request_parser::read_request(&path)?);
+        request_parser::delete_request(&path)?;
+    }
+    fs::write(
+        item_path.join(ENTRY_FILE_NAME),
+        serde_json::to_string_pretty(&trashed)?,
+    )?;
+
+    Ok(id)
+}
+
+/// The entries of the trash, the most recently deleted first.
+pub fn list(workspace: &Workspace) -> Vec<TrashEntry> {
+    let mut entries: Vec<TrashEntry> = fs::read_dir(trash_path(workspace))
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let item = item.ok()?;
+            let raw_data = fs::read_to_string(item.path().join(ENTRY_FILE_NAME)).ok()?;
+            let mut entry: TrashEntry = serde_json::from_str(&raw_data).ok()?;
+            entry.id = item.file_name().to_string_lossy().to_string();
+            Some(entry)
+        })
+        .collect();
+    entries.sort_by(|a, b| b.id.cmp(&a.id));
+    entries
+}
+
+/// Puts an entry of the trash back where it was deleted from, and returns its path.
+pub fn restore(workspace: &Workspace, id: &str) -> anyhow::Result<String> {
+    let item_path = trash_path(workspace).join(id);
+    let raw_data = fs::read_to_string(item_path.join(ENTRY_FILE_NAME))
+        .map_err(|_| anyhow::anyhow!("{} is not in the trash", id))?;
+    let entry: TrashEntry = serde_json::from_str(&raw_data)?;
+
+    let file_path = workspace
+        .collections_path()
+        .join(entry.path.split('#').next().unwrap_or_default());
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let restored = match entry.request {
+        // the request goes back at the end of the file it was deleted from
+        Some(request) => {
+            let count = request_parser::list_requests(&file_path).len();
+            let mut request_path = file_path.clone().into_os_string();
+            request_path.push(format!("#{}", count + 1));
+            request_parser::update_request(&PathBuf::from(request_path), request)?;
+
+            let file = entry.path.split('#').next().unwrap_or_default();
+            match count {
+                0 => file.to_string(),
+                _ => format!("{}#{}", file, count + 1),
+            }
+        }
+        None => {
+            if file_path.exists() {
+                anyhow::bail!("{} already exists", entry.path);
+            }
+            fs::rename(item_path.join(file_name(&entry.path)), &file_path)?;
+            entry.path
+        }
+    };
+
+    fs::remove_dir_all(item_path)?;
+    Ok(restored)
+}
+
+/// Removes an entry from the trash for good.
+pub fn purge(workspace: &Workspace, id: &str) -> anyhow::Result<()> {
+    fs::remove_dir_all(trash_path(workspace).join(id))?;
+    Ok(())
+}
+
+pub fn purge_all(workspace: &Workspace) -> anyhow::Result<()> {
+    let trash_path = trash_path(workspace);
+    if trash_path.exists() {
+        fs::remove_dir_all(trash_path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a workspace with the `api` collection holding `users.json`
+    fn workspace(name: &str) -> Workspace {
+        let root =
+            std::env::temp_dir().join(format!("fetched-trash-{}-{}", name, std::process::id()));
+        let workspace = Workspace::load(root);
+        let collection = workspace.collections_path().join("api");
+        fs::create_dir_all(&collection).unwrap();
+        let request = RequestStructure {
+            method: "GET".to_string(),
+            url: "http://localhost/users".to_string(),
+            ..RequestStructure::default()
+        };
+        request_parser::update_request(&collection.join("users.json"), request).unwrap();
+        workspace
+    }
+
+    #[test]
+    fn trashes_and_restores_a_whole_file() {
+        let workspace = workspace("file");
+        let file = workspace.collections_path().join("api/users.json");
+        let raw_data = fs::read_to_string(&file).unwrap();
+
+        let id = move_to_trash(&workspace, "api/users.json").unwrap();
+        assert!(!file.exists());
+        let entries = list(&workspace);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            (entries[0].id.as_str(), entries[0].path.as_str()),
+            (id.as_str(), "api/users.json")
+        );
+
+        assert_eq!(restore(&workspace, &id).unwrap(), "api/users.json");
+        assert_eq!(fs::read_to_string(&file).unwrap(), raw_data);
+        assert!(list(&workspace).is_empty());
+        fs::remove_dir_all(&workspace.root).unwrap();
+    }
+
+    #[test]
+    fn trashes_a_single_request_of_a_shared_file() {
+        let workspace = workspace("shared");
+        let file = workspace.collections_path().join("api/shared.http");
+        fs::write(
+            &file,
+            "GET http://localhost/users\n\n###\nGET http://localhost/teams\n",
+        )
+        .unwrap();
+
+        let id = move_to_trash(&workspace, "api/shared.http#1").unwrap();
+        assert_eq!(request_parser::list_requests(&file), ["shared.http"]);
+        let left = request_parser::read_request(&file).unwrap();
+        assert_eq!(left.url, "http://localhost/teams");
+
+        // it goes back at the end of the file
+        assert_eq!(restore(&workspace, &id).unwrap(), "api/shared.http#2");
+        let restored = request_parser::read_request(&file.with_file_name("shared.http#2")).unwrap();
+        assert_eq!(restored.url, "http://localhost/users");
+        fs::remove_dir_all(&workspace.root).unwrap();
+    }
+
+    #[test]
+    fn does_not_restore_over_an_existing_file() {
+        let workspace = workspace("existing");
+        let file = workspace.collections_path().join("api/users.json");
+        let id = move_to_trash(&workspace, "api/users.json").unwrap();
+        fs::write(&file, "{}").unwrap();
+
+        let err = restore(&workspace, &id).unwrap_err();
+        assert_eq!(err.to_string(), "api/users.json already exists");
+        assert_eq!(fs::read_to_string(&file).unwrap(), "{}");
+        // the entry stays in the trash
+        assert_eq!(list(&workspace).len(), 1);
+        fs::remove_dir_all(&workspace.root).unwrap();
+    }
+
+    #[test]
+    fn purges_entries_for_good() {
+        let workspace = workspace("purge");
+        let first = move_to_trash(&workspace, "api/users.json").unwrap();
+        let second = move_to_trash(&workspace, "api").unwrap();
+        assert_eq!(list(&workspace).len(), 2);
+
+        purge(&workspace, &first).unwrap();
+        assert_eq!(list(&workspace)[0].id, second);
+        purge_all(&workspace).unwrap();
+        assert!(list(&workspace).is_empty());
+        assert!(!trash_path(&workspace).exists());
+        assert!(restore(&workspace, &second).is_err());
+        fs::remove_dir_all(&workspace.root).unwrap();
+    }
+}
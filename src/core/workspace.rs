@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::constants::{RECENT_WORKSPACES_FILE, WORKSPACE_FILE_NAME};
+use crate::core::enums::RequestFileFormat;
+use crate::core::handler;
+
+// how many workspaces the switcher remembers
+const RECENT_WORKSPACES_LIMIT: usize = 10;
+
+// settings shared by everyone working in the workspace, read from `fetched.toml` which
+// also marks the root of the workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    #[serde(default)]
+    pub request_format: RequestFileFormat,
+    // the folder holding the collections, relative to the root of the workspace
+    #[serde(default = "default_collections_dir")]
+    pub collections_dir: String,
+}
+
+fn default_collections_dir() -> String {
+    "collections".to_string()
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        WorkspaceConfig {
+            request_format: RequestFileFormat::default(),
+            collections_dir: default_collections_dir(),
+        }
+    }
+}
+
+/// The opened workspace, along with its settings read once when it is opened.
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub config: WorkspaceConfig,
+}
+
+impl Workspace {
+    /// The workspace whose root is `root`, without a `fetched.toml` the default settings apply.
+    pub fn load(root: PathBuf) -> Self {
+        let config = fs::read_to_string(root.join(WORKSPACE_FILE_NAME))
+            .ok()
+            .and_then(|raw_data| toml::from_str(&raw_data).ok())
+            .unwrap_or_default();
+        Workspace { root, config }
+    }
+
+    /// The workspace of the working directory. Without a `fetched.toml` above it the working
+    /// directory itself is the root.
+    pub fn current() -> Self {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        Workspace::load(discover(&cwd).unwrap_or(cwd))
+    }
+
+    /// The folder whose folders are the collections, request paths are relative to it.
+    pub fn collections_path(&self) -> PathBuf {
+        self.root.join(&self.config.collections_dir)
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RecentWorkspaces {
+    #[serde(default)]
+    recent: Vec<PathBuf>,
+}
+
+/// The closest folder holding a `fetched.toml`, walking up from `start`.
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|folder| folder.join(WORKSPACE_FILE_NAME).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Opens the workspace `path` is in. A folder which is not part of a workspace yet becomes
+/// one by writing a `fetched.toml` into it.
+pub fn open(path: &Path) -> anyhow::Result<Workspace> {
+    let path = path
+        .canonicalize()
+        .map_err(|_| anyhow::anyhow!("{} does not exist", path.display()))?;
+    if !path.is_dir() {
+        anyhow::bail!("{} is not a folder", path.display());
+    }
+
+    let root = match discover(&path) {
+        Some(root) => root,
+        None => {
+            fs::write(
+                path.join(WORKSPACE_FILE_NAME),
+                toml::to_string_pretty(&WorkspaceConfig::default())?,
+            )?;
+            path
+        }
+    };
+    remember(&root);
+    Ok(Workspace::load(root))
+}
+
+fn recent_workspaces_path() -> PathBuf {
+    handler::get_project_path()
+        .unwrap_or_default()
+        .join(RECENT_WORKSPACES_FILE)
+}
+
+/// The workspaces opened before, the last one first.
+pub fn recent() -> Vec<PathBuf> {
+    fs::read_to_string(recent_workspaces_path())
+        .ok()
+        .and_then(|raw_data| toml::from_str::<RecentWorkspaces>(&raw_data).ok())
+        .unwrap_or_default()
+        .recent
+        .into_iter()
+        .filter(|root| root.join(WORKSPACE_FILE_NAME).is_file())
+        .collect()
+}
+
+// the list is only a convenience, failing to write it does not stop the workspace from opening
+fn remember(root: &Path) {
+    let mut workspaces = RecentWorkspaces { recent: recent() };
+    workspaces.recent.retain(|recent| recent != root);
+    workspaces.recent.insert(0, root.to_path_buf());
+    workspaces.recent.truncate(RECENT_WORKSPACES_LIMIT);
+
+    if let Ok(raw_data) = toml::to_string_pretty(&workspaces) {
+        let path = recent_workspaces_path();
+        _ = fs::create_dir_all(path.parent().unwrap_or(&path));
+        _ = fs::write(path, raw_data);
+    }
+}
+
+/// Remembers the workspace found from the working directory, so the switcher offers it later.
+pub fn remember_current(workspace: &Workspace) {
+    if workspace.root.join(WORKSPACE_FILE_NAME).is_file() {
+        remember(&workspace.root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_settings_of_the_workspace_once_it_is_loaded() {
+        let root = std::env::temp_dir().join(format!("fetched-workspace-{}", std::process::id()));
+        let nested = root.join("api").join("users");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            root.join(WORKSPACE_FILE_NAME),
+            "request_format = \"yaml\"\ncollections_dir = \"requests\"\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::load(discover(&nested).unwrap());
+        assert_eq!(workspace.root, root);
+        assert_eq!(workspace.collections_path(), root.join("requests"));
+        assert_eq!(workspace.config.request_format, RequestFileFormat::Yaml);
+
+        // a folder outside of any workspace gets the default settings
+        let workspace = Workspace::load(nested.clone());
+        assert_eq!(workspace.collections_path(), nested.join("collections"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
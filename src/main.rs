@@ -5,12 +5,59 @@ mod constants;
 mod core;
 
 use components::structs::App;
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use crossterm::ExecutableCommand;
+use std::io::stdout;
+
+// `--workspace <path>` opens another workspace than the one of the working directory
+fn take_workspace_flag(args: &mut Vec<String>) -> Option<String> {
+    let index = args.iter().position(|arg| arg.starts_with("--workspace"))?;
+    let flag = args.remove(index);
+    match flag.strip_prefix("--workspace=") {
+        Some(path) => Some(path.to_string()),
+        None if index < args.len() => Some(args.remove(index)),
+        None => Some("".to_string()),
+    }
+}
 
 fn main() -> Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let workspace = match take_workspace_flag(&mut args) {
+        Some(path) if path.is_empty() => {
+            eprintln!("Usage: fetched --workspace <path>");
+            std::process::exit(1);
+        }
+        Some(path) => match core::workspace::open(&core::helpers::expand_path(&path)) {
+            Ok(workspace) => workspace,
+            Err(err) => {
+                eprintln!("Failed to open the workspace: {}", err);
+                std::process::exit(1);
+            }
+        },
+        None => core::workspace::Workspace::current(),
+    };
+
+    if let [command, format, file] = args.as_slice() {
+        if command == "export" {
+            match core::handler::export_request_file(&workspace, format, file) {
+                Ok(snippet) => println!("{}", snippet),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+    }
+
     core::handler::create_config_folder();
+    core::workspace::remember_current(&workspace);
     color_eyre::install()?;
     let terminal = ratatui::init();
-    let app_result = App::new().run(terminal);
+    // pasted text arrives as a whole instead of as single key presses
+    _ = stdout().execute(EnableBracketedPaste);
+    let app_result = App::new(workspace).run(terminal);
+    _ = stdout().execute(DisableBracketedPaste);
     ratatui::restore();
     app_result
 }
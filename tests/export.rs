@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// the request fixture and the golden snippets live in test/export
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test")
+        .join("export")
+}
+
+fn assert_matches_golden_file(format: &str) {
+    assert_export_matches(format, "request", &format!("{}.golden", format));
+}
+
+fn assert_export_matches(format: &str, request: &str, golden_file: &str) {
+    let output = Command::new(env!("CARGO_BIN_EXE_fetched"))
+        .args(["export", format, request])
+        .current_dir(fixtures_dir())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let golden = fs::read_to_string(fixtures_dir().join(golden_file)).unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), golden);
+}
+
+#[test]
+fn export_as_curl() {
+    assert_matches_golden_file("curl");
+}
+
+#[test]
+fn export_as_httpie() {
+    assert_matches_golden_file("httpie");
+}
+
+#[test]
+fn export_as_reqwest() {
+    assert_matches_golden_file("reqwest");
+}
+
+#[test]
+fn export_as_python() {
+    assert_matches_golden_file("python");
+}
+
+#[test]
+fn export_as_fetch() {
+    assert_matches_golden_file("fetch");
+}
+
+#[test]
+fn export_from_http_file() {
+    assert_export_matches("curl", "requests.http#2", "http.golden");
+}
+
+#[test]
+fn export_with_unknown_format_fails() {
+    let output = Command::new(env!("CARGO_BIN_EXE_fetched"))
+        .args(["export", "cobol", "request"])
+        .current_dir(fixtures_dir())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn export_of_broken_request_reports_position() {
+    let output = Command::new(env!("CARGO_BIN_EXE_fetched"))
+        .args(["export", "curl", "broken"])
+        .current_dir(fixtures_dir())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "broken:5:3: trailing comma\n"
+    );
+}